@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+use crate::err::YukinoError;
+
+#[derive(Error, Debug)]
+pub enum MigrationError {
+    #[error("QueryError: {0}")]
+    QueryError(#[from] sqlx::Error),
+    #[error(
+        "UnsupportedModification: column `{1}` of table `{0}` cannot be modified on this database"
+    )]
+    UnsupportedModification(String, String),
+    #[error("IrreversibleMigration: migration {0} cannot be rolled back")]
+    IrreversibleMigration(i64),
+}
+
+impl YukinoError for MigrationError {}
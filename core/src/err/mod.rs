@@ -1,5 +1,7 @@
 pub use base::*;
 pub use convert::*;
+pub use migration::*;
 
 mod base;
 mod convert;
+mod migration;
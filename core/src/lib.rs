@@ -1,4 +1,5 @@
 pub mod err;
+pub mod migration;
 pub mod operator;
 pub mod query;
 pub mod view;
@@ -0,0 +1,203 @@
+use interface::EntityDefinition;
+use query_builder::{
+    AlterTable, CreateIndex, CreateTable, Dialect, DropIndex, DropTable, QueryBuildState, ToSql,
+};
+
+use crate::err::MigrationError;
+use crate::migration::{DatabaseSchema, TableSchema};
+
+#[derive(Clone, Debug)]
+pub struct MigrationStep {
+    pub up: String,
+    pub down: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Migration {
+    pub steps: Vec<MigrationStep>,
+}
+
+struct MigrationPlanner {
+    dialect: &'static dyn Dialect,
+    steps: Vec<MigrationStep>,
+}
+
+impl Migration {
+    // Tables not belonging to any of `entities` are left untouched
+    pub fn diff(
+        entities: &[EntityDefinition],
+        schema: &DatabaseSchema,
+        dialect: &'static dyn Dialect,
+    ) -> Result<Self, MigrationError> {
+        let mut planner = MigrationPlanner {
+            dialect,
+            steps: vec![],
+        };
+
+        for entity in sort_by_dependency(entities) {
+            match schema.tables.get(&entity.table_name) {
+                Some(table) => planner.alter_table(entity, table)?,
+                None => planner.create_table(entity),
+            }
+        }
+
+        Ok(Migration {
+            steps: planner.steps,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn up(&self) -> Vec<String> {
+        self.steps.iter().map(|step| step.up.clone()).collect()
+    }
+
+    pub fn down(&self) -> Option<Vec<String>> {
+        self.steps
+            .iter()
+            .rev()
+            .map(|step| step.down.clone())
+            .collect()
+    }
+}
+
+impl MigrationPlanner {
+    fn render(&self, query: &dyn ToSql) -> String {
+        let mut state = QueryBuildState::create(self.dialect);
+        query.to_sql(&mut state).unwrap();
+        state.to_string()
+    }
+
+    fn push(&mut self, up: &dyn ToSql, down: Option<&dyn ToSql>) {
+        let step = MigrationStep {
+            up: self.render(up),
+            down: down.map(|query| self.render(query)),
+        };
+
+        self.steps.push(step);
+    }
+
+    fn create_table(&mut self, entity: &EntityDefinition) {
+        self.push(
+            &CreateTable::from_definition(entity),
+            Some(&DropTable::from_definition(entity)),
+        );
+
+        for index in &entity.indexes {
+            self.push(
+                &CreateIndex::from_definition(entity.table_name.clone(), index.clone()),
+                Some(&DropIndex::named(
+                    entity.table_name.clone(),
+                    index.name.clone(),
+                )),
+            );
+        }
+    }
+
+    fn alter_table(
+        &mut self,
+        entity: &EntityDefinition,
+        table: &TableSchema,
+    ) -> Result<(), MigrationError> {
+        let table_name = &entity.table_name;
+
+        for index in table
+            .indexes
+            .iter()
+            .filter(|index| !entity.indexes.contains(index))
+        {
+            self.push(
+                &DropIndex::named(table_name.clone(), index.name.clone()),
+                Some(&CreateIndex::from_definition(
+                    table_name.clone(),
+                    index.clone(),
+                )),
+            );
+        }
+
+        for column in entity.columns() {
+            match table.column(&column.name) {
+                None => self.push(
+                    &AlterTable::add_column(table_name.clone(), column.clone()),
+                    Some(&AlterTable::drop_column(
+                        table_name.clone(),
+                        column.name.clone(),
+                    )),
+                ),
+                Some(existing) if !existing.matches(column, self.dialect) => {
+                    if !self.dialect.supports_modify_column() {
+                        return Err(MigrationError::UnsupportedModification(
+                            table_name.clone(),
+                            column.name.clone(),
+                        ));
+                    }
+
+                    let down = existing
+                        .definition(self.dialect)
+                        .map(|previous| AlterTable::modify_column(table_name.clone(), previous));
+                    self.push(
+                        &AlterTable::modify_column(table_name.clone(), column.clone()),
+                        down.as_ref().map(|query| query as &dyn ToSql),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        for existing in table
+            .columns
+            .iter()
+            .filter(|existing| !entity.columns().any(|c| c.name == existing.name))
+        {
+            let down = existing
+                .definition(self.dialect)
+                .map(|previous| AlterTable::add_column(table_name.clone(), previous));
+            self.push(
+                &AlterTable::drop_column(table_name.clone(), existing.name.clone()),
+                down.as_ref().map(|query| query as &dyn ToSql),
+            );
+        }
+
+        for index in entity
+            .indexes
+            .iter()
+            .filter(|index| !table.indexes.contains(index))
+        {
+            self.push(
+                &CreateIndex::from_definition(table_name.clone(), index.clone()),
+                Some(&DropIndex::named(table_name.clone(), index.name.clone())),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+// Referenced tables are created before the tables referencing them
+fn sort_by_dependency(entities: &[EntityDefinition]) -> Vec<&EntityDefinition> {
+    let mut sorted: Vec<&EntityDefinition> = vec![];
+    let mut remaining: Vec<&EntityDefinition> = entities.iter().collect();
+
+    while !remaining.is_empty() {
+        let (ready, rest): (Vec<_>, Vec<_>) = remaining.iter().partition(|entity| {
+            entity.associations.iter().all(|association| {
+                association.referenced_table_name == entity.table_name
+                    || !remaining
+                        .iter()
+                        .any(|r| r.table_name == association.referenced_table_name)
+            })
+        });
+
+        if ready.is_empty() {
+            sorted.extend(rest);
+            break;
+        }
+
+        sorted.extend(ready);
+        remaining = rest;
+    }
+
+    sorted
+}
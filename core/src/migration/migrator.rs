@@ -0,0 +1,288 @@
+use std::marker::PhantomData;
+
+use sqlx::{
+    Acquire, ColumnIndex, Connection, Database, Decode, Executor, IntoArguments, query, Row, Type,
+};
+use sqlx::database::HasArguments;
+
+use interface::{ColumnDefinition, DatabaseType, EntityDefinition, FieldDefinition, YukinoEntity};
+use query_builder::{AppendToArgs, CreateTable, DatabaseValue, HasDialect, QueryBuildState, ToSql};
+
+use crate::err::MigrationError;
+use crate::migration::{DatabaseSchema, Migration};
+
+pub const MIGRATION_HISTORY_TABLE: &str = "__yukino_migrations";
+
+#[derive(Clone, Debug)]
+pub struct MigrationRecord {
+    pub version: i64,
+    pub name: String,
+    pub up: Vec<String>,
+    pub down: Option<Vec<String>>,
+}
+
+pub struct Migrator<DB: HasDialect> {
+    entities: Vec<EntityDefinition>,
+    _db: PhantomData<DB>,
+}
+
+impl<DB: HasDialect> Migrator<DB> {
+    pub fn create() -> Self {
+        Migrator {
+            entities: vec![],
+            _db: Default::default(),
+        }
+    }
+
+    pub fn register<E: YukinoEntity>(&mut self) -> &mut Self {
+        self.entities.push(E::definition());
+
+        self
+    }
+
+    pub fn entities(&self) -> &[EntityDefinition] {
+        &self.entities
+    }
+}
+
+impl<DB: HasDialect> Default for Migrator<DB> {
+    fn default() -> Self {
+        Self::create()
+    }
+}
+
+impl<DB: HasDialect> Migrator<DB>
+where
+    for<'e> &'e mut DB::Connection: Executor<'e, Database = DB>,
+    for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+    DatabaseValue: for<'q> AppendToArgs<'q, DB>,
+    for<'r> String: Decode<'r, DB> + Type<DB>,
+    for<'r> i64: Decode<'r, DB> + Type<DB>,
+    for<'r> Vec<u8>: Decode<'r, DB> + Type<DB>,
+    usize: ColumnIndex<<DB as Database>::Row>,
+{
+    pub async fn diff<'c, A: Acquire<'c, Database = DB>>(
+        &self,
+        acquire: A,
+    ) -> Result<Migration, MigrationError> {
+        let mut connection = acquire.acquire().await?;
+        let schema = DatabaseSchema::load::<DB>(&mut *connection).await?;
+
+        Migration::diff(&self.entities, &schema, DB::dialect())
+    }
+
+    pub async fn history<'c, A: Acquire<'c, Database = DB>>(
+        &self,
+        acquire: A,
+    ) -> Result<Vec<MigrationRecord>, MigrationError> {
+        let mut connection = acquire.acquire().await?;
+        Self::create_history_table(&mut *connection).await?;
+
+        Self::load_history(&mut *connection).await
+    }
+
+    // Diff the registered entities against the database and apply the result as a new migration,
+    // nothing is recorded if the schema is up to date.
+    // Statements run in a transaction, which is not atomic on MySQL since every DDL statement
+    // commits implicitly there. A failed migration may be left partially applied and unrecorded.
+    pub async fn apply<'c, A: Acquire<'c, Database = DB>>(
+        &self,
+        acquire: A,
+        name: &str,
+    ) -> Result<Option<MigrationRecord>, MigrationError> {
+        let mut connection = acquire.acquire().await?;
+        Self::create_history_table(&mut *connection).await?;
+
+        let mut transaction = connection.begin().await?;
+        let schema = DatabaseSchema::load::<DB>(&mut *transaction).await?;
+        let migration = Migration::diff(&self.entities, &schema, DB::dialect())?;
+        if migration.is_empty() {
+            return Ok(None);
+        }
+
+        let version = Self::load_history(&mut *transaction)
+            .await?
+            .last()
+            .map_or(1, |record| record.version + 1);
+        let record = MigrationRecord {
+            version,
+            name: name.to_string(),
+            up: migration.up(),
+            down: migration.down(),
+        };
+
+        for statement in &record.up {
+            query(statement).execute(&mut *transaction).await?;
+        }
+        Self::insert_history(&mut *transaction, &record).await?;
+        transaction.commit().await?;
+
+        Ok(Some(record))
+    }
+
+    // Roll back the latest applied migration, which is not atomic on MySQL either, see `apply`
+    pub async fn rollback<'c, A: Acquire<'c, Database = DB>>(
+        &self,
+        acquire: A,
+    ) -> Result<Option<MigrationRecord>, MigrationError> {
+        let mut connection = acquire.acquire().await?;
+        Self::create_history_table(&mut *connection).await?;
+
+        let mut transaction = connection.begin().await?;
+        let record = match Self::load_history(&mut *transaction).await?.pop() {
+            Some(record) => record,
+            None => return Ok(None),
+        };
+        let down = record
+            .down
+            .as_ref()
+            .ok_or(MigrationError::IrreversibleMigration(record.version))?;
+
+        for statement in down {
+            query(statement).execute(&mut *transaction).await?;
+        }
+        Self::delete_history(&mut *transaction, record.version).await?;
+        transaction.commit().await?;
+
+        Ok(Some(record))
+    }
+
+    async fn create_history_table(connection: &mut DB::Connection) -> Result<(), MigrationError> {
+        let mut create_table = CreateTable::from_definition(&history_definition());
+        create_table.if_not_exists();
+        let mut state = QueryBuildState::create(DB::dialect());
+        create_table.to_sql(&mut state).unwrap();
+
+        query(&state.to_string()).execute(connection).await?;
+
+        Ok(())
+    }
+
+    async fn load_history(
+        connection: &mut DB::Connection,
+    ) -> Result<Vec<MigrationRecord>, MigrationError> {
+        let dialect = DB::dialect();
+        let statement = format!(
+            "SELECT {}, {}, {}, {} FROM {} ORDER BY {}",
+            dialect.quote_ident("version"),
+            dialect.quote_ident("name"),
+            dialect.quote_ident("up"),
+            dialect.quote_ident("down"),
+            dialect.quote_ident(MIGRATION_HISTORY_TABLE),
+            dialect.quote_ident("version"),
+        );
+
+        query(&statement)
+            .fetch_all(connection)
+            .await?
+            .iter()
+            .map(|row| {
+                let up: Vec<u8> = row.try_get(2)?;
+                let down: Option<Vec<u8>> = row.try_get(3)?;
+
+                Ok(MigrationRecord {
+                    version: row.try_get(0)?,
+                    name: row.try_get(1)?,
+                    up: split_statements(up),
+                    down: down.map(split_statements),
+                })
+            })
+            .collect()
+    }
+
+    async fn insert_history(
+        connection: &mut DB::Connection,
+        record: &MigrationRecord,
+    ) -> Result<(), MigrationError> {
+        let dialect = DB::dialect();
+        let statement = format!(
+            "INSERT INTO {} ({}, {}, {}, {}) VALUES ({}, {}, {}, {})",
+            dialect.quote_ident(MIGRATION_HISTORY_TABLE),
+            dialect.quote_ident("version"),
+            dialect.quote_ident("name"),
+            dialect.quote_ident("up"),
+            dialect.quote_ident("down"),
+            dialect.placeholder(1),
+            dialect.placeholder(2),
+            dialect.placeholder(3),
+            dialect.placeholder(4),
+        );
+        let values = vec![
+            DatabaseValue::BigInteger(record.version),
+            DatabaseValue::String(record.name.clone()),
+            DatabaseValue::Binary(join_statements(&record.up)),
+            record
+                .down
+                .as_ref()
+                .map_or(DatabaseValue::Null(DatabaseType::Binary), |down| {
+                    DatabaseValue::Binary(join_statements(down))
+                }),
+        ];
+
+        values
+            .into_iter()
+            .fold(query(&statement), |query, value| value.bind_on(query))
+            .execute(connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_history(
+        connection: &mut DB::Connection,
+        version: i64,
+    ) -> Result<(), MigrationError> {
+        let dialect = DB::dialect();
+        let statement = format!(
+            "DELETE FROM {} WHERE {} = {}",
+            dialect.quote_ident(MIGRATION_HISTORY_TABLE),
+            dialect.quote_ident("version"),
+            dialect.placeholder(1),
+        );
+
+        DatabaseValue::BigInteger(version)
+            .bind_on(query(&statement))
+            .execute(connection)
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn history_definition() -> EntityDefinition {
+    let field = |name: &str, ty: DatabaseType, optional: bool, primary_key: bool| FieldDefinition {
+        name: name.to_string(),
+        columns: vec![ColumnDefinition {
+            name: name.to_string(),
+            ty,
+            optional,
+            auto_increment: false,
+        }],
+        identity_column: name.to_string(),
+        primary_key,
+    };
+
+    EntityDefinition {
+        table_name: MIGRATION_HISTORY_TABLE.to_string(),
+        associations: vec![],
+        fields: vec![
+            field("version", DatabaseType::BigInteger, false, true),
+            field("name", DatabaseType::String, false, false),
+            field("up", DatabaseType::Binary, false, false),
+            field("down", DatabaseType::Binary, true, false),
+        ],
+        indexes: vec![],
+    }
+}
+
+// Rendered statements never contain line breaks
+fn join_statements(statements: &[String]) -> Vec<u8> {
+    statements.join("\n").into_bytes()
+}
+
+fn split_statements(bytes: Vec<u8>) -> Vec<String> {
+    String::from_utf8_lossy(&bytes)
+        .lines()
+        .map(|line| line.to_string())
+        .collect()
+}
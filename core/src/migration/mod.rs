@@ -0,0 +1,7 @@
+pub use diff::*;
+pub use migrator::*;
+pub use schema::*;
+
+mod diff;
+mod migrator;
+mod schema;
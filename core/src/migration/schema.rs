@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use sqlx::{ColumnIndex, Database, Decode, Error, Executor, IntoArguments, query, Row, Type};
+use sqlx::database::HasArguments;
+
+use interface::{ColumnDefinition, IndexDefinition};
+use query_builder::{Dialect, HasDialect};
+
+#[derive(Clone, Debug)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub ty: String,
+    pub optional: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TableSchema {
+    pub columns: Vec<ColumnSchema>,
+    pub indexes: Vec<IndexDefinition>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DatabaseSchema {
+    pub tables: HashMap<String, TableSchema>,
+}
+
+impl ColumnSchema {
+    pub fn matches(&self, definition: &ColumnDefinition, dialect: &dyn Dialect) -> bool {
+        self.optional == definition.optional
+            && dialect
+                .parse_column_type(&self.ty)
+                .is_some_and(|ty| dialect.column_type(&ty) == dialect.column_type(&definition.ty))
+    }
+
+    pub fn definition(&self, dialect: &dyn Dialect) -> Option<ColumnDefinition> {
+        dialect
+            .parse_column_type(&self.ty)
+            .map(|ty| ColumnDefinition {
+                name: self.name.clone(),
+                ty,
+                optional: self.optional,
+                auto_increment: false,
+            })
+    }
+}
+
+impl TableSchema {
+    pub fn column(&self, name: &str) -> Option<&ColumnSchema> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+}
+
+impl DatabaseSchema {
+    pub async fn load<DB: HasDialect>(connection: &mut DB::Connection) -> Result<Self, Error>
+    where
+        for<'e> &'e mut DB::Connection: Executor<'e, Database = DB>,
+        for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+        for<'r> String: Decode<'r, DB> + Type<DB>,
+        usize: ColumnIndex<<DB as Database>::Row>,
+    {
+        let dialect = DB::dialect();
+        let mut schema = DatabaseSchema::default();
+
+        for row in query(dialect.column_info_query())
+            .fetch_all(&mut *connection)
+            .await?
+        {
+            let table: String = row.try_get(0)?;
+            let nullable: String = row.try_get(3)?;
+            schema
+                .tables
+                .entry(table)
+                .or_default()
+                .columns
+                .push(ColumnSchema {
+                    name: row.try_get(1)?,
+                    ty: row.try_get(2)?,
+                    optional: nullable == "YES",
+                });
+        }
+
+        for row in query(dialect.index_info_query())
+            .fetch_all(&mut *connection)
+            .await?
+        {
+            let table: String = row.try_get(0)?;
+            let name: String = row.try_get(1)?;
+            let column: String = row.try_get(2)?;
+            let unique: String = row.try_get(3)?;
+            let indexes = &mut schema.tables.entry(table).or_default().indexes;
+
+            match indexes.iter_mut().find(|index| index.name == name) {
+                Some(index) => index.columns.push(column),
+                None => indexes.push(IndexDefinition {
+                    name,
+                    columns: vec![column],
+                    unique: unique == "YES",
+                }),
+            }
+        }
+
+        Ok(schema)
+    }
+}
@@ -0,0 +1,46 @@
+use generic_array::arr;
+use generic_array::typenum::U1;
+
+use interface::DatabaseType;
+use query_builder::{Expr, FunctionCall, ScalarFunction, ScalarFunctionCall};
+
+use crate::view::{AnyTagExprView, ExprViewBoxWithTag, SingleExprView, TagList, Value};
+
+pub trait ExprLength: Value {
+    type Result: Value<L = U1>;
+
+    // Length in bytes, it is cast since the type returned by LENGTH varies across databases
+    fn expr_length<TTags: TagList>(
+        expr: ExprViewBoxWithTag<Self, TTags>,
+    ) -> ExprViewBoxWithTag<Self::Result, TTags> {
+        let fn_call = FunctionCall::Scalar(ScalarFunctionCall {
+            function: ScalarFunction::Length,
+            params: vec![expr.collect_expr().into_iter().next().unwrap()],
+        });
+
+        SingleExprView::from_exprs_with_tags(arr![Expr;
+            Expr::Cast(Box::new(Expr::FunctionCall(Box::new(fn_call))), DatabaseType::BigInteger)
+        ])
+    }
+}
+
+impl ExprLength for Vec<u8> {
+    type Result = i64;
+}
+
+impl ExprLength for Option<Vec<u8>> {
+    type Result = Option<i64>;
+}
+
+pub trait Length {
+    type Output;
+    fn length(self) -> Self::Output;
+}
+
+impl<T: ExprLength, TTags: TagList> Length for ExprViewBoxWithTag<T, TTags> {
+    type Output = ExprViewBoxWithTag<T::Result, TTags>;
+
+    fn length(self) -> Self::Output {
+        T::expr_length(self)
+    }
+}
@@ -9,8 +9,9 @@ use sqlx::types::time::{Date, PrimitiveDateTime, Time};
 use query_builder::{DatabaseValue, Expr};
 
 use crate::view::{
-    AnyTagExprView, AnyTagsValue, ConcreteList, ExprViewBoxWithTag, MergeList, SingleExprView,
-    SubqueryFnCallView, SubqueryIntoView, TagList, TagsOfValueView, Value, ValueCountOf,
+    AnyTagExprView, AnyTagsValue, ConcreteList, CustomValue, ExprViewBoxWithTag, InList,
+    MergeList, OrdViewTag, SingleExprView, SubqueryFnCallView, SubqueryIntoView, TagList,
+    TagsOfValueView, Value, ValueCountOf,
 };
 
 pub trait ExprNot: Value + Not {
@@ -65,6 +66,34 @@ where
     }
 }
 
+// Tuples, such as composite keys, are compared row by row
+impl<L: Value, R: Value> ExprIn for (L, R)
+where
+    (L, R): Value,
+{
+    fn expr_in_arr<Tags: TagList, I: IntoIterator<Item = Self>>(
+        e: ExprViewBoxWithTag<Self, Tags>,
+        arr: I,
+    ) -> ExprViewBoxWithTag<bool, Tags> {
+        let columns = e.collect_expr();
+        let result = arr
+            .into_iter()
+            .map(|item| {
+                columns
+                    .iter()
+                    .cloned()
+                    .zip(item.to_database_values())
+                    .map(|(column, value)| Expr::Eq(Box::new(column), Box::new(Expr::Lit(value))))
+                    .reduce(|l, r| Expr::And(Box::new(l), Box::new(r)))
+                    .unwrap()
+            })
+            .reduce(|l, r| Expr::Or(Box::new(l), Box::new(r)))
+            .unwrap_or_else(|| Expr::InArr(Box::new(columns[0].clone()), vec![]));
+
+        SingleExprView::from_exprs_with_tags(arr![Expr; result])
+    }
+}
+
 macro_rules! op_trait {
     (
         $op_trait: ident,
@@ -114,6 +143,42 @@ macro_rules! impl_expr_for {
         )*
     }
 }
+// Custom values are compared as their inner values, ordering requires `OrdViewTag` in their tags
+macro_rules! impl_expr_for_custom_value {
+    (
+        $expr_op_trait: ident,
+        $expr_op_method: ident,
+        $expr_variant: ident,
+        $($bounds: tt)*
+    ) => {
+        impl<T: CustomValue> $expr_op_trait for T where $($bounds)* {
+            type ResultTags<LTags: TagList + MergeList<RTags>, RTags: TagList> = ConcreteList<LTags, RTags>;
+            fn $expr_op_method<LTags: TagList + MergeList<RTags>, RTags: TagList>(
+                l: ExprViewBoxWithTag<Self, LTags>,
+                r: ExprViewBoxWithTag<T, RTags>
+            ) -> ExprViewBoxWithTag<bool, Self::ResultTags<LTags, RTags>> {
+                let l_expr = l.collect_expr().into_iter().next().unwrap();
+                let r_expr = r.collect_expr().into_iter().next().unwrap();
+                let result = Expr::$expr_variant(Box::new(l_expr), Box::new(r_expr));
+                SingleExprView::from_exprs_with_tags(arr![Expr; result])
+            }
+        }
+    }
+}
+macro_rules! impl_op_for_custom_value {
+    ($op_trait: ident, $op_trait_method: ident) => {
+        impl<T: CustomValue> $op_trait for T
+        where
+            T::Inner: $op_trait,
+        {
+            fn $op_trait_method(&self, rhs: &Self) -> bool {
+                self.clone()
+                    .into_inner()
+                    .$op_trait_method(&rhs.clone().into_inner())
+            }
+        }
+    };
+}
 macro_rules! impl_expr_in_for {
     ([$($ty: ty),*])  => {
         $(
@@ -295,14 +360,31 @@ impl_op_for!(>=, Bte, bte, [bool, u16, i16, u32, i32, u64, i64, f32, f64, Decima
 impl_op_for!(<, Lt, lt, [bool, u16, i16, u32, i32, u64, i64, f32, f64, Decimal, String, Date, Time, PrimitiveDateTime]);
 impl_op_for!(<=, Lte, lte, [bool, u16, i16, u32, i32, u64, i64, f32, f64, Decimal, String, Date, Time, PrimitiveDateTime]);
 
-impl_expr_in_for!([bool, u16, i16, u32, i32, u64, i64, f32, f64, Decimal, String]);
+impl_expr_in_for!([bool, u16, i16, u32, i32, u64, i64, f32, f64, Decimal, String, Vec<u8>]);
+
+impl<T: CustomValue> ExprIn for T {
+    fn expr_in_arr<Tags: TagList, L: IntoIterator<Item = Self>>(
+        e: ExprViewBoxWithTag<Self, Tags>,
+        arr: L,
+    ) -> ExprViewBoxWithTag<bool, Tags> {
+        let values = arr
+            .into_iter()
+            .flat_map(|item| item.to_database_values())
+            .collect();
+        let expr = e.collect_expr().into_iter().next().unwrap();
+
+        SingleExprView::from_exprs_with_tags(arr![Expr; Expr::InArr(Box::new(expr), values)])
+    }
+}
 
 impl_bool_operator!(&, And, and, ViewAnd, view_and, ExprAnd, expr_and, And, [bool]);
 impl_bool_operator!(|, Or, or, ViewOr, view_or, ExprOr, expr_or, Or, [bool]);
 impl_bool_operator!(==, PartialEq, eq, ViewEq, view_eq, ExprEq, expr_eq, Eq,
-    [bool, u16, i16, u32, i32, u64, i64, f32, f64, Decimal, String, Date, Time, PrimitiveDateTime]);
+    [bool, u16, i16, u32, i32, u64, i64, f32, f64, Decimal, String, Date, Time, PrimitiveDateTime,
+    Vec<u8>]);
 impl_bool_operator!(!=, PartialEq, ne, ViewNeq, view_neq, ExprNeq, expr_neq, Neq,
-    [bool, u16, i16, u32, i32, u64, i64, f32, f64, Decimal, String, Date, Time, PrimitiveDateTime]);
+    [bool, u16, i16, u32, i32, u64, i64, f32, f64, Decimal, String, Date, Time, PrimitiveDateTime,
+    Vec<u8>]);
 impl_bool_operator!(>, Bt, bt, ViewBt, view_bt, ExprBt, expr_bt, Bt,
     [u16, i16, u32, i32, u64, i64, f32, f64, Decimal, String, Date, Time, PrimitiveDateTime]);
 impl_bool_operator!(>=, Bte, bte, ViewBte, view_bte, ExprBte, expr_bte, Bte,
@@ -312,6 +394,18 @@ impl_bool_operator!(<, Lt, lt, ViewLt, view_lt, ExprLt, expr_lt, Lt,
 impl_bool_operator!(<=, Lte, lte, ViewLte, view_lte, ExprLte, expr_lte, Lte,
     [u16, i16, u32, i32, u64, i64, f32, f64, Decimal, String, Date, Time, PrimitiveDateTime]);
 
+impl_op_for_custom_value!(Bt, bt);
+impl_op_for_custom_value!(Bte, bte);
+impl_op_for_custom_value!(Lt, lt);
+impl_op_for_custom_value!(Lte, lte);
+
+impl_expr_for_custom_value!(ExprEq, expr_eq, Eq, T: PartialEq);
+impl_expr_for_custom_value!(ExprNeq, expr_neq, Neq, T: PartialEq);
+impl_expr_for_custom_value!(ExprBt, expr_bt, Bt, T: Bt, OrdViewTag: InList<T::Tags>);
+impl_expr_for_custom_value!(ExprBte, expr_bte, Bte, T: Bte, OrdViewTag: InList<T::Tags>);
+impl_expr_for_custom_value!(ExprLt, expr_lt, Lt, T: Lt, OrdViewTag: InList<T::Tags>);
+impl_expr_for_custom_value!(ExprLte, expr_lte, Lte, T: Lte, OrdViewTag: InList<T::Tags>);
+
 generate_macro!(and, ViewAnd, view_and);
 generate_macro!(or, ViewOr, view_or);
 generate_macro!(eq, ViewEq, view_eq);
@@ -0,0 +1,89 @@
+use std::marker::PhantomData;
+
+use generic_array::arr;
+use generic_array::typenum::U1;
+
+use query_builder::Expr;
+
+use crate::view::{
+    AnyTagExprView, ConcreteList, ExprViewBoxWithTag, MergeList, SingleExprView, TagList, Value,
+};
+
+pub struct Case;
+
+// Tags of conditions and values are merged as in binary operators
+pub struct CaseWhen<T: Value<L = U1>, TTags: TagList> {
+    branches: Vec<(Expr, Expr)>,
+    _marker: PhantomData<(T, TTags)>,
+}
+
+// `CASE WHEN .. THEN .. ELSE .. END`, branches are added by `when` and finished by `otherwise`
+pub fn case() -> Case {
+    Case
+}
+
+fn branch<T: Value<L = U1>, CTags: TagList, VTags: TagList>(
+    condition: ExprViewBoxWithTag<bool, CTags>,
+    value: ExprViewBoxWithTag<T, VTags>,
+) -> (Expr, Expr) {
+    (
+        condition.collect_expr().into_iter().next().unwrap(),
+        value.collect_expr().into_iter().next().unwrap(),
+    )
+}
+
+impl Case {
+    pub fn when<
+        T: Value<L = U1>,
+        CTags: TagList + MergeList<VTags>,
+        VTags: TagList,
+        C: Into<ExprViewBoxWithTag<bool, CTags>>,
+        V: Into<ExprViewBoxWithTag<T, VTags>>,
+    >(
+        self,
+        condition: C,
+        value: V,
+    ) -> CaseWhen<T, ConcreteList<CTags, VTags>> {
+        CaseWhen {
+            branches: vec![branch(condition.into(), value.into())],
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T: Value<L = U1>, TTags: TagList> CaseWhen<T, TTags> {
+    pub fn when<
+        CTags: TagList + MergeList<VTags>,
+        VTags: TagList,
+        C: Into<ExprViewBoxWithTag<bool, CTags>>,
+        V: Into<ExprViewBoxWithTag<T, VTags>>,
+    >(
+        mut self,
+        condition: C,
+        value: V,
+    ) -> CaseWhen<T, ConcreteList<TTags, ConcreteList<CTags, VTags>>>
+    where
+        TTags: MergeList<ConcreteList<CTags, VTags>>,
+    {
+        self.branches.push(branch(condition.into(), value.into()));
+
+        CaseWhen {
+            branches: self.branches,
+            _marker: Default::default(),
+        }
+    }
+
+    pub fn otherwise<DTags: TagList, D: Into<ExprViewBoxWithTag<T, DTags>>>(
+        self,
+        default: D,
+    ) -> ExprViewBoxWithTag<T, ConcreteList<TTags, DTags>>
+    where
+        TTags: MergeList<DTags>,
+    {
+        let default = default.into().collect_expr().into_iter().next().unwrap();
+
+        SingleExprView::from_exprs_with_tags(arr![Expr;
+            Expr::Case(self.branches, Box::new(default))
+        ])
+    }
+}
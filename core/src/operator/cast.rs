@@ -0,0 +1,23 @@
+use generic_array::arr;
+
+use query_builder::Expr;
+
+use crate::view::{
+    AnyTagExprView, ExprViewBoxWithTag, NullableValue, ScalarValue, SingleExprView, TagList,
+};
+
+pub trait Cast<T: ScalarValue, TTags: TagList> {
+    // `CAST(self AS ..)`, the conversion is done by the database and may fail or lose precision.
+    // The result is nullable if `self` is, e.g. `Option<i32>` is cast to `Option<String>`
+    fn cast<R: ScalarValue + NullableValue>(self) -> ExprViewBoxWithTag<T::CastOf<R>, TTags>;
+}
+
+impl<T: ScalarValue, TTags: TagList> Cast<T, TTags> for ExprViewBoxWithTag<T, TTags> {
+    fn cast<R: ScalarValue + NullableValue>(self) -> ExprViewBoxWithTag<T::CastOf<R>, TTags> {
+        let expr = self.collect_expr().into_iter().next().unwrap();
+
+        SingleExprView::from_exprs_with_tags(arr![Expr;
+            Expr::Cast(Box::new(expr), R::database_type())
+        ])
+    }
+}
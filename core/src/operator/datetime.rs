@@ -0,0 +1,234 @@
+use generic_array::arr;
+use generic_array::typenum::U1;
+use sqlx::types::time::{Date, PrimitiveDateTime, Time};
+
+use interface::DatabaseType;
+use query_builder::{DateTimeFunction, DateTimeFunctionCall, DateTimeUnit, Expr, FunctionCall};
+
+use crate::view::{
+    AnyTagExprView, ConcreteList, ExprViewBoxWithTag, MergeList, ScalarValue, SingleExprView,
+    TagList, TagsOfValueView, Value,
+};
+
+fn datetime_fn_call(function: DateTimeFunction, params: Vec<Expr>, ty: DatabaseType) -> Expr {
+    Expr::FunctionCall(Box::new(FunctionCall::DateTime(DateTimeFunctionCall {
+        function,
+        params,
+        ty,
+    })))
+}
+
+fn single_expr<T: Value<L = U1>, TTags: TagList>(view: ExprViewBoxWithTag<T, TTags>) -> Expr {
+    view.collect_expr().into_iter().next().unwrap()
+}
+
+// Values of `Date`, `Time` and `PrimitiveDateTime`. Integer results are cast for the same reason
+// as `ExprLength`, and other results are cast back to the type of the operand since databases
+// may return strings or timestamps for them
+pub trait ExprDateTime: ScalarValue {
+    type Integer: Value<L = U1>;
+
+    fn expr_extract<TTags: TagList>(
+        unit: DateTimeUnit,
+        expr: ExprViewBoxWithTag<Self, TTags>,
+    ) -> ExprViewBoxWithTag<Self::Integer, TTags> {
+        let fn_call = datetime_fn_call(
+            DateTimeFunction::Extract(unit),
+            vec![single_expr(expr)],
+            Self::database_type(),
+        );
+
+        SingleExprView::from_exprs_with_tags(arr![Expr;
+            Expr::Cast(Box::new(fn_call), DatabaseType::BigInteger)
+        ])
+    }
+
+    fn expr_truncate<TTags: TagList>(
+        unit: DateTimeUnit,
+        expr: ExprViewBoxWithTag<Self, TTags>,
+    ) -> ExprViewBoxWithTag<Self, TTags> {
+        let fn_call = datetime_fn_call(
+            DateTimeFunction::Truncate(unit),
+            vec![single_expr(expr)],
+            Self::database_type(),
+        );
+
+        SingleExprView::from_exprs_with_tags(arr![Expr;
+            Expr::Cast(Box::new(fn_call), Self::database_type())
+        ])
+    }
+
+    fn expr_add_interval<LTags: TagList + MergeList<RTags>, RTags: TagList>(
+        unit: DateTimeUnit,
+        expr: ExprViewBoxWithTag<Self, LTags>,
+        amount: ExprViewBoxWithTag<i32, RTags>,
+    ) -> ExprViewBoxWithTag<Self, ConcreteList<LTags, RTags>> {
+        let fn_call = datetime_fn_call(
+            DateTimeFunction::AddInterval(unit),
+            vec![single_expr(expr), single_expr(amount)],
+            Self::database_type(),
+        );
+
+        SingleExprView::from_exprs_with_tags(arr![Expr;
+            Expr::Cast(Box::new(fn_call), Self::database_type())
+        ])
+    }
+
+    fn expr_diff_in_seconds<LTags: TagList + MergeList<RTags>, RTags: TagList>(
+        l: ExprViewBoxWithTag<Self, LTags>,
+        r: ExprViewBoxWithTag<Self, RTags>,
+    ) -> ExprViewBoxWithTag<Self::Integer, ConcreteList<LTags, RTags>> {
+        let fn_call = datetime_fn_call(
+            DateTimeFunction::DiffInSeconds,
+            vec![single_expr(l), single_expr(r)],
+            Self::database_type(),
+        );
+
+        SingleExprView::from_exprs_with_tags(arr![Expr;
+            Expr::Cast(Box::new(fn_call), DatabaseType::BigInteger)
+        ])
+    }
+}
+
+// Values with a date part
+pub trait ExprDate: ExprDateTime {}
+
+// Values with a time part
+pub trait ExprTime: ExprDateTime {}
+
+macro_rules! impl_datetime {
+    ($ty: ty, [$($traits: ident),*]) => {
+        impl ExprDateTime for $ty {
+            type Integer = i64;
+        }
+
+        impl ExprDateTime for Option<$ty> {
+            type Integer = Option<i64>;
+        }
+
+        $(
+            impl $traits for $ty {}
+            impl $traits for Option<$ty> {}
+        )*
+    };
+}
+
+impl_datetime!(Date, [ExprDate]);
+impl_datetime!(Time, [ExprTime]);
+impl_datetime!(PrimitiveDateTime, [ExprDate, ExprTime]);
+
+pub trait DateTimeFunctions<T: ExprDateTime, TTags: TagList> {
+    fn add_interval<RTags: TagList, R: Into<ExprViewBoxWithTag<i32, RTags>>>(
+        self,
+        amount: R,
+        unit: DateTimeUnit,
+    ) -> ExprViewBoxWithTag<T, ConcreteList<TTags, RTags>>
+    where
+        TTags: MergeList<RTags>;
+
+    fn add_days<RTags: TagList, R: Into<ExprViewBoxWithTag<i32, RTags>>>(
+        self,
+        amount: R,
+    ) -> ExprViewBoxWithTag<T, ConcreteList<TTags, RTags>>
+    where
+        TTags: MergeList<RTags>;
+
+    // `self - other` in seconds
+    fn diff_in_seconds<RTags: TagList, R: Into<ExprViewBoxWithTag<T, RTags>>>(
+        self,
+        other: R,
+    ) -> ExprViewBoxWithTag<T::Integer, ConcreteList<TTags, RTags>>
+    where
+        TTags: MergeList<RTags>;
+}
+
+impl<T: ExprDateTime, TTags: TagList> DateTimeFunctions<T, TTags> for ExprViewBoxWithTag<T, TTags> {
+    fn add_interval<RTags: TagList, R: Into<ExprViewBoxWithTag<i32, RTags>>>(
+        self,
+        amount: R,
+        unit: DateTimeUnit,
+    ) -> ExprViewBoxWithTag<T, ConcreteList<TTags, RTags>>
+    where
+        TTags: MergeList<RTags>,
+    {
+        T::expr_add_interval(unit, self, amount.into())
+    }
+
+    fn add_days<RTags: TagList, R: Into<ExprViewBoxWithTag<i32, RTags>>>(
+        self,
+        amount: R,
+    ) -> ExprViewBoxWithTag<T, ConcreteList<TTags, RTags>>
+    where
+        TTags: MergeList<RTags>,
+    {
+        T::expr_add_interval(DateTimeUnit::Day, self, amount.into())
+    }
+
+    fn diff_in_seconds<RTags: TagList, R: Into<ExprViewBoxWithTag<T, RTags>>>(
+        self,
+        other: R,
+    ) -> ExprViewBoxWithTag<T::Integer, ConcreteList<TTags, RTags>>
+    where
+        TTags: MergeList<RTags>,
+    {
+        T::expr_diff_in_seconds(self, other.into())
+    }
+}
+
+pub trait DateFunctions<T: ExprDate, TTags: TagList> {
+    fn year(self) -> ExprViewBoxWithTag<T::Integer, TTags>;
+
+    fn month(self) -> ExprViewBoxWithTag<T::Integer, TTags>;
+
+    fn day(self) -> ExprViewBoxWithTag<T::Integer, TTags>;
+
+    // Set the parts smaller than `unit` to their minimum, e.g. the first day of the month
+    fn date_trunc(self, unit: DateTimeUnit) -> ExprViewBoxWithTag<T, TTags>;
+}
+
+impl<T: ExprDate, TTags: TagList> DateFunctions<T, TTags> for ExprViewBoxWithTag<T, TTags> {
+    fn year(self) -> ExprViewBoxWithTag<T::Integer, TTags> {
+        T::expr_extract(DateTimeUnit::Year, self)
+    }
+
+    fn month(self) -> ExprViewBoxWithTag<T::Integer, TTags> {
+        T::expr_extract(DateTimeUnit::Month, self)
+    }
+
+    fn day(self) -> ExprViewBoxWithTag<T::Integer, TTags> {
+        T::expr_extract(DateTimeUnit::Day, self)
+    }
+
+    fn date_trunc(self, unit: DateTimeUnit) -> ExprViewBoxWithTag<T, TTags> {
+        T::expr_truncate(unit, self)
+    }
+}
+
+pub trait TimeFunctions<T: ExprTime, TTags: TagList> {
+    fn hour(self) -> ExprViewBoxWithTag<T::Integer, TTags>;
+
+    fn minute(self) -> ExprViewBoxWithTag<T::Integer, TTags>;
+
+    fn second(self) -> ExprViewBoxWithTag<T::Integer, TTags>;
+}
+
+impl<T: ExprTime, TTags: TagList> TimeFunctions<T, TTags> for ExprViewBoxWithTag<T, TTags> {
+    fn hour(self) -> ExprViewBoxWithTag<T::Integer, TTags> {
+        T::expr_extract(DateTimeUnit::Hour, self)
+    }
+
+    fn minute(self) -> ExprViewBoxWithTag<T::Integer, TTags> {
+        T::expr_extract(DateTimeUnit::Minute, self)
+    }
+
+    fn second(self) -> ExprViewBoxWithTag<T::Integer, TTags> {
+        T::expr_extract(DateTimeUnit::Second, self)
+    }
+}
+
+// The current date and time of the database, which is in UTC for SQLite
+pub fn now() -> ExprViewBoxWithTag<PrimitiveDateTime, TagsOfValueView<PrimitiveDateTime>> {
+    SingleExprView::from_exprs_with_tags(arr![Expr;
+        datetime_fn_call(DateTimeFunction::Now, vec![], DatabaseType::DateTime)
+    ])
+}
@@ -0,0 +1,69 @@
+use generic_array::arr;
+use serde_json::Value as JsonValue;
+use sqlx::types::Json as SqlxJson;
+
+use query_builder::{DatabaseValue, Expr, FunctionCall, ScalarFunction, ScalarFunctionCall};
+
+use crate::view::{
+    AddTag, AnyTagExprView, ConcreteList, ExprViewBoxWithTag, Json, JsonObject, MergeList,
+    OrdViewTag, ScalarValue, SetBit, SingleExprView, TagList, TagsOfValueView, True, U0,
+    Value,
+};
+
+// Aggregate and window tags of the JSON expression are kept, the result is ordered if `T` is. The
+// bounds below name the offset of `OrdViewTag` as `U0`, rustc fails to normalize `OffsetOfTag` there
+type JsonExtractTags<TTags, T> = ConcreteList<AddTag<TTags, OrdViewTag>, TagsOfValueView<T>>;
+
+pub trait ExprJsonExtract: Value {
+    fn expr_json_extract<T: ScalarValue, TTags: TagList + SetBit<U0, True>>(
+        expr: ExprViewBoxWithTag<Self, TTags>,
+        path: &str,
+    ) -> ExprViewBoxWithTag<T, JsonExtractTags<TTags, T>>
+    where
+        AddTag<TTags, OrdViewTag>: MergeList<TagsOfValueView<T>>,
+    {
+        let fn_call = FunctionCall::Scalar(ScalarFunctionCall {
+            function: ScalarFunction::JsonExtract,
+            params: vec![
+                expr.collect_expr().into_iter().next().unwrap(),
+                Expr::Lit(DatabaseValue::String(path.to_string())),
+            ],
+        });
+
+        SingleExprView::from_exprs_with_tags(arr![Expr;
+            Expr::Cast(Box::new(Expr::FunctionCall(Box::new(fn_call))), T::database_type())
+        ])
+    }
+}
+
+impl ExprJsonExtract for JsonValue {}
+impl ExprJsonExtract for Option<JsonValue> {}
+impl<T: JsonObject> ExprJsonExtract for SqlxJson<T> {}
+impl<T: JsonObject> ExprJsonExtract for Option<SqlxJson<T>> {}
+impl<T: JsonObject> ExprJsonExtract for Json<T> {}
+impl<T: JsonObject> ExprJsonExtract for Option<Json<T>> {}
+
+pub trait JsonExtract<TTags: TagList + SetBit<U0, True>> {
+    // Extract the value at `path`, e.g. `$.address.city`, as `T`. Use an `Option` as `T` if the
+    // path may be absent
+    fn json_extract<T: ScalarValue>(
+        self,
+        path: &str,
+    ) -> ExprViewBoxWithTag<T, JsonExtractTags<TTags, T>>
+    where
+        AddTag<TTags, OrdViewTag>: MergeList<TagsOfValueView<T>>;
+}
+
+impl<J: ExprJsonExtract, TTags: TagList + SetBit<U0, True>> JsonExtract<TTags>
+    for ExprViewBoxWithTag<J, TTags>
+{
+    fn json_extract<T: ScalarValue>(
+        self,
+        path: &str,
+    ) -> ExprViewBoxWithTag<T, JsonExtractTags<TTags, T>>
+    where
+        AddTag<TTags, OrdViewTag>: MergeList<TagsOfValueView<T>>,
+    {
+        J::expr_json_extract(self, path)
+    }
+}
@@ -0,0 +1,184 @@
+use generic_array::arr;
+use generic_array::typenum::U1;
+use sqlx::types::Decimal;
+
+use interface::DatabaseType;
+use query_builder::{DatabaseValue, Expr, FunctionCall, ScalarFunction, ScalarFunctionCall};
+
+use crate::view::{
+    AnyTagExprView, ConcreteList, ExprViewBoxWithTag, MergeList, ScalarValue, SingleExprView,
+    TagList, Value,
+};
+
+fn scalar_fn_call(function: ScalarFunction, params: Vec<Expr>) -> Expr {
+    Expr::FunctionCall(Box::new(FunctionCall::Scalar(ScalarFunctionCall {
+        function,
+        params,
+    })))
+}
+
+fn single_expr<T: Value<L = U1>, TTags: TagList>(view: ExprViewBoxWithTag<T, TTags>) -> Expr {
+    view.collect_expr().into_iter().next().unwrap()
+}
+
+// Databases widen the results of rounding functions differently, e.g. `FLOOR` of integers is a
+// double in Postgres, so they are cast back to the type of the operand
+pub trait ExprNumeric: ScalarValue {
+    type Double: Value<L = U1>;
+
+    fn expr_numeric_fn<TTags: TagList>(
+        function: ScalarFunction,
+        expr: ExprViewBoxWithTag<Self, TTags>,
+        extra_params: Vec<Expr>,
+    ) -> ExprViewBoxWithTag<Self, TTags> {
+        let mut params = vec![single_expr(expr)];
+        params.extend(extra_params);
+
+        SingleExprView::from_exprs_with_tags(arr![Expr;
+            Expr::Cast(Box::new(scalar_fn_call(function, params)), Self::database_type())
+        ])
+    }
+
+    fn expr_power<LTags: TagList + MergeList<RTags>, RTags: TagList>(
+        base: ExprViewBoxWithTag<Self, LTags>,
+        exponent: ExprViewBoxWithTag<Self, RTags>,
+    ) -> ExprViewBoxWithTag<Self::Double, ConcreteList<LTags, RTags>> {
+        let fn_call = scalar_fn_call(
+            ScalarFunction::Power,
+            vec![single_expr(base), single_expr(exponent)],
+        );
+
+        SingleExprView::from_exprs_with_tags(arr![Expr;
+            Expr::Cast(Box::new(fn_call), DatabaseType::Double)
+        ])
+    }
+
+    fn expr_sqrt<TTags: TagList>(
+        expr: ExprViewBoxWithTag<Self, TTags>,
+    ) -> ExprViewBoxWithTag<Self::Double, TTags> {
+        let fn_call = scalar_fn_call(ScalarFunction::Sqrt, vec![single_expr(expr)]);
+
+        SingleExprView::from_exprs_with_tags(arr![Expr;
+            Expr::Cast(Box::new(fn_call), DatabaseType::Double)
+        ])
+    }
+
+    fn expr_extremum<LTags: TagList + MergeList<RTags>, RTags: TagList>(
+        function: ScalarFunction,
+        l: ExprViewBoxWithTag<Self, LTags>,
+        r: ExprViewBoxWithTag<Self, RTags>,
+    ) -> ExprViewBoxWithTag<Self, ConcreteList<LTags, RTags>> {
+        let fn_call = scalar_fn_call(function, vec![single_expr(l), single_expr(r)]);
+
+        SingleExprView::from_exprs_with_tags(arr![Expr; fn_call])
+    }
+}
+
+macro_rules! impl_numeric {
+    ($($ty: ty),*) => {
+        $(
+            impl ExprNumeric for $ty {
+                type Double = f64;
+            }
+
+            impl ExprNumeric for Option<$ty> {
+                type Double = Option<f64>;
+            }
+        )*
+    };
+}
+
+impl_numeric!(u16, i16, u32, i32, u64, i64, f32, f64, Decimal);
+
+pub trait MathFunctions<T: ExprNumeric, TTags: TagList> {
+    fn abs(self) -> ExprViewBoxWithTag<T, TTags>;
+
+    // Round half away from zero to `precision` decimal places
+    fn round(self, precision: i32) -> ExprViewBoxWithTag<T, TTags>;
+
+    fn floor(self) -> ExprViewBoxWithTag<T, TTags>;
+
+    fn ceil(self) -> ExprViewBoxWithTag<T, TTags>;
+
+    // Not supported by SQLite, which is built without math functions by sqlx
+    fn pow<RTags: TagList, R: Into<ExprViewBoxWithTag<T, RTags>>>(
+        self,
+        exponent: R,
+    ) -> ExprViewBoxWithTag<T::Double, ConcreteList<TTags, RTags>>
+    where
+        TTags: MergeList<RTags>;
+
+    // Not supported by SQLite, as `pow`
+    fn sqrt(self) -> ExprViewBoxWithTag<T::Double, TTags>;
+
+    // The result is null if either side is null, except in Postgres
+    fn greatest<RTags: TagList, R: Into<ExprViewBoxWithTag<T, RTags>>>(
+        self,
+        r: R,
+    ) -> ExprViewBoxWithTag<T, ConcreteList<TTags, RTags>>
+    where
+        TTags: MergeList<RTags>;
+
+    fn least<RTags: TagList, R: Into<ExprViewBoxWithTag<T, RTags>>>(
+        self,
+        r: R,
+    ) -> ExprViewBoxWithTag<T, ConcreteList<TTags, RTags>>
+    where
+        TTags: MergeList<RTags>;
+}
+
+impl<T: ExprNumeric, TTags: TagList> MathFunctions<T, TTags> for ExprViewBoxWithTag<T, TTags> {
+    fn abs(self) -> ExprViewBoxWithTag<T, TTags> {
+        T::expr_numeric_fn(ScalarFunction::Abs, self, vec![])
+    }
+
+    fn round(self, precision: i32) -> ExprViewBoxWithTag<T, TTags> {
+        T::expr_numeric_fn(
+            ScalarFunction::Round,
+            self,
+            vec![Expr::Lit(DatabaseValue::Integer(precision))],
+        )
+    }
+
+    fn floor(self) -> ExprViewBoxWithTag<T, TTags> {
+        T::expr_numeric_fn(ScalarFunction::Floor, self, vec![])
+    }
+
+    fn ceil(self) -> ExprViewBoxWithTag<T, TTags> {
+        T::expr_numeric_fn(ScalarFunction::Ceil, self, vec![])
+    }
+
+    fn pow<RTags: TagList, R: Into<ExprViewBoxWithTag<T, RTags>>>(
+        self,
+        exponent: R,
+    ) -> ExprViewBoxWithTag<T::Double, ConcreteList<TTags, RTags>>
+    where
+        TTags: MergeList<RTags>,
+    {
+        T::expr_power(self, exponent.into())
+    }
+
+    fn sqrt(self) -> ExprViewBoxWithTag<T::Double, TTags> {
+        T::expr_sqrt(self)
+    }
+
+    fn greatest<RTags: TagList, R: Into<ExprViewBoxWithTag<T, RTags>>>(
+        self,
+        r: R,
+    ) -> ExprViewBoxWithTag<T, ConcreteList<TTags, RTags>>
+    where
+        TTags: MergeList<RTags>,
+    {
+        T::expr_extremum(ScalarFunction::Greatest, self, r.into())
+    }
+
+    fn least<RTags: TagList, R: Into<ExprViewBoxWithTag<T, RTags>>>(
+        self,
+        r: R,
+    ) -> ExprViewBoxWithTag<T, ConcreteList<TTags, RTags>>
+    where
+        TTags: MergeList<RTags>,
+    {
+        T::expr_extremum(ScalarFunction::Least, self, r.into())
+    }
+}
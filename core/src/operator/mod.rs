@@ -1,13 +1,31 @@
 pub use aggr_func::*;
+pub use binary::*;
 pub use boolean::*;
+pub use case::*;
+pub use cast::*;
+pub use datetime::*;
+pub use json::*;
+pub use math::*;
+pub use nullable::*;
 pub use numeric::*;
 pub use order::*;
+pub use string::*;
 pub use subquery::*;
 pub use tuple::*;
+pub use window::*;
 
 mod aggr_func;
+mod binary;
 mod boolean;
+mod case;
+mod cast;
+mod datetime;
+mod json;
+mod math;
+mod nullable;
 mod numeric;
 mod order;
+mod string;
 mod subquery;
 mod tuple;
+mod window;
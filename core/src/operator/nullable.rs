@@ -0,0 +1,115 @@
+use generic_array::arr;
+use generic_array::typenum::U1;
+
+use query_builder::{Expr, FunctionCall, ScalarFunction, ScalarFunctionCall};
+
+use crate::view::{
+    AnyTagExprView, AnyTagsValue, ConcreteList, ExprViewBoxWithTag, MergeList, SingleExprView,
+    TagList, Value,
+};
+
+pub trait ExprNullable: Value<L = U1> {
+    type Inner: Value<L = U1>;
+
+    fn expr_is_null<TTags: TagList>(
+        expr: ExprViewBoxWithTag<Self, TTags>,
+    ) -> ExprViewBoxWithTag<bool, TTags> {
+        let expr = expr.collect_expr().into_iter().next().unwrap();
+
+        SingleExprView::from_exprs_with_tags(arr![Expr; Expr::IsNull(Box::new(expr))])
+    }
+
+    fn expr_is_not_null<TTags: TagList>(
+        expr: ExprViewBoxWithTag<Self, TTags>,
+    ) -> ExprViewBoxWithTag<bool, TTags> {
+        let expr = expr.collect_expr().into_iter().next().unwrap();
+
+        SingleExprView::from_exprs_with_tags(arr![Expr; Expr::IsNotNull(Box::new(expr))])
+    }
+
+    // The result is not null as long as `default` is not null
+    fn expr_coalesce<LTags: TagList + MergeList<RTags>, RTags: TagList>(
+        expr: ExprViewBoxWithTag<Self, LTags>,
+        default: ExprViewBoxWithTag<Self::Inner, RTags>,
+    ) -> ExprViewBoxWithTag<Self::Inner, ConcreteList<LTags, RTags>> {
+        let fn_call = FunctionCall::Scalar(ScalarFunctionCall {
+            function: ScalarFunction::Coalesce,
+            params: vec![
+                expr.collect_expr().into_iter().next().unwrap(),
+                default.collect_expr().into_iter().next().unwrap(),
+            ],
+        });
+
+        SingleExprView::from_exprs_with_tags(arr![Expr; Expr::FunctionCall(Box::new(fn_call))])
+    }
+}
+
+impl<T: Value<L = U1>> ExprNullable for Option<T>
+where
+    Option<T>: Value<L = U1>,
+{
+    type Inner = T;
+}
+
+// Views are consumed as in other operators, the names follow SQL
+#[allow(clippy::wrong_self_convention)]
+pub trait Nullable<T: ExprNullable, TTags: TagList> {
+    fn is_null(self) -> ExprViewBoxWithTag<bool, TTags>;
+
+    fn is_not_null(self) -> ExprViewBoxWithTag<bool, TTags>;
+}
+
+impl<T: ExprNullable, TTags: TagList> Nullable<T, TTags> for ExprViewBoxWithTag<T, TTags> {
+    fn is_null(self) -> ExprViewBoxWithTag<bool, TTags> {
+        T::expr_is_null(self)
+    }
+
+    fn is_not_null(self) -> ExprViewBoxWithTag<bool, TTags> {
+        T::expr_is_not_null(self)
+    }
+}
+
+pub trait UnwrapOr<D> {
+    type Output;
+
+    fn unwrap_or(self, default: D) -> Self::Output;
+
+    // Alias of `unwrap_or` named after `IFNULL`, rendered as `COALESCE` on all databases
+    fn if_null(self, default: D) -> Self::Output
+    where
+        Self: Sized,
+    {
+        self.unwrap_or(default)
+    }
+}
+
+impl<
+        T: ExprNullable<Inner = I>,
+        I: Value<L = U1>,
+        LTags: TagList + MergeList<RTags>,
+        RTags: TagList,
+    > UnwrapOr<ExprViewBoxWithTag<I, RTags>> for ExprViewBoxWithTag<T, LTags>
+{
+    type Output = ExprViewBoxWithTag<I, ConcreteList<LTags, RTags>>;
+
+    fn unwrap_or(self, default: ExprViewBoxWithTag<I, RTags>) -> Self::Output {
+        T::expr_coalesce(self, default)
+    }
+}
+
+// Values take the tags of the view as in arithmetic operators, e.g. the result of
+// `average().unwrap_or(..)` is still an aggregate
+impl<T: ExprNullable<Inner = I>, I: AnyTagsValue<L = U1>, LTags: TagList + MergeList<LTags>>
+    UnwrapOr<I> for ExprViewBoxWithTag<T, LTags>
+{
+    type Output = ExprViewBoxWithTag<I, ConcreteList<LTags, LTags>>;
+
+    fn unwrap_or(self, default: I) -> Self::Output {
+        T::expr_coalesce(self, default.view_with_tags::<LTags>())
+    }
+}
+
+// `COALESCE(view, default)`, the same as `view.unwrap_or(default)`
+pub fn coalesce<V: UnwrapOr<D>, D>(view: V, default: D) -> V::Output {
+    view.unwrap_or(default)
+}
@@ -0,0 +1,185 @@
+use generic_array::arr;
+use generic_array::typenum::U1;
+
+use interface::DatabaseType;
+use query_builder::{DatabaseValue, Expr, FunctionCall, ScalarFunction, ScalarFunctionCall};
+
+use crate::view::{
+    AnyTagExprView, ConcreteList, ExprViewBoxWithTag, MergeList, SingleExprView, TagList, Value,
+};
+
+fn scalar_fn_call(function: ScalarFunction, params: Vec<Expr>) -> Expr {
+    Expr::FunctionCall(Box::new(FunctionCall::Scalar(ScalarFunctionCall {
+        function,
+        params,
+    })))
+}
+
+// `%` and `_` are wildcards in patterns of `LIKE`, `\` is used as the escape character
+fn escape_like_pattern(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+pub trait ExprString: Value<L = U1> {
+    type Length: Value<L = U1>;
+
+    fn expr_string_fn<TTags: TagList>(
+        function: ScalarFunction,
+        expr: ExprViewBoxWithTag<Self, TTags>,
+        extra_params: Vec<Expr>,
+    ) -> ExprViewBoxWithTag<Self, TTags> {
+        let mut params = vec![expr.collect_expr().into_iter().next().unwrap()];
+        params.extend(extra_params);
+
+        SingleExprView::from_exprs_with_tags(arr![Expr; scalar_fn_call(function, params)])
+    }
+
+    // Length in characters, cast for the same reason as `ExprLength`
+    fn expr_char_length<TTags: TagList>(
+        expr: ExprViewBoxWithTag<Self, TTags>,
+    ) -> ExprViewBoxWithTag<Self::Length, TTags> {
+        let fn_call = scalar_fn_call(
+            ScalarFunction::CharLength,
+            vec![expr.collect_expr().into_iter().next().unwrap()],
+        );
+
+        SingleExprView::from_exprs_with_tags(arr![Expr;
+            Expr::Cast(Box::new(fn_call), DatabaseType::BigInteger)
+        ])
+    }
+
+    fn expr_concat<LTags: TagList + MergeList<RTags>, RTags: TagList>(
+        l: ExprViewBoxWithTag<Self, LTags>,
+        r: ExprViewBoxWithTag<Self, RTags>,
+    ) -> ExprViewBoxWithTag<Self, ConcreteList<LTags, RTags>> {
+        let fn_call = scalar_fn_call(
+            ScalarFunction::Concat,
+            vec![
+                l.collect_expr().into_iter().next().unwrap(),
+                r.collect_expr().into_iter().next().unwrap(),
+            ],
+        );
+
+        SingleExprView::from_exprs_with_tags(arr![Expr; fn_call])
+    }
+}
+
+impl ExprString for String {
+    type Length = i64;
+}
+
+impl ExprString for Option<String> {
+    type Length = Option<i64>;
+}
+
+pub trait ExprLike: ExprString {
+    fn expr_like<TTags: TagList>(
+        expr: ExprViewBoxWithTag<Self, TTags>,
+        pattern: String,
+    ) -> ExprViewBoxWithTag<bool, TTags> {
+        let l = expr.collect_expr().into_iter().next().unwrap();
+        let r = Expr::Lit(DatabaseValue::String(pattern));
+
+        SingleExprView::from_exprs_with_tags(arr![Expr; Expr::Like(Box::new(l), Box::new(r))])
+    }
+}
+
+impl ExprLike for String {}
+
+// Null values are not like any pattern
+impl ExprLike for Option<String> {}
+
+pub trait Like {
+    type Output;
+
+    // `pattern` is used as it is, wildcards in it can be escaped by `\`
+    fn like(self, pattern: &str) -> Self::Output;
+
+    fn contains(self, value: &str) -> Self::Output;
+
+    fn starts_with(self, value: &str) -> Self::Output;
+
+    fn ends_with(self, value: &str) -> Self::Output;
+}
+
+impl<T: ExprLike, TTags: TagList> Like for ExprViewBoxWithTag<T, TTags> {
+    type Output = ExprViewBoxWithTag<bool, TTags>;
+
+    fn like(self, pattern: &str) -> Self::Output {
+        T::expr_like(self, pattern.to_string())
+    }
+
+    fn contains(self, value: &str) -> Self::Output {
+        T::expr_like(self, format!("%{}%", escape_like_pattern(value)))
+    }
+
+    fn starts_with(self, value: &str) -> Self::Output {
+        T::expr_like(self, format!("{}%", escape_like_pattern(value)))
+    }
+
+    fn ends_with(self, value: &str) -> Self::Output {
+        T::expr_like(self, format!("%{}", escape_like_pattern(value)))
+    }
+}
+
+pub trait StringFunctions<T: ExprString, TTags: TagList> {
+    fn lower(self) -> ExprViewBoxWithTag<T, TTags>;
+
+    fn upper(self) -> ExprViewBoxWithTag<T, TTags>;
+
+    fn trim(self) -> ExprViewBoxWithTag<T, TTags>;
+
+    fn char_length(self) -> ExprViewBoxWithTag<T::Length, TTags>;
+
+    fn concat<RTags: TagList, R: Into<ExprViewBoxWithTag<T, RTags>>>(
+        self,
+        r: R,
+    ) -> ExprViewBoxWithTag<T, ConcreteList<TTags, RTags>>
+    where
+        TTags: MergeList<RTags>;
+
+    // `start` begins from 1 as in SQL
+    fn substring(self, start: i32, length: i32) -> ExprViewBoxWithTag<T, TTags>;
+}
+
+impl<T: ExprString, TTags: TagList> StringFunctions<T, TTags> for ExprViewBoxWithTag<T, TTags> {
+    fn lower(self) -> ExprViewBoxWithTag<T, TTags> {
+        T::expr_string_fn(ScalarFunction::Lower, self, vec![])
+    }
+
+    fn upper(self) -> ExprViewBoxWithTag<T, TTags> {
+        T::expr_string_fn(ScalarFunction::Upper, self, vec![])
+    }
+
+    fn trim(self) -> ExprViewBoxWithTag<T, TTags> {
+        T::expr_string_fn(ScalarFunction::Trim, self, vec![])
+    }
+
+    fn char_length(self) -> ExprViewBoxWithTag<T::Length, TTags> {
+        T::expr_char_length(self)
+    }
+
+    fn concat<RTags: TagList, R: Into<ExprViewBoxWithTag<T, RTags>>>(
+        self,
+        r: R,
+    ) -> ExprViewBoxWithTag<T, ConcreteList<TTags, RTags>>
+    where
+        TTags: MergeList<RTags>,
+    {
+        T::expr_concat(self, r.into())
+    }
+
+    fn substring(self, start: i32, length: i32) -> ExprViewBoxWithTag<T, TTags> {
+        T::expr_string_fn(
+            ScalarFunction::Substring,
+            self,
+            vec![
+                Expr::Lit(DatabaseValue::Integer(start)),
+                Expr::Lit(DatabaseValue::Integer(length)),
+            ],
+        )
+    }
+}
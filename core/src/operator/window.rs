@@ -0,0 +1,205 @@
+use generic_array::arr;
+use generic_array::typenum::U1;
+use sqlx::types::Decimal;
+
+use interface::DatabaseType;
+use query_builder::{
+    AggregateFunction, DatabaseValue, Expr, FunctionCall, OrderByItem, WindowFunction,
+    WindowFunctionCall,
+};
+
+use crate::operator::{ExprAverage, ExprCount, ExprMax, ExprMin, ExprSum, SortResult};
+use crate::query::GroupResult;
+use crate::view::{
+    AddTag, AnyTagExprView, ExprViewBoxWithTag, NullableOf, NullableValue, OffsetOfTag, SetBit,
+    SingleExprView, TagList, TagsOfValueView, True, Value, WindowViewTag,
+};
+
+// Rows a window function is evaluated over. `partition_by` and `order_by` take the results of the
+// closures of `group_by` and `sort`, the whole result set is a single partition by default
+#[derive(Clone, Default)]
+pub struct Window {
+    partition_by: Vec<Expr>,
+    order_by: Vec<OrderByItem>,
+}
+
+// Results of window functions are tagged, so that they are rejected by `filter` and `group_by`
+type WindowTags<TTags> = AddTag<TTags, WindowViewTag>;
+
+pub fn window() -> Window {
+    Window::default()
+}
+
+fn single_expr<T: Value<L = U1>, TTags: TagList>(view: ExprViewBoxWithTag<T, TTags>) -> Expr {
+    view.collect_expr().into_iter().next().unwrap()
+}
+
+impl Window {
+    pub fn partition_by<R: GroupResult>(mut self, partition: R) -> Self {
+        self.partition_by = partition.collect_expr_vec();
+        self
+    }
+
+    pub fn order_by<R: SortResult>(mut self, order: R) -> Self {
+        self.order_by = order.order_by_items();
+        self
+    }
+
+    fn fn_call(self, function: WindowFunction, params: Vec<Expr>) -> Expr {
+        Expr::FunctionCall(Box::new(FunctionCall::Window(WindowFunctionCall {
+            function,
+            params,
+            partition_by: self.partition_by,
+            order_by: self.order_by,
+        })))
+    }
+
+    fn view<T: Value<L = U1>, TTags: TagList>(
+        self,
+        function: WindowFunction,
+        params: Vec<Expr>,
+    ) -> ExprViewBoxWithTag<T, TTags> {
+        SingleExprView::from_exprs_with_tags(arr![Expr; self.fn_call(function, params)])
+    }
+
+    // Ranks are cast since MySQL returns unsigned integers for them
+    fn rank_view(
+        self,
+        function: WindowFunction,
+    ) -> ExprViewBoxWithTag<i64, WindowTags<TagsOfValueView<i64>>> {
+        SingleExprView::from_exprs_with_tags(arr![Expr;
+            Expr::Cast(Box::new(self.fn_call(function, vec![])), DatabaseType::BigInteger)
+        ])
+    }
+
+    pub fn row_number(self) -> ExprViewBoxWithTag<i64, WindowTags<TagsOfValueView<i64>>> {
+        self.rank_view(WindowFunction::RowNumber)
+    }
+
+    pub fn rank(self) -> ExprViewBoxWithTag<i64, WindowTags<TagsOfValueView<i64>>> {
+        self.rank_view(WindowFunction::Rank)
+    }
+
+    pub fn dense_rank(self) -> ExprViewBoxWithTag<i64, WindowTags<TagsOfValueView<i64>>> {
+        self.rank_view(WindowFunction::DenseRank)
+    }
+
+    // Value of the row `offset` rows before the current one, null if there is no such row
+    pub fn lag<
+        T: NullableValue<L = U1>,
+        TTags: TagList + SetBit<OffsetOfTag<WindowViewTag>, True>,
+        V: Into<ExprViewBoxWithTag<T, TTags>>,
+    >(
+        self,
+        view: V,
+        offset: i32,
+    ) -> ExprViewBoxWithTag<NullableOf<T>, WindowTags<TTags>> {
+        self.view(
+            WindowFunction::Lag,
+            vec![
+                single_expr(view.into()),
+                Expr::Lit(DatabaseValue::Integer(offset)),
+            ],
+        )
+    }
+
+    // Value of the row `offset` rows after the current one, null if there is no such row
+    pub fn lead<
+        T: NullableValue<L = U1>,
+        TTags: TagList + SetBit<OffsetOfTag<WindowViewTag>, True>,
+        V: Into<ExprViewBoxWithTag<T, TTags>>,
+    >(
+        self,
+        view: V,
+        offset: i32,
+    ) -> ExprViewBoxWithTag<NullableOf<T>, WindowTags<TTags>> {
+        self.view(
+            WindowFunction::Lead,
+            vec![
+                single_expr(view.into()),
+                Expr::Lit(DatabaseValue::Integer(offset)),
+            ],
+        )
+    }
+
+    pub fn first_value<
+        T: Value<L = U1>,
+        TTags: TagList + SetBit<OffsetOfTag<WindowViewTag>, True>,
+        V: Into<ExprViewBoxWithTag<T, TTags>>,
+    >(
+        self,
+        view: V,
+    ) -> ExprViewBoxWithTag<T, WindowTags<TTags>> {
+        self.view(WindowFunction::FirstValue, vec![single_expr(view.into())])
+    }
+
+    // Aggregate functions over the window, they are evaluated for each row instead of each group.
+    // With `order_by`, the frame ends at the current row, which gives running totals
+    pub fn sum<T: ExprSum<L = U1>, TTags: TagList, V: Into<ExprViewBoxWithTag<T, TTags>>>(
+        self,
+        view: V,
+    ) -> ExprViewBoxWithTag<Option<Decimal>, WindowTags<TagsOfValueView<Option<Decimal>>>> {
+        self.view(
+            WindowFunction::Aggregate(AggregateFunction::Sum),
+            vec![single_expr(view.into())],
+        )
+    }
+
+    pub fn average<
+        T: ExprAverage<L = U1>,
+        TTags: TagList,
+        V: Into<ExprViewBoxWithTag<T, TTags>>,
+    >(
+        self,
+        view: V,
+    ) -> ExprViewBoxWithTag<Option<Decimal>, WindowTags<TagsOfValueView<Option<Decimal>>>> {
+        self.view(
+            WindowFunction::Aggregate(AggregateFunction::Average),
+            vec![single_expr(view.into())],
+        )
+    }
+
+    pub fn count<T: ExprCount<L = U1>, TTags: TagList, V: Into<ExprViewBoxWithTag<T, TTags>>>(
+        self,
+        view: V,
+    ) -> ExprViewBoxWithTag<i64, WindowTags<TagsOfValueView<i64>>> {
+        self.view(
+            WindowFunction::Aggregate(AggregateFunction::Count),
+            vec![single_expr(view.into())],
+        )
+    }
+
+    pub fn max<
+        T: ExprMax<L = U1>,
+        TTags: TagList + SetBit<OffsetOfTag<WindowViewTag>, True>,
+        V: Into<ExprViewBoxWithTag<T, TTags>>,
+    >(
+        self,
+        view: V,
+    ) -> ExprViewBoxWithTag<Option<T>, WindowTags<TTags>>
+    where
+        Option<T>: Value<L = U1>,
+    {
+        self.view(
+            WindowFunction::Aggregate(AggregateFunction::Max),
+            vec![single_expr(view.into())],
+        )
+    }
+
+    pub fn min<
+        T: ExprMin<L = U1>,
+        TTags: TagList + SetBit<OffsetOfTag<WindowViewTag>, True>,
+        V: Into<ExprViewBoxWithTag<T, TTags>>,
+    >(
+        self,
+        view: V,
+    ) -> ExprViewBoxWithTag<Option<T>, WindowTags<TTags>>
+    where
+        Option<T>: Value<L = U1>,
+    {
+        self.view(
+            WindowFunction::Aggregate(AggregateFunction::Min),
+            vec![single_expr(view.into())],
+        )
+    }
+}
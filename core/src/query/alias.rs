@@ -1,9 +1,11 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
+use generic_array::typenum::Unsigned;
+
 use query_builder::{Alias, Expr, SelectItem};
 
-use crate::view::EntityWithView;
+use crate::view::{EntityWithView, Value, ValueCountOf};
 
 pub type AliasName = String;
 pub type TableName = &'static str;
@@ -24,6 +26,26 @@ impl AliasGenerator {
         self.generate_alias(E::table_name())
     }
 
+    pub fn generate_cte_alias(&mut self, name: &'static str) -> Alias {
+        self.generate_alias(name)
+    }
+
+    pub fn generate_compound_alias(&mut self) -> Alias {
+        self.generate_alias("compound")
+    }
+
+    // Alias of the `index`th column in the select list, which is also the column name of CTEs
+    pub fn select_alias(index: usize) -> String {
+        format!("U{}", index)
+    }
+
+    // Aliases of the columns selected for `R`
+    pub fn select_aliases<R: Value>() -> Vec<String> {
+        (0..ValueCountOf::<R>::USIZE)
+            .map(Self::select_alias)
+            .collect()
+    }
+
     pub fn generate_select_list(
         &self,
         exprs: impl IntoIterator<Item = Expr>,
@@ -35,7 +57,7 @@ impl AliasGenerator {
             .map(|(index, expr)| SelectItem {
                 expr,
                 alias: if with_alias {
-                    Some(format!("U{}", index))
+                    Some(Self::select_alias(index))
                 } else {
                     None
                 },
@@ -1,10 +1,19 @@
 use std::collections::BTreeMap;
 use std::hash::Hash;
 
-use interface::{Association, FieldMarker, PrimaryKeyTypeOf, WithPrimaryKey};
+use futures::{FutureExt, future::BoxFuture};
+use generic_array::{ArrayLength, typenum::U0};
+use sqlx::{Acquire, Error, Executor, IntoArguments};
+use sqlx::database::HasArguments;
 
-use crate::query::FilteredQueryBuilder;
-use crate::view::{EntityWithView, FieldMarkerWithView, TypeOfMarker, Value, ViewWithPrimaryKey};
+use interface::{Association, FieldMarker, ForeignKeyMarker, PrimaryKeyTypeOf, WithPrimaryKey};
+use query_builder::{AppendToArgs, ColumnOf, DatabaseValue, HasDialect};
+
+use crate::query::{FetchMulti, FilteredQueryBuilder};
+use crate::view::{
+    DBMapping, EntityWithView, FieldMarkerWithView, TypeOfMarker, Value, ValueCountOf,
+    ViewWithPrimaryKey,
+};
 
 pub trait AssociationBuilder<
     Children: EntityWithView + Association<Parent, ForeignField, ForeignKeyType = TypeOfMarker<ForeignField>>,
@@ -68,7 +77,7 @@ pub trait BelongsToEntities<Parent: EntityWithView>: EntityWithView {
         TypeOfMarker<ForeignField>: Value + Ord + Hash,
     {
         FilteredQueryBuilder::<Parent>::build_from_parent_entities(
-            r.iter().map(|i| i.primary_key().clone()).collect(),
+            r.iter().map(|i| i.primary_key()).collect(),
         )
     }
 }
@@ -136,19 +145,15 @@ impl<
         Parent: WithPrimaryKey<PrimaryKeyType = TypeOfMarker<ForeignField>>,
         TypeOfMarker<ForeignField>: Value + Ord + Hash,
     {
-        let parent: BTreeMap<PrimaryKeyTypeOf<Parent>, Parent> = self
-            .into_iter()
-            .map(|p| (p.primary_key().clone(), p))
-            .collect();
+        let parent: BTreeMap<PrimaryKeyTypeOf<Parent>, Parent> =
+            self.into_iter().map(|p| (p.primary_key(), p)).collect();
 
-        let mut grouped_children: BTreeMap<PrimaryKeyTypeOf<Parent>, Vec<Right>> = parent
-            .values()
-            .map(|p| (p.primary_key().clone(), vec![]))
-            .collect();
+        let mut grouped_children: BTreeMap<PrimaryKeyTypeOf<Parent>, Vec<Right>> =
+            parent.values().map(|p| (p.primary_key(), vec![])).collect();
 
         for r_i in right {
             grouped_children
-                .get_mut(r_i.children().foreign_key())
+                .get_mut(&r_i.children().foreign_key())
                 .unwrap()
                 .push(r_i);
         }
@@ -166,3 +171,174 @@ impl<Children: EntityWithView, Parent: EntityWithView> BelongsToQuery<Parent> fo
 impl<Children: EntityWithView, Parent: EntityWithView> BelongsToView<Parent> for Children {}
 
 impl<Children: EntityWithView, Parent: EntityWithView> BelongsToEntities<Parent> for Children {}
+
+pub type LoadedChildren<Parent, Children> = Vec<(Parent, Vec<Children>)>;
+pub type LoadedChild<Parent, Children> = Vec<(Parent, Option<Children>)>;
+
+// Load children of all parents in one query, used by the generated `load_*` methods of
+// `has_many` associations
+pub trait LoadChildren<Children: EntityWithView, ForeignField, DB: HasDialect>: Sized {
+    type Parent: EntityWithView;
+
+    fn load_children<'c: 'e, 'e, E: 'e + Executor<'c, Database = DB>>(
+        self,
+        executor: E,
+    ) -> BoxFuture<'e, Result<LoadedChildren<Self::Parent, Children>, Error>>;
+}
+
+// Like `LoadChildren`, used by the generated `load_*` methods of `has_one` associations
+pub trait LoadChild<Children: EntityWithView, ForeignField, DB: HasDialect>: Sized {
+    type Parent: EntityWithView;
+
+    fn load_child<'c: 'e, 'e, E: 'e + Executor<'c, Database = DB>>(
+        self,
+        executor: E,
+    ) -> BoxFuture<'e, Result<LoadedChild<Self::Parent, Children>, Error>>;
+}
+
+impl<
+        Children: EntityWithView
+            + Association<Parent, ForeignField, ForeignKeyType = TypeOfMarker<ForeignField>>
+            + for<'r> DBMapping<'r, DB, U0>,
+        Parent: EntityWithView + WithPrimaryKey<PrimaryKeyType = TypeOfMarker<ForeignField>>,
+        ForeignField: FieldMarkerWithView + FieldMarker<Entity = Children>,
+        DB: HasDialect,
+    > LoadChildren<Children, ForeignField, DB> for Vec<Parent>
+where
+    FilteredQueryBuilder<Parent>: AssociationBuilder<Children, Parent, ForeignField>,
+    FilteredQueryBuilder<Children>: FetchMulti<Children, DB>,
+    Parent::View: ViewWithPrimaryKey<PrimaryKeyType = TypeOfMarker<ForeignField>>,
+    TypeOfMarker<ForeignField>: Value + Ord + Hash,
+    DatabaseValue: for<'q> AppendToArgs<'q, DB>,
+    for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+    <ValueCountOf<Children> as ArrayLength<DatabaseValue>>::ArrayType: Unpin,
+    ValueCountOf<Children>: for<'r> ArrayLength<ColumnOf<DB>>,
+{
+    type Parent = Parent;
+
+    fn load_children<'c: 'e, 'e, E: 'e + Executor<'c, Database = DB>>(
+        self,
+        executor: E,
+    ) -> BoxFuture<'e, Result<LoadedChildren<Parent, Children>, Error>> {
+        let query = Children::belonging_to::<ForeignField>(&self);
+
+        async move {
+            let children = query.exec(executor).await?.try_collect()?;
+
+            Ok(self.join::<ForeignField>(children))
+        }
+        .boxed()
+    }
+}
+
+impl<Children: EntityWithView, Parent: EntityWithView, ForeignField, DB: HasDialect>
+    LoadChild<Children, ForeignField, DB> for Vec<Parent>
+where
+    Self: LoadChildren<Children, ForeignField, DB, Parent = Parent>,
+{
+    type Parent = Parent;
+
+    fn load_child<'c: 'e, 'e, E: 'e + Executor<'c, Database = DB>>(
+        self,
+        executor: E,
+    ) -> BoxFuture<'e, Result<LoadedChild<Parent, Children>, Error>> {
+        self.load_children(executor)
+            .map(|result| {
+                result.map(|grouped| {
+                    grouped
+                        .into_iter()
+                        .map(|(parent, children)| (parent, children.into_iter().next()))
+                        .collect()
+                })
+            })
+            .boxed()
+    }
+}
+
+// Load targets of a many to many association across the join entity `SourceField::Entity`, the
+// join rows and the targets are fetched by two queries on the same connection
+pub trait LoadThrough<Target: EntityWithView, SourceField, TargetField, DB: HasDialect>:
+    Sized
+{
+    type Parent: EntityWithView;
+
+    fn load_through<'c, A: 'c + Acquire<'c, Database = DB> + Send>(
+        self,
+        acquire: A,
+    ) -> BoxFuture<'c, Result<LoadedChildren<Self::Parent, Target>, Error>>;
+}
+
+impl<
+        Parent: EntityWithView + WithPrimaryKey<PrimaryKeyType = TypeOfMarker<SourceField>>,
+        Through: EntityWithView
+            + Association<Parent, SourceField, ForeignKeyType = TypeOfMarker<SourceField>>
+            + Association<Target, TargetField, ForeignKeyType = TypeOfMarker<TargetField>>
+            + for<'r> DBMapping<'r, DB, U0>,
+        Target: EntityWithView
+            + WithPrimaryKey<PrimaryKeyType = TypeOfMarker<TargetField>>
+            + for<'r> DBMapping<'r, DB, U0>,
+        SourceField: FieldMarkerWithView + FieldMarker<Entity = Through>,
+        TargetField: ForeignKeyMarker<Parent = Target> + FieldMarkerWithView + FieldMarker<Entity = Through>,
+        DB: HasDialect,
+    > LoadThrough<Target, SourceField, TargetField, DB> for Vec<Parent>
+where
+    FilteredQueryBuilder<Parent>: AssociationBuilder<Through, Parent, SourceField>,
+    FilteredQueryBuilder<Through>: FetchMulti<Through, DB>,
+    FilteredQueryBuilder<Target>: FetchMulti<Target, DB>,
+    Parent::View: ViewWithPrimaryKey<PrimaryKeyType = TypeOfMarker<SourceField>>,
+    TypeOfMarker<SourceField>: Value + Ord + Hash,
+    TypeOfMarker<TargetField>: Value + Ord + Hash,
+    for<'e> &'e mut DB::Connection: Executor<'e, Database = DB>,
+    DatabaseValue: for<'q> AppendToArgs<'q, DB>,
+    for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+    <ValueCountOf<Through> as ArrayLength<DatabaseValue>>::ArrayType: Unpin,
+    ValueCountOf<Through>: for<'r> ArrayLength<ColumnOf<DB>>,
+    <ValueCountOf<Target> as ArrayLength<DatabaseValue>>::ArrayType: Unpin,
+    ValueCountOf<Target>: for<'r> ArrayLength<ColumnOf<DB>>,
+{
+    type Parent = Parent;
+
+    fn load_through<'c, A: 'c + Acquire<'c, Database = DB> + Send>(
+        self,
+        acquire: A,
+    ) -> BoxFuture<'c, Result<LoadedChildren<Parent, Target>, Error>> {
+        let through_query = Through::belonging_to::<SourceField>(&self);
+        let target_query = Through::belonging_to::<SourceField>(&self).referenced::<TargetField>();
+
+        async move {
+            let mut connection = acquire.acquire().await?;
+            let through: Vec<Through> =
+                through_query.exec(&mut *connection).await?.try_collect()?;
+            let targets: BTreeMap<PrimaryKeyTypeOf<Target>, Target> = target_query
+                .exec(&mut *connection)
+                .await?
+                .map(|target| target.map(|t| (t.primary_key(), t)))
+                .collect::<Result<_, _>>()?;
+
+            let parent: BTreeMap<PrimaryKeyTypeOf<Parent>, Parent> =
+                self.into_iter().map(|p| (p.primary_key(), p)).collect();
+            let mut grouped_targets: BTreeMap<PrimaryKeyTypeOf<Parent>, Vec<Target>> = parent
+                .keys()
+                .map(|primary_key| (primary_key.clone(), vec![]))
+                .collect();
+
+            for row in through {
+                let source_key = Association::<Parent, SourceField>::foreign_key(&row);
+                let target_key = Association::<Target, TargetField>::foreign_key(&row);
+
+                if let (Some(group), Some(target)) = (
+                    grouped_targets.get_mut(&source_key),
+                    targets.get(&target_key),
+                ) {
+                    group.push(target.clone());
+                }
+            }
+
+            Ok(parent
+                .into_values()
+                .zip(grouped_targets.into_values())
+                .collect())
+        }
+        .boxed()
+    }
+}
@@ -0,0 +1,144 @@
+use std::marker::PhantomData;
+
+use generic_array::typenum::U1;
+use sqlx::Database;
+
+use query_builder::{
+    Alias, CompoundSelect, Expr, IntoSelectSource, OrderByItem, SelectQuery, SetOperator,
+    YukinoQuery,
+};
+
+use crate::operator::SortResult;
+use crate::query::{AliasGenerator, CteValue, Executable, MappedQueryBuilder, MultiRows, Sort};
+use crate::view::{SubqueryView, TagList, Value};
+
+// Rows of `UNION`, `UNION ALL`, `INTERSECT` or `EXCEPT` of two queries, `ORDER BY` and `LIMIT`
+// apply to the rows of the result
+#[derive(Clone)]
+pub struct CompoundQueryBuilder<R: Value> {
+    query: CompoundSelect,
+    alias_generator: AliasGenerator,
+    order_by_items: Vec<OrderByItem>,
+    limit: Option<usize>,
+    offset: usize,
+    _marker: PhantomData<R>,
+}
+
+// Queries of rows of `R` which can be combined with each other
+pub trait Compound<R: Value>: Sized {
+    fn compound_operand(self) -> SelectQuery;
+
+    fn union<O: Compound<R>>(self, other: O) -> CompoundQueryBuilder<R> {
+        CompoundQueryBuilder::create(SetOperator::Union, self, other)
+    }
+
+    fn union_all<O: Compound<R>>(self, other: O) -> CompoundQueryBuilder<R> {
+        CompoundQueryBuilder::create(SetOperator::UnionAll, self, other)
+    }
+
+    // `INTERSECT` and `EXCEPT` are only supported by MySQL 8.0.31 and later, older servers reject
+    // the query as a syntax error
+    fn intersect<O: Compound<R>>(self, other: O) -> CompoundQueryBuilder<R> {
+        CompoundQueryBuilder::create(SetOperator::Intersect, self, other)
+    }
+
+    // Requires MySQL 8.0.31 or later, as `intersect`
+    fn except<O: Compound<R>>(self, other: O) -> CompoundQueryBuilder<R> {
+        CompoundQueryBuilder::create(SetOperator::Except, self, other)
+    }
+}
+
+impl<R: Value, RTags: TagList> Compound<R> for MappedQueryBuilder<R, RTags, MultiRows> {
+    fn compound_operand(self) -> SelectQuery {
+        self.into_select_query()
+    }
+}
+
+impl<R: Value> Compound<R> for CompoundQueryBuilder<R> {
+    fn compound_operand(self) -> SelectQuery {
+        self.select_query(true)
+    }
+}
+
+impl<R: Value> CompoundQueryBuilder<R> {
+    fn create<L: Compound<R>, O: Compound<R>>(operator: SetOperator, left: L, right: O) -> Self {
+        let mut alias_generator = AliasGenerator::create();
+
+        CompoundQueryBuilder {
+            query: CompoundSelect {
+                operator,
+                left: left.compound_operand(),
+                right: right.compound_operand(),
+                alias: alias_generator.generate_compound_alias(),
+            },
+            alias_generator,
+            order_by_items: vec![],
+            limit: None,
+            offset: 0,
+            _marker: Default::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+
+        self
+    }
+
+    #[must_use]
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+
+        self
+    }
+
+    fn alias(&self) -> &Alias {
+        &self.query.alias
+    }
+
+    fn select_query(&self, with_alias: bool) -> SelectQuery {
+        let columns = AliasGenerator::select_aliases::<R>()
+            .into_iter()
+            .map(|column| self.alias().create_ident_expr(&column))
+            .collect::<Vec<Expr>>();
+
+        SelectQuery::create(
+            self.query.clone().source(),
+            self.alias_generator
+                .generate_select_list(columns, with_alias),
+            self.order_by_items.clone(),
+            self.limit,
+            self.offset,
+        )
+    }
+}
+
+impl<R: CteValue> Sort<R::View> for CompoundQueryBuilder<R> {
+    type Result = Self;
+
+    fn sort<T: SortResult, F: Fn(R::View) -> T>(mut self, f: F) -> Self::Result {
+        let result = f(R::view_of_alias(self.alias()));
+        self.order_by_items = result.order_by_items();
+
+        self
+    }
+}
+
+impl<R: Value, DB: Database> Executable<R, DB> for CompoundQueryBuilder<R>
+where
+    SelectQuery: YukinoQuery<DB>,
+{
+    type ResultType = MultiRows;
+    type Query = SelectQuery;
+
+    fn generate_query(self) -> Self::Query {
+        self.select_query(true)
+    }
+}
+
+impl<T: Value<L = U1>> SubqueryView<T> for CompoundQueryBuilder<T> {
+    fn subquery(&self) -> SelectQuery {
+        self.select_query(false)
+    }
+}
@@ -0,0 +1,387 @@
+use std::marker::PhantomData;
+use std::ops::{Add, Sub};
+
+use generic_array::{GenericArray, sequence::Split, typenum::Sum};
+use sqlx::Database;
+
+use query_builder::{
+    Alias, AliasedTable, CommonTableExpr, Expr, IntoSelectSource, Join, JoinType, OrderByItem,
+    Select, SelectFrom, SelectQuery, YukinoQuery,
+};
+
+use crate::operator::SortResult;
+use crate::query::{
+    AliasGenerator, Executable, Filter, Filter2, Fold, FoldedQueryBuilder, FoldResult,
+    GroupResult, Map, Map2, MappedQueryBuilder, MultiRows, Sort,
+};
+use crate::view::{
+    EntityView, EntityWithView, ExprViewBox, ExprViewBoxWithTag, MergeList, NotInList, ScalarValue,
+    TagList, TagsOfValueView, Value, ValueCount, ValueCountOf, WindowViewTag,
+};
+
+// Values of rows of CTEs and compound selects, elements of pairs are viewed separately as in `map`
+pub trait CteValue: Value {
+    type View: Clone;
+
+    fn cte_view(exprs: GenericArray<Expr, ValueCountOf<Self>>) -> Self::View;
+
+    // View of the columns selected from the CTE or compound select aliased by `alias`
+    fn view_of_alias(alias: &Alias) -> Self::View {
+        let exprs = AliasGenerator::select_aliases::<Self>()
+            .into_iter()
+            .map(|column| alias.create_ident_expr(&column));
+
+        Self::cte_view(GenericArray::from_exact_iter(exprs).unwrap())
+    }
+}
+
+impl<T: ScalarValue> CteValue for T {
+    type View = ExprViewBox<T>;
+
+    fn cte_view(exprs: GenericArray<Expr, ValueCountOf<Self>>) -> Self::View {
+        T::view_from_exprs(exprs)
+    }
+}
+
+impl<L: CteValue, R: CteValue> CteValue for (L, R)
+where
+    TagsOfValueView<L>: MergeList<TagsOfValueView<R>>,
+    ValueCountOf<L>: Add<ValueCountOf<R>>,
+    Sum<ValueCountOf<L>, ValueCountOf<R>>:
+        ValueCount + Sub<ValueCountOf<L>, Output = ValueCountOf<R>>,
+{
+    type View = (L::View, R::View);
+
+    fn cte_view(exprs: GenericArray<Expr, ValueCountOf<Self>>) -> Self::View {
+        let (v0, v1) = Split::split(exprs);
+
+        (L::cte_view(v0), R::cte_view(v1))
+    }
+}
+
+pub struct CteQueryBuilder<R: CteValue> {
+    query: SelectFrom,
+    alias: Alias,
+    alias_generator: AliasGenerator,
+    _marker: PhantomData<R>,
+}
+
+pub struct SortedCteQueryBuilder<R: CteValue> {
+    nested: CteQueryBuilder<R>,
+    order_by: Vec<OrderByItem>,
+    limit: Option<usize>,
+    offset: usize,
+}
+
+pub struct JoinedCteQueryBuilder<R: CteValue, E: EntityWithView> {
+    query: SelectFrom,
+    alias: Alias,
+    joined_alias: Alias,
+    alias_generator: AliasGenerator,
+    _marker: PhantomData<(R, E)>,
+}
+
+// `WITH name AS (query)`, rows of the CTE can be filtered, sorted, limited, folded, mapped and
+// joined with entities. Grouping them is not supported, and neither are several CTEs in one `WITH`,
+// a CTE can be defined by a query on another one instead, which nests the `WITH` of the latter.
+pub fn with<R: CteValue, RTags: TagList>(
+    name: &'static str,
+    query: MappedQueryBuilder<R, RTags, MultiRows>,
+) -> CteQueryBuilder<R> {
+    CteQueryBuilder::create(
+        name,
+        Some(CommonTableExpr {
+            name: name.to_string(),
+            columns: AliasGenerator::select_aliases::<R>(),
+            query: query.into_select_query(),
+            recursive: None,
+        }),
+    )
+}
+
+// `WITH RECURSIVE name AS (anchor UNION ALL step)`, `step` is built from the rows of the CTE
+// itself, e.g. joining the children of nodes found in the previous step
+pub fn with_recursive<
+    R: CteValue,
+    RTags: TagList,
+    STags: TagList,
+    F: Fn(CteQueryBuilder<R>) -> MappedQueryBuilder<R, STags, MultiRows>,
+>(
+    name: &'static str,
+    anchor: MappedQueryBuilder<R, RTags, MultiRows>,
+    step: F,
+) -> CteQueryBuilder<R> {
+    let recursive = step(CteQueryBuilder::create(name, None)).into_select_query();
+
+    CteQueryBuilder::create(
+        name,
+        Some(CommonTableExpr {
+            name: name.to_string(),
+            columns: AliasGenerator::select_aliases::<R>(),
+            query: anchor.into_select_query(),
+            recursive: Some(recursive),
+        }),
+    )
+}
+
+impl<R: CteValue> CteQueryBuilder<R> {
+    // The definition is absent if the CTE is referenced inside itself
+    fn create(name: &'static str, cte: Option<CommonTableExpr>) -> Self {
+        let mut alias_generator = AliasGenerator::create();
+        let alias = alias_generator.generate_cte_alias(name);
+        let mut query = Select::from(name.to_string(), alias.clone());
+
+        if let Some(cte) = cte {
+            query.with(cte);
+        }
+
+        CteQueryBuilder {
+            query,
+            alias,
+            alias_generator,
+            _marker: Default::default(),
+        }
+    }
+
+    fn view(&self) -> R::View {
+        R::view_of_alias(&self.alias)
+    }
+
+    // Inner join with the rows of `E` satisfying `on`
+    pub fn join<
+        E: EntityWithView,
+        C: Into<ExprViewBoxWithTag<bool, Tags>>,
+        Tags: TagList,
+        F: Fn(R::View, E::View) -> C,
+    >(
+        mut self,
+        on: F,
+    ) -> JoinedCteQueryBuilder<R, E> {
+        let joined_alias = self.alias_generator.generate_root_alias::<E>();
+        let on = on(self.view(), E::View::pure(&joined_alias))
+            .into()
+            .collect_expr()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        self.query.add_joins(vec![Join {
+            ty: JoinType::InnerJoin,
+            table: AliasedTable {
+                table: E::table_name().to_string(),
+                alias: joined_alias.clone(),
+            },
+            on,
+        }]);
+
+        JoinedCteQueryBuilder {
+            query: self.query,
+            alias: self.alias,
+            joined_alias,
+            alias_generator: self.alias_generator,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<R: CteValue> Filter<R::View> for CteQueryBuilder<R> {
+    fn filter<F, T: Into<ExprViewBoxWithTag<bool, Tags>>, Tags: TagList>(mut self, f: F) -> Self
+    where
+        F: Fn(R::View) -> T,
+        WindowViewTag: NotInList<Tags>,
+    {
+        let view = f(self.view()).into();
+
+        view.collect_expr().into_iter().for_each(|e| {
+            self.query.and_where(e);
+        });
+
+        self
+    }
+}
+
+impl<R: CteValue> Fold<<R::View as GroupResult>::Vertical> for CteQueryBuilder<R>
+where
+    R::View: GroupResult,
+{
+    fn fold<RV: FoldResult, F: Fn(<R::View as GroupResult>::Vertical) -> RV>(
+        self,
+        f: F,
+    ) -> FoldedQueryBuilder<RV> {
+        let result = f(self.view().vertical_view());
+
+        FoldedQueryBuilder::create(self.query.source(), result, self.alias_generator)
+    }
+}
+
+impl<R: CteValue> Map<R::View> for CteQueryBuilder<R> {
+    type ResultType = MultiRows;
+
+    fn map<
+        T: Value,
+        TTags: TagList,
+        RV: Into<ExprViewBoxWithTag<T, TTags>>,
+        F: Fn(R::View) -> RV,
+    >(
+        self,
+        f: F,
+    ) -> MappedQueryBuilder<T, TTags, Self::ResultType> {
+        let result_view = f(self.view()).into();
+
+        MappedQueryBuilder::create(
+            self.query.source(),
+            vec![],
+            result_view,
+            self.alias_generator,
+        )
+    }
+}
+
+impl<R: CteValue> Sort<R::View> for CteQueryBuilder<R> {
+    type Result = SortedCteQueryBuilder<R>;
+
+    fn sort<T: SortResult, F: Fn(R::View) -> T>(self, f: F) -> Self::Result {
+        let result = f(self.view());
+
+        SortedCteQueryBuilder {
+            nested: self,
+            order_by: result.order_by_items(),
+            limit: None,
+            offset: 0,
+        }
+    }
+}
+
+impl<R: CteValue> SortedCteQueryBuilder<R> {
+    #[must_use]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+
+        self
+    }
+
+    #[must_use]
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+
+        self
+    }
+}
+
+impl<R: CteValue> Map<R::View> for SortedCteQueryBuilder<R> {
+    type ResultType = MultiRows;
+
+    fn map<
+        T: Value,
+        TTags: TagList,
+        RV: Into<ExprViewBoxWithTag<T, TTags>>,
+        F: Fn(R::View) -> RV,
+    >(
+        self,
+        f: F,
+    ) -> MappedQueryBuilder<T, TTags, Self::ResultType> {
+        let result_view = f(self.nested.view()).into();
+        let mapped = MappedQueryBuilder::create(
+            self.nested.query.source(),
+            self.order_by,
+            result_view,
+            self.nested.alias_generator,
+        )
+        .offset(self.offset);
+
+        match self.limit {
+            Some(limit) => mapped.limit(limit),
+            None => mapped,
+        }
+    }
+}
+
+impl<R: CteValue, DB: Database> Executable<R, DB> for CteQueryBuilder<R>
+where
+    SelectQuery: YukinoQuery<DB>,
+{
+    type ResultType = MultiRows;
+    type Query = SelectQuery;
+
+    fn generate_query(self) -> Self::Query {
+        SortedCteQueryBuilder {
+            nested: self,
+            order_by: vec![],
+            limit: None,
+            offset: 0,
+        }
+        .generate_query()
+    }
+}
+
+impl<R: CteValue, DB: Database> Executable<R, DB> for SortedCteQueryBuilder<R>
+where
+    SelectQuery: YukinoQuery<DB>,
+{
+    type ResultType = MultiRows;
+    type Query = SelectQuery;
+
+    fn generate_query(self) -> Self::Query {
+        let columns = AliasGenerator::select_aliases::<R>()
+            .iter()
+            .map(|column| self.nested.alias.create_ident_expr(column))
+            .collect::<Vec<_>>();
+
+        SelectQuery::create(
+            self.nested.query.source(),
+            self.nested
+                .alias_generator
+                .generate_select_list(columns, true),
+            self.order_by,
+            self.limit,
+            self.offset,
+        )
+    }
+}
+
+impl<R: CteValue, E: EntityWithView> Filter2<R::View, E::View> for JoinedCteQueryBuilder<R, E> {
+    fn filter<F, T: Into<ExprViewBoxWithTag<bool, Tags>>, Tags: TagList>(mut self, f: F) -> Self
+    where
+        F: Fn(R::View, E::View) -> T,
+        WindowViewTag: NotInList<Tags>,
+    {
+        let view = f(
+            R::view_of_alias(&self.alias),
+            E::View::pure(&self.joined_alias),
+        )
+        .into();
+
+        view.collect_expr().into_iter().for_each(|e| {
+            self.query.and_where(e);
+        });
+
+        self
+    }
+}
+
+impl<R: CteValue, E: EntityWithView> Map2<R::View, E::View> for JoinedCteQueryBuilder<R, E> {
+    type ResultType = MultiRows;
+
+    fn map<
+        T: Value,
+        TTags: TagList,
+        RV: Into<ExprViewBoxWithTag<T, TTags>>,
+        F: Fn(R::View, E::View) -> RV,
+    >(
+        self,
+        f: F,
+    ) -> MappedQueryBuilder<T, TTags, Self::ResultType> {
+        let result_view = f(
+            R::view_of_alias(&self.alias),
+            E::View::pure(&self.joined_alias),
+        )
+        .into();
+
+        MappedQueryBuilder::create(
+            self.query.source(),
+            vec![],
+            result_view,
+            self.alias_generator,
+        )
+    }
+}
@@ -2,12 +2,17 @@ use std::marker::PhantomData;
 use std::vec::IntoIter;
 
 use async_trait::async_trait;
+use futures::{future, FutureExt, SinkExt, stream, StreamExt};
+use futures::channel::mpsc::channel;
+use futures::stream::BoxStream;
 use generic_array::{ArrayLength, typenum::U0};
-use sqlx::{Database, Error, Executor, MySql, query};
+use sqlx::{Database, Error, Executor, IntoArguments, query};
+use sqlx::database::HasArguments;
 use sqlx::query::Query;
 
 use query_builder::{
-    AppendToArgs, BindArgs, ColumnOf, DatabaseValue, QueryBuildState, ToSql, YukinoQuery,
+    AppendToArgs, BindArgs, ColumnOf, DatabaseValue, Dialect, HasDialect, QueryBuildState, ToSql,
+    YukinoQuery,
 };
 
 use crate::view::{DBMapping, Value, ValueCountOf};
@@ -29,25 +34,56 @@ pub trait Executable<T: Value, DB: Database> {
 impl ExecuteResultType for SingleRow {}
 impl ExecuteResultType for MultiRows {}
 
+// `generate_query` with the database named, which cannot be inferred if more than one is enabled
+pub trait GenerateQueryFor<T: Value>: Sized {
+    fn generate_query_for<DB: Database>(self) -> <Self as Executable<T, DB>>::Query
+    where
+        Self: Executable<T, DB>,
+    {
+        Executable::<T, DB>::generate_query(self)
+    }
+}
+
+impl<T: Value, E> GenerateQueryFor<T> for E {}
+
+// Queries the dialect cannot express (e.g. `POWER` in SQLite) or binding values which failed to
+// convert (e.g. JSON maps with non-string keys) fail here instead of panicking
+pub(crate) fn render_query<Q: ToSql>(
+    query: &Q,
+    dialect: &'static dyn Dialect,
+) -> Result<String, Error> {
+    let mut state = QueryBuildState::create(dialect);
+    query.to_sql(&mut state).map_err(|e| {
+        Error::Configuration(
+            format!(
+                "Query is not supported by the database or binds invalid values: {}",
+                e
+            )
+            .into(),
+        )
+    })?;
+
+    Ok(state.to_string())
+}
+
 #[async_trait]
-pub trait FetchOne<T: Value + for<'r> DBMapping<'r, MySql, U0>>:
-    Executable<T, MySql, ResultType = SingleRow>
+pub trait FetchOne<T: Value + for<'r> DBMapping<'r, DB, U0>, DB: HasDialect>:
+    Executable<T, DB, ResultType = SingleRow>
 {
-    async fn exec<'c, 'e, E: 'e + Executor<'c, Database = MySql>>(
+    async fn exec<'c, 'e, E: 'e + Executor<'c, Database = DB>>(
         self,
         executor: E,
     ) -> Result<T, Error>
     where
         Self: Sized,
-        DatabaseValue: for<'q> AppendToArgs<'q, MySql>,
+        DatabaseValue: for<'q> AppendToArgs<'q, DB>,
+        for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
         <ValueCountOf<T> as ArrayLength<DatabaseValue>>::ArrayType: Unpin,
-        ValueCountOf<T>: for<'r> ArrayLength<ColumnOf<MySql>>,
+        ValueCountOf<T>: for<'r> ArrayLength<ColumnOf<DB>>,
     {
         let yukino_query = self.generate_query();
-        let mut state = QueryBuildState::default();
-        yukino_query.to_sql(&mut state).unwrap();
-        let raw_query = state.to_string();
-        let query: Query<MySql, _> = query(&raw_query);
+        let raw_query = render_query(&yukino_query, DB::dialect())?;
+        let query: Query<DB, _> = query(&raw_query);
         let query_with_args = yukino_query.bind_args(query);
         let row = query_with_args.fetch_one(executor).await?;
 
@@ -81,24 +117,22 @@ where
 }
 
 #[async_trait]
-pub trait FetchMulti<T: Value + for<'r> DBMapping<'r, MySql, U0>>:
-    Executable<T, MySql, ResultType = MultiRows>
+pub trait FetchMulti<T: Value + for<'r> DBMapping<'r, DB, U0>, DB: HasDialect>:
+    Executable<T, DB, ResultType = MultiRows>
 {
-    async fn exec<'c: 'e, 'e, E: 'e + Executor<'c, Database = MySql>>(
+    async fn exec<'c: 'e, 'e, E: 'e + Executor<'c, Database = DB>>(
         self,
         executor: E,
-    ) -> Result<QueryResultIterator<MySql, T>, Error>
+    ) -> Result<QueryResultIterator<DB, T>, Error>
     where
         Self: Sized,
-        DatabaseValue: for<'q> AppendToArgs<'q, MySql>,
+        DatabaseValue: for<'q> AppendToArgs<'q, DB>,
+        for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
         <ValueCountOf<T> as ArrayLength<DatabaseValue>>::ArrayType: Unpin,
-        ValueCountOf<T>: for<'r> ArrayLength<ColumnOf<MySql>>,
+        ValueCountOf<T>: for<'r> ArrayLength<ColumnOf<DB>>,
     {
         let yukino_query = self.generate_query();
-
-        let mut state = QueryBuildState::default();
-        yukino_query.to_sql(&mut state).unwrap();
-        let query_str = state.to_string();
+        let query_str = render_query(&yukino_query, DB::dialect())?;
 
         let query_result = yukino_query
             .bind_args(query(&query_str))
@@ -111,17 +145,57 @@ pub trait FetchMulti<T: Value + for<'r> DBMapping<'r, MySql, U0>>:
             _marker: PhantomData,
         })
     }
+
+    fn exec_stream<'c: 'e, 'e, E: 'e + Executor<'c, Database = DB>>(
+        self,
+        executor: E,
+    ) -> BoxStream<'e, Result<T, Error>>
+    where
+        Self: Sized,
+        Self::Query: 'e,
+        T: Send + 'e,
+        DatabaseValue: for<'q> AppendToArgs<'q, DB>,
+        for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+        <ValueCountOf<T> as ArrayLength<DatabaseValue>>::ArrayType: Unpin,
+        ValueCountOf<T>: for<'r> ArrayLength<ColumnOf<DB>>,
+    {
+        let yukino_query = self.generate_query();
+        let query_str = match render_query(&yukino_query, DB::dialect()) {
+            Ok(query_str) => query_str,
+            Err(e) => return stream::once(future::ready(Err(e))).boxed(),
+        };
+
+        // The row stream borrows the query string, so it is driven by a future owning both,
+        // rows are handed over one by one through the channel.
+        let (mut sender, receiver) = channel(0);
+        let producer = async move {
+            let mut rows = yukino_query.bind_args(query(&query_str)).fetch(executor);
+
+            while let Some(row) = rows.next().await {
+                let result = row.and_then(|row| T::from_result(&row));
+                if sender.send(result).await.is_err() {
+                    break;
+                }
+            }
+        };
+
+        stream::select(receiver.map(Some), producer.into_stream().map(|_| None))
+            .filter_map(future::ready)
+            .boxed()
+    }
 }
 
 impl<
-        T: Value + for<'r> DBMapping<'r, MySql, U0>,
-        E: Executable<T, MySql, ResultType = SingleRow>,
-    > FetchOne<T> for E
+        T: Value + for<'r> DBMapping<'r, DB, U0>,
+        DB: HasDialect,
+        E: Executable<T, DB, ResultType = SingleRow>,
+    > FetchOne<T, DB> for E
 {
 }
 impl<
-        T: Value + for<'r> DBMapping<'r, MySql, U0>,
-        E: Executable<T, MySql, ResultType = MultiRows>,
-    > FetchMulti<T> for E
+        T: Value + for<'r> DBMapping<'r, DB, U0>,
+        DB: HasDialect,
+        E: Executable<T, DB, ResultType = MultiRows>,
+    > FetchMulti<T, DB> for E
 {
 }
@@ -3,7 +3,7 @@ use std::marker::PhantomData;
 
 use sqlx::Database;
 
-use interface::{Association, FieldMarker, WithPrimaryKey};
+use interface::{Association, FieldMarker, ForeignKeyMarker, ParentOf, WithPrimaryKey};
 use query_builder::{
     Alias, Expr, IntoSelectSource, OrderByItem, Select, SelectFrom, SelectItem, SelectQuery,
     YukinoQuery,
@@ -12,13 +12,13 @@ use query_builder::{
 use crate::operator::{In, SortResult};
 use crate::query::{
     AliasGenerator, AssociationBuilder, Delete, DeletionBuilder, Executable, Fold,
-    FoldedQueryBuilder, FoldResult, GroupBy, GroupedQueryBuilder, GroupResult, Map, MappedQueryBuilder,
-    MultiRows, Sort, Update, UpdateQueryBuilder,
+    FoldedQueryBuilder, FoldResult, GroupBy, GroupedQueryBuilder, GroupResult, JoinedQueryBuilder,
+    Map, MappedQueryBuilder, MultiRows, OptionalView, Sort, Update, UpdateQueryBuilder,
 };
 use crate::view::{
     AssociatedView, EntityView, EntityWithView, ExprBoxOfAssociatedView, ExprView,
-    ExprViewBoxWithTag, FieldMarkerWithView, TagList, TagOfMarker, TypeOfMarker, Value,
-    ViewWithPrimaryKey,
+    ExprViewBoxWithTag, FieldMarkerWithView, NotInList, TagList, TagOfMarker, TypeOfMarker, Value,
+    ViewWithPrimaryKey, WindowViewTag,
 };
 
 pub struct FilteredQueryBuilder<E: EntityWithView> {
@@ -37,20 +37,23 @@ pub trait Filter<View> {
     #[must_use]
     fn filter<F, R: Into<ExprViewBoxWithTag<bool, Tags>>, Tags: TagList>(self, f: F) -> Self
     where
-        F: Fn(View) -> R;
+        F: Fn(View) -> R,
+        WindowViewTag: NotInList<Tags>;
 }
 
 pub trait Filter2<View1, View2> {
     #[must_use]
     fn filter<F, R: Into<ExprViewBoxWithTag<bool, Tags>>, Tags: TagList>(self, f: F) -> Self
     where
-        F: Fn(View1, View2) -> R;
+        F: Fn(View1, View2) -> R,
+        WindowViewTag: NotInList<Tags>;
 }
 
 impl<E: EntityWithView> Filter<E::View> for FilteredQueryBuilder<E> {
     fn filter<F, R: Into<ExprViewBoxWithTag<bool, Tags>>, Tags: TagList>(mut self, f: F) -> Self
     where
         F: Fn(E::View) -> R,
+        WindowViewTag: NotInList<Tags>,
     {
         let view = f(E::View::pure(&self.root_alias)).into();
 
@@ -177,6 +180,79 @@ impl<E: EntityWithView> FilteredQueryBuilder<E> {
             _entity: Default::default(),
         }
     }
+
+    pub fn join<ForeignField: ForeignKeyMarker + FieldMarkerWithView + FieldMarker<Entity = E>>(
+        self,
+    ) -> JoinedQueryBuilder<E, <ParentOf<ForeignField> as EntityWithView>::View>
+    where
+        ParentOf<ForeignField>: EntityWithView,
+        TypeOfMarker<ForeignField>: Value,
+        E: Association<
+            ParentOf<ForeignField>,
+            ForeignField,
+            ForeignKeyType = TypeOfMarker<ForeignField>,
+        >,
+    {
+        JoinedQueryBuilder::create(
+            self.query,
+            self.root_alias,
+            self.alias_generator,
+            E::foreign_key_columns(),
+        )
+    }
+
+    // Parents referenced by the rows of this query
+    pub fn referenced<
+        ForeignField: ForeignKeyMarker + FieldMarkerWithView + FieldMarker<Entity = E>,
+    >(
+        self,
+    ) -> FilteredQueryBuilder<ParentOf<ForeignField>>
+    where
+        ParentOf<ForeignField>: EntityWithView,
+        TypeOfMarker<ForeignField>: Value,
+        E: Association<
+            ParentOf<ForeignField>,
+            ForeignField,
+            ForeignKeyType = TypeOfMarker<ForeignField>,
+        >,
+    {
+        let subquery = self
+            .query
+            .select(select_columns(&self.root_alias, E::foreign_key_columns()));
+
+        let mut result = ParentOf::<ForeignField>::all();
+        let columns = result
+            .root_alias
+            .create_columns_expr(ParentOf::<ForeignField>::primary_key_columns());
+
+        result
+            .query
+            .and_where(Expr::In(Box::new(columns), subquery));
+
+        result
+    }
+
+    pub fn left_join<
+        ForeignField: ForeignKeyMarker + FieldMarkerWithView + FieldMarker<Entity = E>,
+    >(
+        self,
+    ) -> JoinedQueryBuilder<E, OptionalView<ParentOf<ForeignField>>>
+    where
+        ParentOf<ForeignField>: EntityWithView,
+        TypeOfMarker<ForeignField>: Value,
+        E: Association<
+            ParentOf<ForeignField>,
+            ForeignField,
+            ForeignKeyType = TypeOfMarker<ForeignField>,
+        >,
+    {
+        JoinedQueryBuilder::create(
+            self.query,
+            self.root_alias,
+            self.alias_generator,
+            E::foreign_key_columns(),
+        )
+    }
 }
 
 impl<E: EntityWithView> Map<E::View> for SortedFilteredQueryBuilder<E> {
@@ -239,46 +315,40 @@ where
         ForeignKeyTags = TagOfMarker<ForeignField>,
     >,
     ExprBoxOfAssociatedView<Children::View, Parent, ForeignField>:
-        In<<Parent as WithPrimaryKey>::PrimaryKeyType>,
+        In<<Parent as WithPrimaryKey>::PrimaryKeyType, OutputTags = TagOfMarker<ForeignField>>,
+    WindowViewTag: NotInList<TagOfMarker<ForeignField>>,
     TypeOfMarker<ForeignField>: Value + Ord + Hash,
 {
     fn build_query(self) -> FilteredQueryBuilder<Children> {
-        let subquery = self.query.select(vec![SelectItem {
-            expr: self
-                .root_alias
-                .create_ident_expr(Parent::primary_key_name()),
-            alias: Some("result_0".to_string()),
-        }]);
+        let subquery = self.query.select(select_columns(
+            &self.root_alias,
+            Parent::primary_key_columns(),
+        ));
 
         let mut result = Children::all();
-        let ident = result
+        let columns = result
             .root_alias
-            .create_ident_expr(Children::foreign_key_name());
+            .create_columns_expr(Children::foreign_key_columns());
 
-        result.query.and_where(Expr::In(Box::new(ident), subquery));
+        result
+            .query
+            .and_where(Expr::In(Box::new(columns), subquery));
 
         result
     }
 
     fn build_from_parent_view(parent_view: &Parent::View) -> FilteredQueryBuilder<Children> {
         let mut result = Children::all();
-        let primary_key_view = parent_view;
 
-        let ident = result
-            .root_alias
-            .create_ident_expr(Children::foreign_key_name());
-
-        result.query.and_where(Expr::Eq(
-            Box::new(ident),
-            Box::new(
-                primary_key_view
-                    .primary_key()
-                    .collect_expr()
-                    .into_iter()
-                    .next()
-                    .unwrap(),
-            ),
-        ));
+        for (foreign_key, primary_key) in Children::foreign_key_columns()
+            .iter()
+            .zip(parent_view.primary_key().collect_expr())
+        {
+            result.query.and_where(Expr::Eq(
+                Box::new(result.root_alias.create_ident_expr(foreign_key)),
+                Box::new(primary_key),
+            ));
+        }
 
         result
     }
@@ -286,6 +356,17 @@ where
     fn build_from_parent_entities(
         primary_keys: Vec<TypeOfMarker<ForeignField>>,
     ) -> FilteredQueryBuilder<Children> {
-        Children::all().filter(|view| view.foreign_key().clone().in_arr(primary_keys.clone()))
+        Children::all().filter(|view| view.foreign_key().in_arr(primary_keys.clone()))
     }
 }
+
+fn select_columns(alias: &Alias, columns: &[&str]) -> Vec<SelectItem> {
+    columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| SelectItem {
+            expr: alias.create_ident_expr(column),
+            alias: Some(format!("result_{}", index)),
+        })
+        .collect()
+}
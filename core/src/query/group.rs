@@ -15,6 +15,7 @@ use crate::query::{
 use crate::view::{
     ConcreteList, EntityView, EntityViewTag, EntityWithView, ExprViewBoxWithTag, MergeList,
     NotInList, TagList, TagsOfValueView, Value, ValueCountOf, VerticalExprView, VerticalView,
+    WindowViewTag,
 };
 
 pub trait GroupResult: Clone {
@@ -106,6 +107,7 @@ impl<View: GroupResult, AggregateView: FoldResult, E: EntityWithView> Filter2<Vi
     fn filter<F, R: Into<ExprViewBoxWithTag<bool, Tags>>, Tags: TagList>(mut self, f: F) -> Self
     where
         F: Fn(View, AggregateView) -> R,
+        WindowViewTag: NotInList<Tags>,
     {
         let result = f(self.view.clone(), self.aggregate.clone()).into();
 
@@ -127,6 +129,7 @@ impl<View: GroupResult, E: EntityWithView> Filter<View> for GroupedQueryBuilder<
     fn filter<F, R: Into<ExprViewBoxWithTag<bool, Tags>>, Tags: TagList>(mut self, f: F) -> Self
     where
         F: Fn(View) -> R,
+        WindowViewTag: NotInList<Tags>,
     {
         let result = f(self.view.clone()).into();
 
@@ -382,6 +385,7 @@ impl<View: GroupResult, E: EntityWithView> GroupFold<View, E>
 impl<T1: Value, T1Tags: TagList> GroupResult for ExprViewBoxWithTag<T1, T1Tags>
 where
     EntityViewTag: NotInList<T1Tags>,
+    WindowViewTag: NotInList<T1Tags>,
 {
     type Value = T1;
     type Tags = T1Tags;
@@ -409,6 +413,7 @@ where
     (T1, T2): Value,
     TagsOfValueView<T1>: MergeList<TagsOfValueView<T2>>,
     EntityViewTag: NotInList<T1Tags> + NotInList<T2Tags>,
+    WindowViewTag: NotInList<T1Tags> + NotInList<T2Tags>,
     ValueCountOf<T1>: Add<ValueCountOf<T2>, Output = ValueCountOf<(T1, T2)>>,
     ValueCountOf<(T1, T2)>: Sub<ValueCountOf<T1>, Output = ValueCountOf<T2>>,
 {
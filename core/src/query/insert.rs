@@ -1,9 +1,22 @@
-use sqlx::Database;
+use std::marker::PhantomData;
 
-use query_builder::{ArgSourceList, Insert, InsertQuery, YukinoQuery};
+use async_trait::async_trait;
+use generic_array::{ArrayLength, typenum::U0};
+use sqlx::{Acquire, Database, Error, Executor, IntoArguments, query};
+use sqlx::database::HasArguments;
 
-use crate::query::{Executable, MultiRows};
-use crate::view::{EntityWithView, Insertable};
+use interface::YukinoEntity;
+use query_builder::{
+    Alias, AppendToArgs, ArgSourceList, AssignmentItem, AssignmentValue, BindArgs, ColumnOf,
+    DatabaseValue, Expr, HasDialect, Insert, InsertQuery, OnConflict, Order, OrderByItem, Select,
+    SelectItem, SelectQuery, SelectSource, SingleSource, YukinoQuery,
+};
+
+use crate::query::{Executable, MultiRows, render_query};
+use crate::view::{
+    DBMapping, EntityView, EntityWithView, ExprView, ExprViewBoxWithTag, FieldMarkerWithView,
+    Insertable, TagList, Value, ValueCountOf,
+};
 
 impl<DB: Database, S: for<'q> ArgSourceList<'q, DB>> Executable<(), DB> for InsertQuery<DB, S>
 where
@@ -36,3 +49,260 @@ impl<
         )
     }
 }
+
+pub struct UpsertAssignments<E: EntityWithView> {
+    assignments: Vec<AssignmentItem>,
+    _entity: PhantomData<E>,
+}
+
+impl<E: EntityWithView> UpsertAssignments<E> {
+    pub fn create() -> Self {
+        UpsertAssignments {
+            assignments: vec![],
+            _entity: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn set<
+        FMarker: FieldMarkerWithView<Entity = E, FieldType = T>,
+        T: Value,
+        Tags: TagList,
+        V: Into<ExprViewBoxWithTag<T, Tags>>,
+    >(
+        mut self,
+        _m: FMarker,
+        v: V,
+    ) -> Self {
+        let result = v.into();
+        self.assignments.extend(
+            FMarker::columns()
+                .into_iter()
+                .zip(result.collect_expr())
+                .map(|(column, e)| AssignmentItem {
+                    column,
+                    value: AssignmentValue::Expr(Box::new(e)),
+                }),
+        );
+
+        self
+    }
+}
+
+impl<E: EntityWithView> Default for UpsertAssignments<E> {
+    fn default() -> Self {
+        Self::create()
+    }
+}
+
+pub trait InsertSource<DB: Database>: for<'q> ArgSourceList<'q, DB> {
+    type Entity: EntityWithView;
+    type Returning;
+
+    fn row_count(&self) -> usize;
+
+    fn collect_returning(entities: Vec<Self::Entity>) -> Result<Self::Returning, Error>;
+}
+
+impl<DB: Database, InsertObject: Insertable<DB>> InsertSource<DB> for SingleSource<InsertObject> {
+    type Entity = InsertObject::Entity;
+    type Returning = InsertObject::Entity;
+
+    fn row_count(&self) -> usize {
+        1
+    }
+
+    fn collect_returning(entities: Vec<Self::Entity>) -> Result<Self::Returning, Error> {
+        entities.into_iter().next().ok_or(Error::RowNotFound)
+    }
+}
+
+impl<DB: Database, InsertObject: Insertable<DB>> InsertSource<DB> for Vec<InsertObject> {
+    type Entity = InsertObject::Entity;
+    type Returning = Vec<InsertObject::Entity>;
+
+    fn row_count(&self) -> usize {
+        self.len()
+    }
+
+    fn collect_returning(entities: Vec<Self::Entity>) -> Result<Self::Returning, Error> {
+        Ok(entities)
+    }
+}
+
+// The primary key is used as the conflict target on databases requiring one
+pub trait Upsert<E: EntityWithView>: Sized {
+    fn on_conflict_update<F: FnOnce(E::View) -> UpsertAssignments<E>>(self, f: F) -> Self;
+
+    fn on_conflict_do_nothing(self) -> Self;
+}
+
+impl<DB: Database, S: InsertSource<DB>> Upsert<S::Entity> for InsertQuery<DB, S> {
+    fn on_conflict_update<
+        F: FnOnce(<S::Entity as EntityWithView>::View) -> UpsertAssignments<S::Entity>,
+    >(
+        mut self,
+        f: F,
+    ) -> Self {
+        let excluded = <S::Entity as EntityWithView>::View::pure(&OnConflict::excluded());
+        self.on_conflict(OnConflict::DoUpdate {
+            target: primary_key_columns::<S::Entity>(),
+            assignments: f(excluded).assignments,
+        });
+
+        self
+    }
+
+    fn on_conflict_do_nothing(mut self) -> Self {
+        self.on_conflict(OnConflict::DoNothing {
+            target: primary_key_columns::<S::Entity>(),
+        });
+
+        self
+    }
+}
+
+#[async_trait]
+pub trait InsertReturning<DB: HasDialect, S: InsertSource<DB>>: Sized {
+    // Insert and fetch the inserted rows back, with `RETURNING` if the database supports it.
+    // Otherwise the rows are selected by the auto increment primary key, from `LAST_INSERT_ID()`
+    // to `LAST_INSERT_ID() + row_count - 1`, so the primary key must be generated by the database.
+    // This relies on InnoDB assigning consecutive ids to a multi-row `VALUES` insert, which may
+    // not hold with `innodb_autoinc_lock_mode = 2` under concurrent inserts. Upserts can not be
+    // fetched this way, since they may update a row instead of generating an id.
+    async fn exec_returning<'c, A: Acquire<'c, Database = DB> + Send>(
+        self,
+        acquire: A,
+    ) -> Result<S::Returning, Error>
+    where
+        S::Entity: for<'r> DBMapping<'r, DB, U0>,
+        for<'e> &'e mut DB::Connection: Executor<'e, Database = DB>,
+        DatabaseValue: for<'q> AppendToArgs<'q, DB>,
+        for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+        <ValueCountOf<S::Entity> as ArrayLength<DatabaseValue>>::ArrayType: Unpin,
+        ValueCountOf<S::Entity>: for<'r> ArrayLength<ColumnOf<DB>>;
+}
+
+#[async_trait]
+impl<DB: HasDialect, S: InsertSource<DB> + Send> InsertReturning<DB, S> for InsertQuery<DB, S> {
+    async fn exec_returning<'c, A: Acquire<'c, Database = DB> + Send>(
+        mut self,
+        acquire: A,
+    ) -> Result<S::Returning, Error>
+    where
+        S::Entity: for<'r> DBMapping<'r, DB, U0>,
+        for<'e> &'e mut DB::Connection: Executor<'e, Database = DB>,
+        DatabaseValue: for<'q> AppendToArgs<'q, DB>,
+        for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+        <ValueCountOf<S::Entity> as ArrayLength<DatabaseValue>>::ArrayType: Unpin,
+        ValueCountOf<S::Entity>: for<'r> ArrayLength<ColumnOf<DB>>,
+    {
+        let dialect = DB::dialect();
+        let table_name = <S::Entity as YukinoEntity>::table_name();
+        let alias = Alias {
+            name: table_name.to_string(),
+        };
+        let select_items = returning_items::<S::Entity>(&alias);
+        if !dialect.supports_returning() && self.conflict().is_some() {
+            return Err(Error::Configuration(
+                format!(
+                    "Upserted rows of `{}` cannot be fetched without `RETURNING`",
+                    table_name
+                )
+                .into(),
+            ));
+        }
+        let mut connection = acquire.acquire().await?;
+
+        let rows = if dialect.supports_returning() {
+            self.returning(select_items);
+            let query_str = render_query(&self, dialect)?;
+
+            self.bind_args(query(&query_str))
+                .fetch_all(&mut *connection)
+                .await?
+        } else {
+            let primary_key = auto_increment_primary_key::<S::Entity>()
+                .filter(|column| !self.columns().contains(column))
+                .ok_or_else(|| {
+                    Error::Configuration(
+                        format!(
+                            "Inserted rows of `{}` cannot be fetched without a generated primary \
+                             key",
+                            table_name
+                        )
+                        .into(),
+                    )
+                })?;
+
+            let row_count = self.values().row_count();
+            let query_str = render_query(&self, dialect)?;
+            let result = self
+                .bind_args(query(&query_str))
+                .execute(&mut *connection)
+                .await?;
+            // The id of the first row inserted by the statement
+            let first_id = DB::last_insert_id(&result).ok_or_else(|| {
+                Error::Configuration("Cannot get the id of the last inserted row".into())
+            })?;
+
+            let primary_key_expr = alias.create_ident_expr(&primary_key);
+            let mut from = Select::from(table_name.to_string(), alias.clone());
+            from.and_where(Expr::Bte(
+                Box::new(primary_key_expr.clone()),
+                Box::new(Expr::Lit(DatabaseValue::UnsignedBigInteger(first_id))),
+            ))
+            .and_where(Expr::Lt(
+                Box::new(primary_key_expr.clone()),
+                Box::new(Expr::Lit(DatabaseValue::UnsignedBigInteger(
+                    first_id + row_count as u64,
+                ))),
+            ));
+            let order_by = vec![OrderByItem {
+                expr: primary_key_expr,
+                order: Order::Asc,
+            }];
+            let select_query =
+                SelectQuery::create(SelectSource::From(from), select_items, order_by, None, 0);
+
+            let query_str = render_query(&select_query, dialect)?;
+
+            select_query
+                .bind_args(query(&query_str))
+                .fetch_all(&mut *connection)
+                .await?
+        };
+
+        S::collect_returning(
+            rows.iter()
+                .map(|row| S::Entity::from_result(row))
+                .collect::<Result<Vec<_>, _>>()?,
+        )
+    }
+}
+
+fn primary_key_columns<E: EntityWithView>() -> Vec<String> {
+    E::definition()
+        .primary_key_columns()
+        .map(|c| c.name.clone())
+        .collect()
+}
+
+fn auto_increment_primary_key<E: EntityWithView>() -> Option<String> {
+    E::definition()
+        .primary_key_columns()
+        .find(|c| c.auto_increment)
+        .map(|c| c.name.clone())
+}
+
+fn returning_items<E: EntityWithView>(alias: &Alias) -> Vec<SelectItem> {
+    E::View::pure(alias)
+        .collect_expr()
+        .into_iter()
+        .enumerate()
+        .map(|(index, expr)| SelectItem {
+            expr,
+            alias: Some(format!("U{}", index)),
+        })
+        .collect()
+}
@@ -0,0 +1,170 @@
+use std::marker::PhantomData;
+
+use interface::{FieldMarker, WithPrimaryKey, YukinoEntity};
+use query_builder::{Alias, AliasedTable, Expr, IntoSelectSource, Join, JoinType, SelectFrom};
+
+use crate::query::{AliasGenerator, Filter2, Map2, MappedQueryBuilder, MultiRows};
+use crate::view::{
+    EntityView, EntityWithView, ExprViewBox, ExprViewBoxWithTag, FieldMarkerWithView, NotInList,
+    NullableOf, NullableValue, TagList, TypeOfMarker, Value, WindowViewTag,
+};
+
+pub struct JoinedQueryBuilder<E: EntityWithView, JV: JoinedView> {
+    query: SelectFrom,
+    root_alias: Alias,
+    joined_alias: Alias,
+    alias_generator: AliasGenerator,
+    _marker: PhantomData<(E, JV)>,
+}
+
+pub trait JoinedView {
+    type Entity: EntityWithView;
+
+    fn join_type() -> JoinType;
+
+    fn joined(alias: &Alias) -> Self
+    where
+        Self: Sized;
+}
+
+// View of the parent in a left join, fields of which are null if there is no matched row
+pub struct OptionalView<E: EntityWithView> {
+    alias: Alias,
+    _entity: PhantomData<E>,
+}
+
+impl<V: EntityView> JoinedView for V {
+    type Entity = V::Entity;
+
+    fn join_type() -> JoinType {
+        JoinType::InnerJoin
+    }
+
+    fn joined(alias: &Alias) -> Self
+    where
+        Self: Sized,
+    {
+        V::pure(alias)
+    }
+}
+
+impl<E: EntityWithView> JoinedView for OptionalView<E> {
+    type Entity = E;
+
+    fn join_type() -> JoinType {
+        JoinType::LeftJoin
+    }
+
+    fn joined(alias: &Alias) -> Self
+    where
+        Self: Sized,
+    {
+        OptionalView {
+            alias: alias.clone(),
+            _entity: Default::default(),
+        }
+    }
+}
+
+impl<E: EntityWithView> OptionalView<E> {
+    pub fn field<M: FieldMarkerWithView + FieldMarker<Entity = E>>(
+        &self,
+        _m: M,
+    ) -> ExprViewBox<NullableOf<TypeOfMarker<M>>>
+    where
+        TypeOfMarker<M>: NullableValue,
+    {
+        NullableOf::<TypeOfMarker<M>>::view_from_exprs(
+            M::view(E::View::pure(&self.alias)).collect_expr(),
+        )
+    }
+}
+
+impl<E: EntityWithView, JV: JoinedView> JoinedQueryBuilder<E, JV>
+where
+    JV::Entity: WithPrimaryKey,
+{
+    pub fn create(
+        mut query: SelectFrom,
+        root_alias: Alias,
+        mut alias_generator: AliasGenerator,
+        foreign_key_columns: &[&str],
+    ) -> Self {
+        let joined_alias = alias_generator.generate_root_alias::<JV::Entity>();
+        let on = foreign_key_columns
+            .iter()
+            .zip(JV::Entity::primary_key_columns())
+            .map(|(foreign_key, primary_key)| {
+                Expr::Eq(
+                    Box::new(root_alias.create_ident_expr(foreign_key)),
+                    Box::new(joined_alias.create_ident_expr(primary_key)),
+                )
+            })
+            .reduce(|l, r| Expr::And(Box::new(l), Box::new(r)))
+            .unwrap();
+
+        query.add_joins(vec![Join {
+            ty: JV::join_type(),
+            table: AliasedTable {
+                table: JV::Entity::table_name().to_string(),
+                alias: joined_alias.clone(),
+            },
+            on,
+        }]);
+
+        JoinedQueryBuilder {
+            query,
+            root_alias,
+            joined_alias,
+            alias_generator,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<E: EntityWithView, JV: JoinedView> Filter2<E::View, JV> for JoinedQueryBuilder<E, JV> {
+    fn filter<F, R: Into<ExprViewBoxWithTag<bool, Tags>>, Tags: TagList>(mut self, f: F) -> Self
+    where
+        F: Fn(E::View, JV) -> R,
+        WindowViewTag: NotInList<Tags>,
+    {
+        let view = f(
+            E::View::pure(&self.root_alias),
+            JV::joined(&self.joined_alias),
+        )
+        .into();
+
+        view.collect_expr().into_iter().for_each(|e| {
+            self.query.and_where(e);
+        });
+
+        self
+    }
+}
+
+impl<E: EntityWithView, JV: JoinedView> Map2<E::View, JV> for JoinedQueryBuilder<E, JV> {
+    type ResultType = MultiRows;
+
+    fn map<
+        R: Value,
+        RTags: TagList,
+        RV: Into<ExprViewBoxWithTag<R, RTags>>,
+        F: Fn(E::View, JV) -> RV,
+    >(
+        self,
+        f: F,
+    ) -> MappedQueryBuilder<R, RTags, Self::ResultType> {
+        let result_view = f(
+            E::View::pure(&self.root_alias),
+            JV::joined(&self.joined_alias),
+        )
+        .into();
+
+        MappedQueryBuilder::create(
+            self.query.source(),
+            vec![],
+            result_view,
+            self.alias_generator,
+        )
+    }
+}
@@ -44,7 +44,9 @@ pub trait Map2<View1, View2> {
     ) -> MappedQueryBuilder<R, RTags, Self::ResultType>;
 }
 
-impl<R: Value, RTags: TagList, ResultType: ExecuteResultType> MappedQueryBuilder<R, RTags, ResultType> {
+impl<R: Value, RTags: TagList, ResultType: ExecuteResultType>
+    MappedQueryBuilder<R, RTags, ResultType>
+{
     pub fn create(
         query: SelectSource,
         order_by_items: Vec<OrderByItem>,
@@ -68,6 +70,17 @@ impl<R: Value, RTags: TagList, ResultType: ExecuteResultType> MappedQueryBuilder
 
         self
     }
+
+    pub(crate) fn into_select_query(self) -> SelectQuery {
+        SelectQuery::create(
+            self.query,
+            self.alias_generator
+                .generate_select_list(self.view.collect_expr(), true),
+            self.order_by_items,
+            self.limit,
+            self.offset,
+        )
+    }
 }
 
 impl<R: Value, RTags: TagList> MappedQueryBuilder<R, RTags, MultiRows> {
@@ -105,14 +118,7 @@ where
     type Query = SelectQuery;
 
     fn generate_query(self) -> Self::Query {
-        SelectQuery::create(
-            self.query,
-            self.alias_generator
-                .generate_select_list(self.view.collect_expr(), true),
-            self.order_by_items,
-            self.limit,
-            self.offset,
-        )
+        self.into_select_query()
     }
 }
 
@@ -155,7 +161,9 @@ impl<T: Value<L = U1>, TTags: TagList> ExprView<T> for MappedQueryBuilder<T, TTa
     }
 }
 
-impl<T: Value<L = U1>, TTags: TagList> SubqueryIntoView<T> for MappedQueryBuilder<T, TTags, SingleRow> {
+impl<T: Value<L = U1>, TTags: TagList> SubqueryIntoView<T>
+    for MappedQueryBuilder<T, TTags, SingleRow>
+{
     fn as_expr(&self) -> ExprViewBox<T> {
         T::view_from_exprs(arr![Expr; Expr::Subquery(self.subquery())])
     }
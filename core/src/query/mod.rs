@@ -1,23 +1,32 @@
 pub use alias::*;
 pub use assoc::*;
+pub use compound::*;
+pub use cte::*;
 pub use delete::*;
 pub use exec::*;
 pub use filter::*;
 pub use fold::*;
 pub use group::*;
 pub use insert::*;
+pub use join::*;
 pub use map::*;
 pub use sort::*;
+pub use transaction::*;
 pub use update::*;
 
 mod alias;
 mod assoc;
+mod compound;
+mod cte;
 mod delete;
 mod exec;
 mod filter;
 mod fold;
 mod group;
 mod insert;
+mod join;
 mod map;
+mod schema;
 mod sort;
+mod transaction;
 mod update;
@@ -0,0 +1,29 @@
+use sqlx::Database;
+
+use query_builder::{CreateTableQuery, DropTableQuery, YukinoQuery};
+
+use crate::query::{Executable, MultiRows};
+
+impl<DB: Database> Executable<(), DB> for CreateTableQuery
+where
+    Self: YukinoQuery<DB>,
+{
+    type ResultType = MultiRows;
+    type Query = Self;
+
+    fn generate_query(self) -> Self::Query {
+        self
+    }
+}
+
+impl<DB: Database> Executable<(), DB> for DropTableQuery
+where
+    Self: YukinoQuery<DB>,
+{
+    type ResultType = MultiRows;
+    type Query = Self;
+
+    fn generate_query(self) -> Self::Query {
+        self
+    }
+}
@@ -0,0 +1,31 @@
+use futures::future::BoxFuture;
+use sqlx::{Acquire, Error, Transaction};
+
+// Run `callback` in a transaction, commit it if the callback returns `Ok` and roll it back
+// otherwise. `acquire` can be a pool, a connection or a transaction, a transaction begun on
+// another transaction is a SAVEPOINT.
+pub fn transaction<'a, 'c, A, R, E, F>(acquire: A, callback: F) -> BoxFuture<'a, Result<R, E>>
+where
+    A: Acquire<'c> + Send + 'a,
+    F: for<'t> FnOnce(&'t mut Transaction<'c, A::Database>) -> BoxFuture<'t, Result<R, E>>
+        + Send
+        + 'a,
+    R: Send + 'a,
+    E: From<Error> + Send + 'a,
+    'c: 'a,
+{
+    Box::pin(async move {
+        let mut tx = acquire.begin().await?;
+
+        match callback(&mut tx).await {
+            Ok(result) => {
+                tx.commit().await?;
+                Ok(result)
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                Err(e)
+            }
+        }
+    })
+}
@@ -0,0 +1,159 @@
+use sqlx::Database;
+
+use query_builder::RowOf;
+
+use crate::view::ConvertResult;
+
+// Reading values from rows of `DB`. Values which the driver can not decode directly are read as
+// the type they are stored as and converted, e.g. unsigned integers of Postgres.
+pub trait DecodeValue<'r, DB: Database>: Sized {
+    fn decode_value(row: &'r RowOf<DB>, index: &'static str) -> ConvertResult<Self>;
+}
+
+#[cfg(any(feature = "pgsql", feature = "sqlite"))]
+fn checked_convert<S: std::fmt::Display + Copy, T: std::convert::TryFrom<S>>(
+    stored: S,
+) -> ConvertResult<T> {
+    T::try_from(stored).map_err(|_| {
+        sqlx::Error::Decode(
+            format!(
+                "Value {} is out of the range of `{}`",
+                stored,
+                std::any::type_name::<T>()
+            )
+            .into(),
+        )
+    })
+}
+
+macro_rules! impl_decode_value {
+    ($db: ty, [$($ty: ty),*]) => {
+        $(
+            impl<'r> DecodeValue<'r, $db> for $ty {
+                fn decode_value(row: &'r RowOf<$db>, index: &'static str) -> ConvertResult<Self> {
+                    row.try_get_unchecked(index)
+                }
+            }
+
+            impl<'r> DecodeValue<'r, $db> for Option<$ty> {
+                fn decode_value(row: &'r RowOf<$db>, index: &'static str) -> ConvertResult<Self> {
+                    row.try_get_unchecked(index)
+                }
+            }
+        )*
+    };
+    ($db: ty, $ty: ty, $stored: ty, $convert: expr) => {
+        impl<'r> DecodeValue<'r, $db> for $ty {
+            fn decode_value(row: &'r RowOf<$db>, index: &'static str) -> ConvertResult<Self> {
+                let stored: $stored = row.try_get_unchecked(index)?;
+
+                $convert(stored)
+            }
+        }
+
+        impl<'r> DecodeValue<'r, $db> for Option<$ty> {
+            fn decode_value(row: &'r RowOf<$db>, index: &'static str) -> ConvertResult<Self> {
+                let stored: Option<$stored> = row.try_get_unchecked(index)?;
+
+                stored.map($convert).transpose()
+            }
+        }
+    };
+}
+
+#[cfg(feature = "mysql")]
+mod mysql {
+    use sqlx::types::time::{Date, PrimitiveDateTime, Time};
+    use sqlx::types::Decimal;
+    use sqlx::{MySql, Row};
+
+    use query_builder::RowOf;
+
+    use crate::view::decode::DecodeValue;
+    use crate::view::ConvertResult;
+
+    impl_decode_value!(
+        MySql,
+        [
+            bool,
+            u16,
+            u32,
+            u64,
+            i16,
+            i32,
+            i64,
+            f32,
+            f64,
+            Decimal,
+            Date,
+            Time,
+            PrimitiveDateTime,
+            String,
+            Vec<u8>
+        ]
+    );
+}
+
+#[cfg(feature = "pgsql")]
+mod pgsql {
+    use sqlx::types::time::{Date, PrimitiveDateTime, Time};
+    use sqlx::types::Decimal;
+    use sqlx::{Postgres, Row};
+
+    use query_builder::RowOf;
+
+    use crate::view::decode::{checked_convert, DecodeValue};
+    use crate::view::ConvertResult;
+
+    impl_decode_value!(
+        Postgres,
+        [bool, i16, i32, i64, f32, f64, Decimal, Date, Time, PrimitiveDateTime, String, Vec<u8>]
+    );
+
+    // Unsigned values are stored as the next signed type, see `AppendToArgs` of Postgres
+    impl_decode_value!(Postgres, u16, i32, checked_convert);
+    impl_decode_value!(Postgres, u32, i64, checked_convert);
+    impl_decode_value!(Postgres, u64, Decimal, checked_convert);
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use std::str::FromStr;
+
+    use sqlx::{Error, Row, Sqlite};
+    use sqlx::types::Decimal;
+    use sqlx::types::time::{Date, PrimitiveDateTime, Time};
+
+    use query_builder::RowOf;
+
+    use crate::view::ConvertResult;
+    use crate::view::decode::{checked_convert, DecodeValue};
+
+    impl_decode_value!(
+        Sqlite,
+        [bool, u16, u32, i16, i32, i64, f32, f64, String, Vec<u8>]
+    );
+
+    // Decimal and time values are stored as text in the formats of `AppendToArgs` of SQLite
+    fn parse_decimal(stored: String) -> ConvertResult<Decimal> {
+        Decimal::from_str(&stored).map_err(|e| Error::Decode(Box::new(e)))
+    }
+
+    fn parse_date(stored: String) -> ConvertResult<Date> {
+        Date::parse(stored, "%F").map_err(|e| Error::Decode(Box::new(e)))
+    }
+
+    fn parse_time(stored: String) -> ConvertResult<Time> {
+        Time::parse(stored, "%T").map_err(|e| Error::Decode(Box::new(e)))
+    }
+
+    fn parse_datetime(stored: String) -> ConvertResult<PrimitiveDateTime> {
+        PrimitiveDateTime::parse(stored, "%F %T").map_err(|e| Error::Decode(Box::new(e)))
+    }
+
+    impl_decode_value!(Sqlite, u64, i64, checked_convert);
+    impl_decode_value!(Sqlite, Decimal, String, parse_decimal);
+    impl_decode_value!(Sqlite, Date, String, parse_date);
+    impl_decode_value!(Sqlite, Time, String, parse_time);
+    impl_decode_value!(Sqlite, PrimitiveDateTime, String, parse_datetime);
+}
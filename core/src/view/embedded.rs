@@ -0,0 +1,15 @@
+use generic_array::GenericArray;
+
+use interface::ColumnDefinition;
+use query_builder::{Alias, Expr};
+
+use crate::view::Value;
+
+// Value object stored in several columns of its owner, named `{prefix}_{field}`
+pub trait Embedded: Value {
+    fn columns(prefix: &str) -> Vec<ColumnDefinition>;
+
+    fn pure(alias: &Alias, prefix: &str) -> Self::ValueExprView;
+
+    fn embedded_view(exprs: GenericArray<Expr, Self::L>) -> Self::ValueExprView;
+}
@@ -50,7 +50,7 @@ where
     type PrimaryKeyType;
     type PrimaryKeyTags: TagList;
 
-    fn primary_key(&self) -> &ExprBoxOfViewWithPrimaryKey<Self>;
+    fn primary_key(&self) -> ExprBoxOfViewWithPrimaryKey<Self>;
 }
 
 pub trait AssociatedView<
@@ -66,7 +66,7 @@ pub trait AssociatedView<
 {
     type ForeignKeyType: Value + Ord;
     type ForeignKeyTags: TagList;
-    fn foreign_key(&self) -> &ExprBoxOfAssociatedView<Self, Parent, ForeignField>;
+    fn foreign_key(&self) -> ExprBoxOfAssociatedView<Self, Parent, ForeignField>;
 }
 
 pub type TypeOfMarker<M> = <M as FieldMarker>::FieldType;
@@ -94,7 +94,7 @@ pub trait Identifiable: WithPrimaryKey + EntityWithView {
 
 pub trait Deletable: Identifiable {
     fn delete(self) -> DeletionBuilder<Self> {
-        Self::get(self.primary_key().clone()).delete()
+        Self::get(self.primary_key()).delete()
     }
 }
 
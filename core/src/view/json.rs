@@ -0,0 +1,143 @@
+use std::ops::{Deref, DerefMut};
+
+use generic_array::{arr, GenericArray};
+use generic_array::typenum::U1;
+use serde_json::Value as JsonValue;
+use sqlx::{ColumnIndex, Database, Decode, Error, Row, Type};
+
+use interface::DatabaseType;
+use query_builder::{AppendToArgs, DatabaseValue, Expr, QueryOf, RowOf};
+
+use crate::view::{
+    AnyTagExprView, AnyTagsValue, ConvertResult, DBMapping, EmptyTagList, ExprViewBoxWithTag,
+    JsonObject, NullableValue, SingleExprView, TagList, Value,
+};
+use crate::view::index::ResultIndex;
+
+// JSON column of `T`, which is read and written as a `serde_json::Value` on all databases
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Json<T>(pub T);
+
+impl<T> Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Json<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Json<T> {
+    fn from(value: T) -> Self {
+        Json(value)
+    }
+}
+
+fn encode_json<T: JsonObject>(value: Json<T>) -> DatabaseValue {
+    serde_json::to_value(value.0)
+        .map(DatabaseValue::Json)
+        .unwrap_or_else(|e| DatabaseValue::Invalid(DatabaseType::Json, e.to_string()))
+}
+
+fn decode_json<T: JsonObject>(value: JsonValue) -> ConvertResult<Json<T>> {
+    serde_json::from_value(value)
+        .map(Json)
+        .map_err(|e| Error::Decode(Box::new(e)))
+}
+
+impl<T: JsonObject> Value for Json<T> {
+    type L = U1;
+    type ValueExprView = SingleExprView<Self, EmptyTagList>;
+
+    fn to_database_values(self) -> GenericArray<DatabaseValue, Self::L> {
+        arr![DatabaseValue; encode_json(self)]
+    }
+}
+
+impl<T: JsonObject> Value for Option<Json<T>> {
+    type L = U1;
+    type ValueExprView = SingleExprView<Self, EmptyTagList>;
+
+    fn to_database_values(self) -> GenericArray<DatabaseValue, Self::L> {
+        if let Some(nested) = self {
+            arr![DatabaseValue; encode_json(nested)]
+        } else {
+            arr![DatabaseValue; DatabaseValue::Null(DatabaseType::Json)]
+        }
+    }
+}
+
+impl<T: JsonObject> NullableValue for Json<T> {
+    type Nullable = Option<Json<T>>;
+}
+
+impl<T: JsonObject> NullableValue for Option<Json<T>> {
+    type Nullable = Self;
+}
+
+impl<'r, DB: Database, H: ResultIndex, T: JsonObject> DBMapping<'r, DB, H> for Json<T>
+where
+    JsonValue: Decode<'r, DB> + Type<DB>,
+    for<'n> &'n str: ColumnIndex<RowOf<DB>>,
+    DatabaseValue: for<'q> AppendToArgs<'q, DB>,
+{
+    fn from_result(values: &'r RowOf<DB>) -> ConvertResult<Self>
+    where
+        Self: Sized,
+    {
+        decode_json(values.try_get_unchecked(H::index())?)
+    }
+
+    fn bind_on_query(self, query: QueryOf<DB>) -> QueryOf<DB>
+    where
+        Self: Sized,
+    {
+        encode_json(self).bind_on(query)
+    }
+}
+
+impl<'r, DB: Database, H: ResultIndex, T: JsonObject> DBMapping<'r, DB, H> for Option<Json<T>>
+where
+    JsonValue: Decode<'r, DB> + Type<DB>,
+    for<'n> &'n str: ColumnIndex<RowOf<DB>>,
+    DatabaseValue: for<'q> AppendToArgs<'q, DB>,
+{
+    fn from_result(values: &'r RowOf<DB>) -> ConvertResult<Self>
+    where
+        Self: Sized,
+    {
+        let value: Option<JsonValue> = values.try_get_unchecked(H::index())?;
+
+        value.map(decode_json).transpose()
+    }
+
+    fn bind_on_query(self, query: QueryOf<DB>) -> QueryOf<DB>
+    where
+        Self: Sized,
+    {
+        self.to_database_values()
+            .into_iter()
+            .next()
+            .unwrap()
+            .bind_on(query)
+    }
+}
+
+impl<T: JsonObject> AnyTagsValue for Json<T> {
+    fn view_with_tags<Tags: TagList>(self) -> ExprViewBoxWithTag<Self, Tags> {
+        SingleExprView::<Self, Tags>::from_exprs_with_tags(arr![Expr; Expr::Lit(encode_json(self))])
+    }
+}
+
+impl<T: JsonObject> AnyTagsValue for Option<Json<T>> {
+    fn view_with_tags<Tags: TagList>(self) -> ExprViewBoxWithTag<Self, Tags> {
+        let value = self.to_database_values().into_iter().next().unwrap();
+
+        SingleExprView::<Self, Tags>::from_exprs_with_tags(arr![Expr; Expr::Lit(value)])
+    }
+}
@@ -1,6 +1,9 @@
 pub use aggregate::*;
+pub use decode::*;
+pub use embedded::*;
 pub use entity::*;
 pub use index::*;
+pub use json::*;
 pub use node::*;
 pub use subquery::*;
 pub use tag::*;
@@ -10,8 +13,11 @@ pub use value::*;
 pub use vertical::*;
 
 mod aggregate;
+mod decode;
+mod embedded;
 mod entity;
 mod index;
+mod json;
 mod node;
 mod subquery;
 mod tag;
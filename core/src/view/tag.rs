@@ -5,8 +5,12 @@ use crate::view::{EntityWithView, ExprView, Value};
 pub type U0 = Zero;
 pub type U1 = Suc<U0>;
 pub type U2 = Suc<U1>;
+pub type U3 = Suc<U2>;
 pub type Len<L> = <L as BitMap>::L;
-pub type EmptyTagList = BitMapSegment<BitMapSegment<BitMapSegment<Terminal, False>, False>, False>;
+pub type EmptyTagList = BitMapSegment<
+    BitMapSegment<BitMapSegment<BitMapSegment<Terminal, False>, False>, False>,
+    False,
+>;
 pub type AddTag<B, F> = <B as SetBit<<F as Tag>::Offset, True>>::Result;
 pub type RemoveTag<B, F> = <B as SetBit<<F as Tag>::Offset, False>>::Result;
 pub type TagList1<T1> = AddTag<EmptyTagList, T1>;
@@ -194,8 +198,10 @@ impl<L: TagList + MergeBitMap<Len<EmptyTagList>, R>, R: TagList> MergeList<R> fo
 
 impl<B: BitMap<L = Len<EmptyTagList>>> TagList for B {}
 
+// Bits are counted from the outermost segment, `$inner` is the length of the segments inside the
+// one holding the bit of the tag, which is merged by `$strategy`
 macro_rules! create_tag {
-    ($name: ident, $offset: ty, $strategy: ident) => {
+    ($name: ident, $offset: ty, $inner: ty, $strategy: ident) => {
         pub struct $name;
 
         impl Tag for $name {
@@ -203,35 +209,36 @@ macro_rules! create_tag {
         }
 
         impl<
-                L: BitMap<L = $offset> + MergeBitMap<$offset, R>,
-                R: BitMap<L = $offset>,
+                L: BitMap<L = $inner> + MergeBitMap<$inner, R>,
+                R: BitMap<L = $inner>,
                 LH: Bool + $strategy<RH>,
                 RH: Bool,
-            > MergeBitMap<Suc<$offset>, BitMapSegment<R, RH>> for BitMapSegment<L, LH>
+            > MergeBitMap<Suc<$inner>, BitMapSegment<R, RH>> for BitMapSegment<L, LH>
         {
-            type Output = BitMapSegment<
-                <L as MergeBitMap<$offset, R>>::Output,
-                <LH as $strategy<RH>>::Result,
-            >;
+            type Output =
+                BitMapSegment<<L as MergeBitMap<$inner, R>>::Output, <LH as $strategy<RH>>::Result>;
         }
     };
 }
 
-create_tag!(OrdViewTag, U0, And);
-create_tag!(EntityViewTag, U1, AlwaysFalse);
-create_tag!(AggregateViewTag, U2, Or);
+create_tag!(OrdViewTag, U0, U3, And);
+create_tag!(EntityViewTag, U1, U2, AlwaysFalse);
+create_tag!(AggregateViewTag, U2, U1, Or);
+create_tag!(WindowViewTag, U3, U0, Or);
 
 #[cfg(test)]
 mod test {
     use crate::view::{
         AggregateViewTag, AssertBit, ConcreteList, EmptyTagList, EntityViewTag, False, InList,
         NotInList, OffsetOfTag, OrdViewTag, Tag, TagList, TagList1, TagList2, TagList3,
+        WindowViewTag,
     };
 
     type A = TagList3<OrdViewTag, EntityViewTag, AggregateViewTag>;
     type B = TagList2<OrdViewTag, EntityViewTag>;
-    type R = TagList1<OrdViewTag>;
+    type R = TagList2<OrdViewTag, AggregateViewTag>;
     type C = ConcreteList<A, B>;
+    type W = ConcreteList<TagList1<WindowViewTag>, A>;
 
     fn assert_in_list<T: Tag + InList<List>, List: TagList>() {}
     fn assert_not_in_list<
@@ -246,5 +253,21 @@ mod test {
         assert_in_list::<EntityViewTag, A>();
         assert_not_in_list::<EntityViewTag, R>();
         assert_not_in_list::<EntityViewTag, EmptyTagList>();
+        assert_in_list::<WindowViewTag, W>();
+        assert_in_list::<AggregateViewTag, W>();
+        assert_not_in_list::<OrdViewTag, W>();
+        assert_not_in_list::<WindowViewTag, C>();
+    }
+
+    #[test]
+    fn test_merge_strategy() {
+        type Ord = TagList1<OrdViewTag>;
+        type Aggregate = TagList1<AggregateViewTag>;
+
+        // Ordering needs both sides to be ordered, aggregation is kept from either side
+        let _a: EmptyTagList = ConcreteList::<Ord, EmptyTagList>::default();
+        let _b: Ord = ConcreteList::<Ord, Ord>::default();
+        let _c: Aggregate = ConcreteList::<Aggregate, EmptyTagList>::default();
+        let _d: Aggregate = ConcreteList::<EmptyTagList, Aggregate>::default();
     }
 }
@@ -1,27 +1,29 @@
+use std::any::type_name;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
 use generic_array::{arr, ArrayLength, functional::FunctionalSequence, GenericArray};
 use generic_array::typenum::{U1, UInt, UTerm};
 use generic_array::typenum::bit::{B0, B1};
-use sqlx::{ColumnIndex, Database, Decode, Encode, Error, MySql, Row, Type};
-use sqlx::types::Decimal;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use sqlx::{ColumnIndex, Database, Decode, Encode, Error, Row, Type};
+use sqlx::types::{Decimal, Json};
 use sqlx::types::time::{Date, PrimitiveDateTime, Time};
 
 use interface::DatabaseType;
-use query_builder::{DatabaseValue, Expr, QueryOf, RowOf};
+use query_builder::{AppendToArgs, DatabaseValue, Expr, QueryOf, RowOf};
 
 use crate::view::{
-    AnyTagExprView, ExprView, ExprViewBox, ExprViewBoxWithTag, OrdViewTag, TagList, TagList1,
+    AnyTagExprView, DecodeValue, EmptyTagList, ExprView, ExprViewBox, ExprViewBoxWithTag,
+    OrdViewTag, TagList, TagList1,
 };
 use crate::view::index::ResultIndex;
 
 pub type ValueCountOf<T> = <T as Value>::L;
 
-pub trait ValueCount:
-    ArrayLength<Expr> + ArrayLength<DatabaseValue> + ArrayLength<String> + ArrayLength<MySql>
-{
-}
+pub trait ValueCount: ArrayLength<Expr> + ArrayLength<DatabaseValue> + ArrayLength<String> {}
 
 impl ValueCount for UTerm {}
 
@@ -50,6 +52,21 @@ pub trait Value: 'static + Clone + Debug + Send + Sync {
     fn to_database_values(self) -> GenericArray<DatabaseValue, Self::L>;
 }
 
+pub type NullableOf<T> = <T as NullableValue>::Nullable;
+
+// The type of values which may be null, e.g. fields of the parent in a left join
+pub trait NullableValue: Value {
+    type Nullable: Value<L = Self::L>;
+}
+
+// Values of a single column, the type of which is known without a value, e.g. as the target of `CAST`
+pub trait ScalarValue: Value<L = U1> {
+    // Result of casting values of `Self` to `R`, which is nullable if `Self` is
+    type CastOf<R: ScalarValue + NullableValue>: Value<L = U1>;
+
+    fn database_type() -> DatabaseType;
+}
+
 pub type ConvertResult<T> = Result<T, Error>;
 
 pub trait DBMapping<'r, DB: Database, H: ResultIndex>: Value {
@@ -116,6 +133,28 @@ impl<T: Value<L = U1>, Tags: TagList> AnyTagExprView<T> for SingleExprView<T, Ta
 
 macro_rules! impl_value {
     (@inner $ty: ty, $enum: ident) => {
+        // Values are bound as database values, which are converted by the driver as in queries
+        impl<'r, DB: Database, H: ResultIndex> DBMapping<'r, DB, H> for $ty where
+            Self: DecodeValue<'r, DB>,
+            DatabaseValue: for<'q> AppendToArgs<'q, DB>
+        {
+            fn from_result(
+                values: &'r RowOf<DB>
+            ) -> ConvertResult<Self>
+                where Self: Sized
+            {
+                Self::decode_value(values, H::index())
+            }
+
+            fn bind_on_query(self, query: QueryOf<DB>) -> QueryOf<DB> where Self: Sized {
+                self.to_database_values().into_iter().next().unwrap().bind_on(query)
+            }
+        }
+
+        impl_value!(@any_tags $ty);
+    };
+
+    (@native $ty: ty) => {
         impl<'r, DB: Database, H: ResultIndex> DBMapping<'r, DB, H> for $ty where
             Self: Decode<'r, DB>,
             for<'n> &'n str: ColumnIndex<RowOf<DB>>,
@@ -134,6 +173,10 @@ macro_rules! impl_value {
             }
         }
 
+        impl_value!(@any_tags $ty);
+    };
+
+    (@any_tags $ty: ty) => {
         impl AnyTagsValue for $ty {
             fn view_with_tags<Tags: TagList>(self) -> ExprViewBoxWithTag<Self, Tags> {
                 Box::new(SingleExprView {
@@ -171,6 +214,30 @@ macro_rules! impl_value {
             }
         }
 
+        impl NullableValue for $ty {
+            type Nullable = Option<$ty>;
+        }
+
+        impl ScalarValue for $ty {
+            type CastOf<R: ScalarValue + NullableValue> = R;
+
+            fn database_type() -> DatabaseType {
+                DatabaseType::$enum
+            }
+        }
+
+        impl ScalarValue for Option<$ty> {
+            type CastOf<R: ScalarValue + NullableValue> = NullableOf<R>;
+
+            fn database_type() -> DatabaseType {
+                DatabaseType::$enum
+            }
+        }
+
+        impl NullableValue for Option<$ty> {
+            type Nullable = Self;
+        }
+
         impl_value!(@inner $ty, $enum);
         impl_value!(@inner Option<$ty>, $enum);
     };
@@ -190,3 +257,249 @@ impl_value!(Date, Date);
 impl_value!(Time, Time);
 impl_value!(PrimitiveDateTime, DateTime);
 impl_value!(String, String);
+impl_value!(Vec<u8>, Binary);
+
+// JSON values are not comparable, so they are not sortable
+impl Value for JsonValue {
+    type L = U1;
+    type ValueExprView = SingleExprView<Self, EmptyTagList>;
+
+    fn to_database_values(self) -> GenericArray<DatabaseValue, Self::L> {
+        arr![DatabaseValue; DatabaseValue::Json(self)]
+    }
+}
+
+impl Value for Option<JsonValue> {
+    type L = U1;
+    type ValueExprView = SingleExprView<Self, EmptyTagList>;
+
+    fn to_database_values(self) -> GenericArray<DatabaseValue, Self::L> {
+        if let Some(nested) = self {
+            arr![DatabaseValue; DatabaseValue::Json(nested)]
+        } else {
+            arr![DatabaseValue; DatabaseValue::Null(DatabaseType::Json)]
+        }
+    }
+}
+
+impl NullableValue for JsonValue {
+    type Nullable = Option<JsonValue>;
+}
+
+impl NullableValue for Option<JsonValue> {
+    type Nullable = Self;
+}
+
+impl_value!(@native JsonValue);
+impl_value!(@native Option<JsonValue>);
+
+pub trait JsonObject: 'static + Serialize + DeserializeOwned + Clone + Debug + Send + Sync {}
+
+impl<T: 'static + Serialize + DeserializeOwned + Clone + Debug + Send + Sync> JsonObject for T {}
+
+fn json_to_database_value<T: JsonObject>(value: Json<T>) -> DatabaseValue {
+    serde_json::to_value(value.0)
+        .map(DatabaseValue::Json)
+        .unwrap_or_else(|e| DatabaseValue::Invalid(DatabaseType::Json, e.to_string()))
+}
+
+impl<T: JsonObject> Value for Json<T> {
+    type L = U1;
+    type ValueExprView = SingleExprView<Self, EmptyTagList>;
+
+    fn to_database_values(self) -> GenericArray<DatabaseValue, Self::L> {
+        arr![DatabaseValue; json_to_database_value(self)]
+    }
+}
+
+impl<T: JsonObject> Value for Option<Json<T>> {
+    type L = U1;
+    type ValueExprView = SingleExprView<Self, EmptyTagList>;
+
+    fn to_database_values(self) -> GenericArray<DatabaseValue, Self::L> {
+        if let Some(nested) = self {
+            arr![DatabaseValue; json_to_database_value(nested)]
+        } else {
+            arr![DatabaseValue; DatabaseValue::Null(DatabaseType::Json)]
+        }
+    }
+}
+
+impl<T: JsonObject> NullableValue for Json<T> {
+    type Nullable = Option<Json<T>>;
+}
+
+impl<T: JsonObject> NullableValue for Option<Json<T>> {
+    type Nullable = Self;
+}
+
+macro_rules! impl_json_object_value {
+    ($ty: ty) => {
+        impl<'r, DB: Database, H: ResultIndex, T: JsonObject> DBMapping<'r, DB, H> for $ty
+        where
+            Self: Decode<'r, DB>,
+            for<'n> &'n str: ColumnIndex<RowOf<DB>>,
+            for<'q> Self: Encode<'q, DB> + Type<DB>,
+        {
+            fn from_result(values: &'r RowOf<DB>) -> ConvertResult<Self>
+            where
+                Self: Sized,
+            {
+                values.try_get_unchecked(H::index())
+            }
+
+            fn bind_on_query(self, query: QueryOf<DB>) -> QueryOf<DB>
+            where
+                Self: Sized,
+            {
+                query.bind(self)
+            }
+        }
+
+        impl<T: JsonObject> AnyTagsValue for $ty {
+            fn view_with_tags<Tags: TagList>(self) -> ExprViewBoxWithTag<Self, Tags> {
+                Box::new(SingleExprView {
+                    expr: Expr::Lit(self.to_database_values().into_iter().next().unwrap()),
+                    _ty: Default::default(),
+                })
+            }
+        }
+    };
+}
+
+impl_json_object_value!(Json<T>);
+impl_json_object_value!(Option<Json<T>>);
+
+// Values stored as another scalar value, e.g. enums and newtypes deriving `YukinoValue`
+pub trait CustomValue: 'static + Clone + Debug + Send + Sync {
+    type Inner: ScalarValue;
+    type Tags: TagList;
+
+    // `None` if the stored value does not map to any value of `Self`
+    fn from_inner(inner: Self::Inner) -> Option<Self>
+    where
+        Self: Sized;
+
+    fn into_inner(self) -> Self::Inner;
+}
+
+fn custom_value_from_inner<T: CustomValue>(inner: T::Inner) -> ConvertResult<T> {
+    T::from_inner(inner.clone()).ok_or_else(|| {
+        Error::Decode(format!("Unexpected value {:?} of `{}`", inner, type_name::<T>()).into())
+    })
+}
+
+impl<T: CustomValue> Value for T {
+    type L = U1;
+    type ValueExprView = SingleExprView<Self, T::Tags>;
+
+    fn to_database_values(self) -> GenericArray<DatabaseValue, Self::L> {
+        self.into_inner().to_database_values()
+    }
+}
+
+impl<T: CustomValue> Value for Option<T>
+where
+    Option<T::Inner>: ScalarValue,
+{
+    type L = U1;
+    type ValueExprView = SingleExprView<Self, T::Tags>;
+
+    fn to_database_values(self) -> GenericArray<DatabaseValue, Self::L> {
+        self.map(CustomValue::into_inner).to_database_values()
+    }
+}
+
+impl<T: CustomValue> NullableValue for T
+where
+    Option<T::Inner>: ScalarValue,
+{
+    type Nullable = Option<T>;
+}
+
+impl<T: CustomValue> NullableValue for Option<T>
+where
+    Option<T::Inner>: ScalarValue,
+{
+    type Nullable = Self;
+}
+
+impl<T: CustomValue> ScalarValue for T {
+    type CastOf<R: ScalarValue + NullableValue> = R;
+
+    fn database_type() -> DatabaseType {
+        T::Inner::database_type()
+    }
+}
+
+impl<T: CustomValue> ScalarValue for Option<T>
+where
+    Option<T::Inner>: ScalarValue,
+{
+    type CastOf<R: ScalarValue + NullableValue> = NullableOf<R>;
+
+    fn database_type() -> DatabaseType {
+        T::Inner::database_type()
+    }
+}
+
+impl<'r, DB: Database, H: ResultIndex, T: CustomValue> DBMapping<'r, DB, H> for T
+where
+    T::Inner: DBMapping<'r, DB, H>,
+{
+    fn from_result(values: &'r RowOf<DB>) -> ConvertResult<Self>
+    where
+        Self: Sized,
+    {
+        custom_value_from_inner(T::Inner::from_result(values)?)
+    }
+
+    fn bind_on_query(self, query: QueryOf<DB>) -> QueryOf<DB>
+    where
+        Self: Sized,
+    {
+        self.into_inner().bind_on_query(query)
+    }
+}
+
+impl<'r, DB: Database, H: ResultIndex, T: CustomValue> DBMapping<'r, DB, H> for Option<T>
+where
+    Option<T::Inner>: ScalarValue + DBMapping<'r, DB, H>,
+{
+    fn from_result(values: &'r RowOf<DB>) -> ConvertResult<Self>
+    where
+        Self: Sized,
+    {
+        Option::<T::Inner>::from_result(values)?
+            .map(custom_value_from_inner)
+            .transpose()
+    }
+
+    fn bind_on_query(self, query: QueryOf<DB>) -> QueryOf<DB>
+    where
+        Self: Sized,
+    {
+        self.map(CustomValue::into_inner).bind_on_query(query)
+    }
+}
+
+impl<T: CustomValue> AnyTagsValue for T {
+    fn view_with_tags<Tags: TagList>(self) -> ExprViewBoxWithTag<Self, Tags> {
+        Box::new(SingleExprView {
+            expr: Expr::Lit(self.to_database_values().into_iter().next().unwrap()),
+            _ty: Default::default(),
+        })
+    }
+}
+
+impl<T: CustomValue> AnyTagsValue for Option<T>
+where
+    Option<T::Inner>: ScalarValue,
+{
+    fn view_with_tags<Tags: TagList>(self) -> ExprViewBoxWithTag<Self, Tags> {
+        Box::new(SingleExprView {
+            expr: Expr::Lit(self.to_database_values().into_iter().next().unwrap()),
+            _ty: Default::default(),
+        })
+    }
+}
@@ -0,0 +1,155 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Error, Fields, ItemStruct, Result};
+
+use crate::fields::{parse_field_name, unwrap_option};
+
+pub struct EmbeddedResolver;
+
+impl EmbeddedResolver {
+    pub fn get_implements(ast: &ItemStruct) -> Result<TokenStream> {
+        let name = &ast.ident;
+        let view_name = format_ident!("{}View", name);
+        let fields = if let Fields::Named(named_fields) = &ast.fields {
+            named_fields.named.iter().collect::<Vec<_>>()
+        } else {
+            return Err(Error::new_spanned(ast, "Expected named fields"));
+        };
+        let value_count = format_ident!("U{}", fields.len());
+        let field_names: Vec<_> = fields.iter().map(|f| f.ident.clone().unwrap()).collect();
+        let field_tys: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+        let column_names = fields
+            .iter()
+            .map(|f| parse_field_name(f))
+            .collect::<Result<Vec<_>>>()?;
+        let optional = fields.iter().map(|f| unwrap_option(&f.ty).1);
+        let offsets: Vec<_> = (0..fields.len())
+            .map(|index| {
+                let type_num = format_ident!("U{}", index);
+                quote!(yukino::generic_array::typenum::#type_num)
+            })
+            .collect();
+
+        Ok(quote! {
+            #[derive(Clone)]
+            pub struct #view_name {
+                #(pub #field_names: yukino::view::ExprViewBox<#field_tys>),*
+            }
+
+            impl yukino::view::ExprView<#name> for #view_name {
+                type Tags = yukino::view::EmptyTagList;
+
+                fn from_exprs(
+                    exprs: yukino::generic_array::GenericArray<
+                        yukino::query_builder::Expr, yukino::view::ValueCountOf<#name>
+                    >
+                ) -> yukino::view::ExprViewBox<#name>
+                where
+                    Self: Sized {
+                    Box::new(<#name as yukino::view::Embedded>::embedded_view(exprs))
+                }
+
+                fn expr_clone(&self) -> yukino::view::ExprViewBoxWithTag<#name, Self::Tags>
+                where
+                    Self: Sized {
+                    Box::new(self.clone())
+                }
+
+                fn collect_expr(&self) -> yukino::generic_array::GenericArray<
+                    yukino::query_builder::Expr, yukino::view::ValueCountOf<#name>
+                > {
+                    yukino::generic_array::arr![yukino::query_builder::Expr;
+                        #(self.#field_names.collect_expr().into_iter().next().unwrap()),*
+                    ]
+                }
+            }
+
+            impl From<#view_name> for yukino::view::ExprViewBox<#name> {
+                fn from(view: #view_name) -> Self {
+                    Box::new(view)
+                }
+            }
+
+            impl yukino::view::Value for #name {
+                type L = yukino::generic_array::typenum::#value_count;
+                type ValueExprView = #view_name;
+
+                fn to_database_values(self) -> yukino::generic_array::GenericArray<
+                        yukino::query_builder::DatabaseValue,
+                        Self::L
+                > {
+                    yukino::generic_array::arr![yukino::query_builder::DatabaseValue;
+                        #(yukino::view::Value::to_database_values(self.#field_names).into_iter().next().unwrap()),*
+                    ]
+                }
+            }
+
+            impl<'r, DB: sqlx::Database, H: yukino::view::ResultIndex> yukino::view::DBMapping<'r, DB, H> for #name
+                where #(
+                    #offsets: std::ops::Add<H>,
+                    yukino::generic_array::typenum::Sum<#offsets, H>: yukino::view::ResultIndex,
+                    #field_tys: yukino::view::DBMapping<'r, DB, yukino::generic_array::typenum::Sum<#offsets, H>>
+                ),*
+            {
+                fn from_result(
+                    values: &'r yukino::query_builder::RowOf<DB>
+                ) -> yukino::view::ConvertResult<Self>
+                    where Self: Sized
+                {
+                    Ok(#name {
+                        #(#field_names: yukino::view::DBMapping::<
+                            'r,
+                            DB,
+                            yukino::generic_array::typenum::Sum<#offsets, H>
+                        >::from_result(values)?),*
+                    })
+                }
+
+                fn bind_on_query(
+                    self,
+                    query: yukino::query_builder::QueryOf<DB>
+                ) -> yukino::query_builder::QueryOf<DB> where Self: Sized {
+                    #(let query = yukino::view::DBMapping::<
+                        'r,
+                        DB,
+                        yukino::generic_array::typenum::Sum<#offsets, H>
+                    >::bind_on_query(self.#field_names, query);)*
+                    query
+                }
+            }
+
+            impl yukino::view::Embedded for #name {
+                fn columns(prefix: &str) -> Vec<yukino::ColumnDefinition> {
+                    vec![#(yukino::ColumnDefinition {
+                        name: format!("{}_{}", prefix, #column_names),
+                        ty: <#field_tys as yukino::view::ScalarValue>::database_type(),
+                        optional: #optional,
+                        auto_increment: false,
+                    }),*]
+                }
+
+                fn pure(alias: &yukino::query_builder::Alias, prefix: &str) -> Self::ValueExprView {
+                    #view_name {
+                        #(#field_names: <#field_tys as yukino::view::Value>::view_from_exprs(
+                            yukino::generic_array::arr![yukino::query_builder::Expr;
+                                alias.create_ident_expr(&format!("{}_{}", prefix, #column_names))
+                            ]
+                        )),*
+                    }
+                }
+
+                fn embedded_view(
+                    exprs: yukino::generic_array::GenericArray<yukino::query_builder::Expr, Self::L>
+                ) -> Self::ValueExprView {
+                    let mut exprs = exprs.into_iter();
+
+                    #view_name {
+                        #(#field_names: <#field_tys as yukino::view::Value>::view_from_exprs(
+                            yukino::generic_array::arr![yukino::query_builder::Expr; exprs.next().unwrap()]
+                        )),*
+                    }
+                }
+            }
+        })
+    }
+}
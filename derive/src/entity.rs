@@ -1,12 +1,19 @@
 use heck::SnakeCase;
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
-use syn::{Error, Fields, ItemStruct, Lit, Meta, NestedMeta};
+use syn::{
+    Attribute, Error, Expr, ExprLit, Fields, ItemStruct, Lit, Meta, NestedMeta, Path, Token,
+};
+use syn::punctuated::Punctuated;
 use syn::Result;
 
+use interface::IndexDefinition;
+
 use crate::fields::FieldResolver;
 use crate::impls::Implementor;
-use crate::resolved::{ResolvedAssociation, ResolvedEntity};
+use crate::resolved::{
+    RelationKind, ResolvedAssociation, ResolvedEntity, ResolvedField, ResolvedRelation,
+};
 
 pub struct EntityResolver {
     field_resolvers: Vec<Box<dyn FieldResolver>>,
@@ -51,7 +58,11 @@ impl EntityResolver {
                         .iter()
                         .find(|resolver| resolver.can_resolve(field))
                         .ok_or_else(|| {
-                            Error::new_spanned(field, "Cannot find a field resolver for this field")
+                            Error::new_spanned(
+                                field,
+                                "Cannot find a field resolver for this field, mark fields of \
+                                `CustomValue` types, e.g. deriving `YukinoValue`, with `#[value]`",
+                            )
                         })
                         .and_then(|resolver| resolver.resolve_field(field))
                 })
@@ -94,9 +105,10 @@ impl EntityResolver {
                                 })
                                 .map(|field| ResolvedAssociation {
                                     ref_entity_path,
-                                    foreign_key,
-                                    column_name: field.definition.identity_column.clone(),
-                                    ty: field.ty.clone(),
+                                    foreign_keys: vec![foreign_key],
+                                    column_names: vec![field.definition.identity_column.clone()],
+                                    field_marker: field.field_marker.clone(),
+                                    composite: false,
                                 })
                         }
                         _ => Err(Error::new_spanned(
@@ -105,14 +117,52 @@ impl EntityResolver {
                         )),
                     })
             })
+            .chain(
+                ast.attrs
+                    .iter()
+                    .filter(|attr| attr.path.is_ident("belongs_to"))
+                    .map(|attr| resolve_composite_association(attr, &fields)),
+            )
             .collect::<Result<Vec<_>>>()?;
 
-        if fields.iter().filter(|f| f.definition.primary_key).count() > 1 {
-            return Err(Error::new_spanned(
-                ast,
-                "Only one field can be marked as primary key",
-            ));
-        }
+        let relations = ast
+            .attrs
+            .iter()
+            .filter(|attr| {
+                ["has_many", "has_one", "many_to_many"]
+                    .iter()
+                    .any(|name| attr.path.is_ident(name))
+            })
+            .map(|attr| resolve_relation(&ast.ident, attr))
+            .collect::<Result<Vec<_>>>()?;
+
+        let indexes = ast
+            .fields
+            .iter()
+            .zip(fields.iter())
+            .filter_map(|(f, resolved)| {
+                let unique = f.attrs.iter().any(|attr| attr.path.is_ident("unique"));
+                if unique || f.attrs.iter().any(|attr| attr.path.is_ident("index")) {
+                    Some(IndexDefinition {
+                        name: format!(
+                            "{}_{}_{}",
+                            table_name,
+                            resolved.definition.identity_column,
+                            if unique { "unique" } else { "index" }
+                        ),
+                        columns: resolved
+                            .definition
+                            .columns
+                            .iter()
+                            .map(|c| c.name.clone())
+                            .collect(),
+                        unique,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
 
         Ok(ResolvedEntity {
             table_name,
@@ -124,6 +174,8 @@ impl EntityResolver {
             entity_name,
             fields,
             associations,
+            relations,
+            indexes,
         })
     }
 
@@ -140,3 +192,185 @@ impl EntityResolver {
         })
     }
 }
+
+// `#[belongs_to(Parent, via = ("foreign_key_1", "foreign_key_2"))]` on the entity references a
+// composite primary key, the marker of these fields is named by `name` or the snake case name of
+// the parent.
+fn resolve_composite_association(
+    attr: &Attribute,
+    fields: &[ResolvedField],
+) -> Result<ResolvedAssociation> {
+    let arguments = attr.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+    let mut arguments = arguments.iter();
+    let ref_entity_path = match arguments.next() {
+        Some(Expr::Path(p)) => p.path.clone(),
+        _ => return Err(Error::new_spanned(attr, "Expected referenced entity path")),
+    };
+
+    let (mut via, mut name) = (None, None);
+    for argument in arguments {
+        let unexpected = || Err(Error::new_spanned(argument, "Unexpected argument"));
+        let assign = match argument {
+            Expr::Assign(assign) => assign,
+            _ => return unexpected(),
+        };
+
+        match (&*assign.left, &*assign.right) {
+            (Expr::Path(p), Expr::Tuple(t)) if p.path.is_ident("via") => {
+                via = Some(
+                    t.elems
+                        .iter()
+                        .map(|e| match e {
+                            Expr::Lit(ExprLit {
+                                lit: Lit::Str(s), ..
+                            }) => Ok(s.value()),
+                            _ => Err(Error::new_spanned(e, "Expected a str")),
+                        })
+                        .collect::<Result<Vec<_>>>()?,
+                )
+            }
+            (
+                Expr::Path(p),
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(s), ..
+                }),
+            ) if p.path.is_ident("name") => name = Some(s.value()),
+            _ => return unexpected(),
+        }
+    }
+
+    let foreign_keys = via
+        .ok_or_else(|| Error::new_spanned(attr, "Expected `via` argument"))?
+        .iter()
+        .map(|field_name| {
+            fields
+                .iter()
+                .find(|f| f.name == field_name)
+                .ok_or_else(|| Error::new_spanned(attr, "Cannot find a field with this name"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let name = name.unwrap_or_else(|| {
+        ref_entity_path
+            .segments
+            .last()
+            .unwrap()
+            .ident
+            .to_string()
+            .to_snake_case()
+    });
+
+    Ok(ResolvedAssociation {
+        ref_entity_path,
+        foreign_keys: foreign_keys.iter().map(|f| f.name.clone()).collect(),
+        column_names: foreign_keys
+            .iter()
+            .map(|f| f.definition.identity_column.clone())
+            .collect(),
+        field_marker: format_ident!("{}", name),
+        composite: true,
+    })
+}
+
+// `#[has_many(Child, via = "foreign_key")]` and `#[has_one(..)]` navigate to the children
+// referencing this entity. `#[many_to_many(Target, through = JoinEntity)]` navigates across the
+// join entity, foreign keys of which default to `{entity}_id` and can be specified by `via` and
+// `to`. The method name defaults to the snake case name of the referenced entity.
+fn resolve_relation(entity_name: &Ident, attr: &Attribute) -> Result<ResolvedRelation> {
+    let arguments = attr.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+    let mut arguments = arguments.iter();
+    let ref_entity_path = match arguments.next() {
+        Some(Expr::Path(p)) => p.path.clone(),
+        _ => return Err(Error::new_spanned(attr, "Expected referenced entity path")),
+    };
+
+    let (mut via, mut to, mut through, mut name) = (None, None, None, None);
+    for argument in arguments {
+        let (key, value) = match argument {
+            Expr::Assign(assign) => match &*assign.left {
+                Expr::Path(p) if p.path.get_ident().is_some() => {
+                    (p.path.get_ident().unwrap().to_string(), &*assign.right)
+                }
+                _ => return Err(Error::new_spanned(argument, "Unexpected relation argument")),
+            },
+            _ => return Err(Error::new_spanned(argument, "Unexpected relation argument")),
+        };
+
+        let string_value = || match value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(s), ..
+            }) => Ok(s.value()),
+            _ => Err(Error::new_spanned(value, "Expected a str")),
+        };
+
+        match key.as_str() {
+            "via" => via = Some(string_value()?),
+            "to" => to = Some(string_value()?),
+            "name" => name = Some(string_value()?),
+            "through" => match value {
+                Expr::Path(p) => through = Some(p.path.clone()),
+                _ => return Err(Error::new_spanned(value, "Expected an entity path")),
+            },
+            _ => return Err(Error::new_spanned(argument, "Unexpected relation argument")),
+        }
+    }
+
+    let ref_entity_name = ref_entity_path
+        .segments
+        .last()
+        .unwrap()
+        .ident
+        .to_string()
+        .to_snake_case();
+    let (kind, foreign_key_marker) = if attr.path.is_ident("many_to_many") {
+        let through =
+            through.ok_or_else(|| Error::new_spanned(attr, "Expected `through` argument"))?;
+        let via = via.unwrap_or_else(|| format!("{}_id", entity_name.to_string().to_snake_case()));
+        let to = to.unwrap_or_else(|| format!("{}_id", ref_entity_name));
+
+        (
+            RelationKind::ManyToMany {
+                target_marker: field_marker_path(&through, &to),
+                through: through.clone(),
+            },
+            field_marker_path(&through, &via),
+        )
+    } else {
+        if through.is_some() || to.is_some() {
+            return Err(Error::new_spanned(
+                attr,
+                "`through` and `to` are only available in `many_to_many`",
+            ));
+        }
+        let via = via.ok_or_else(|| Error::new_spanned(attr, "Expected `via` argument"))?;
+        let kind = if attr.path.is_ident("has_many") {
+            RelationKind::HasMany
+        } else {
+            RelationKind::HasOne
+        };
+
+        (kind, field_marker_path(&ref_entity_path, &via))
+    };
+
+    let method_name = match (name, &kind) {
+        (Some(name), _) => format_ident!("{}", name),
+        (None, RelationKind::HasOne) => format_ident!("{}", ref_entity_name),
+        (None, _) => format_ident!("{}s", ref_entity_name),
+    };
+
+    Ok(ResolvedRelation {
+        kind,
+        ref_entity_path,
+        foreign_key_marker,
+        method_name,
+    })
+}
+
+// Field markers of an entity are generated in the snake case module next to it
+fn field_marker_path(entity_path: &Path, field_name: &str) -> Path {
+    let mut path = entity_path.clone();
+    let last = path.segments.last_mut().unwrap();
+    last.ident = format_ident!("{}", last.ident.to_string().to_snake_case());
+    path.segments.push(format_ident!("{}", field_name).into());
+
+    path
+}
@@ -21,6 +21,7 @@ pub enum FieldType {
     Float,
     Double,
     String,
+    Binary,
 }
 
 pub struct BasicFieldResolver;
@@ -61,15 +62,17 @@ impl FieldResolver for BasicFieldResolver {
                 identity_column: column_name,
                 primary_key,
             },
+            column_ty: None,
             converter_value_count: 1,
             primary: primary_key,
+            embedded: false,
         })
     }
 }
 
 impl FieldType {
     pub fn from_ty(ty: &Type) -> Option<(Self, bool)> {
-        let branch: [(FieldType, Box<dyn Fn() -> TypeMatchResult>); 10] = [
+        let branch: [(FieldType, Box<dyn Fn() -> TypeMatchResult>); 11] = [
             (FieldType::Bool, Box::new(|| match_optional_ty::<bool>(ty))),
             (FieldType::Short, Box::new(|| match_optional_ty::<i16>(ty))),
             (
@@ -95,6 +98,13 @@ impl FieldType {
                     match_optional_ty_by_param(&str, ty)
                 }),
             ),
+            (
+                FieldType::Binary,
+                Box::new(|| {
+                    let binary = parse_quote!(Vec<u8>);
+                    match_optional_ty_by_param(&binary, ty)
+                }),
+            ),
         ];
         branch
             .iter()
@@ -119,6 +129,7 @@ impl FieldType {
             FieldType::Float => "f32",
             FieldType::Double => "f64",
             FieldType::String => "String",
+            FieldType::Binary => "Vec<u8>",
         })
         .unwrap();
 
@@ -195,6 +206,7 @@ impl FieldType {
             FieldType::Float => format_ident!("{}FloatConverter", prefix),
             FieldType::Double => format_ident!("{}DoubleConverter", prefix),
             FieldType::String => format_ident!("{}StringConverter", prefix),
+            FieldType::Binary => format_ident!("{}BinaryConverter", prefix),
         };
 
         quote! {
@@ -216,6 +228,7 @@ impl From<&FieldType> for DatabaseType {
             FieldType::Float => DatabaseType::Float,
             FieldType::Double => DatabaseType::Double,
             FieldType::String => DatabaseType::String,
+            FieldType::Binary => DatabaseType::Binary,
         }
     }
 }
@@ -166,9 +166,11 @@ impl FieldResolver for DateTimeFieldResolver {
             vertical_full_path: field_ty.vertical_view_path(optional),
             tag_list: field_ty.tags(optional),
             converter_ty: field_ty.converter_ty(optional),
+            column_ty: None,
             converter_value_count: 1,
             field_marker: format_ident!("{}", column_name),
             primary: false,
+            embedded: false,
         })
     }
 }
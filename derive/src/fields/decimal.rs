@@ -85,9 +85,11 @@ impl FieldResolver for DecimalFieldResolver {
                     yukino::converter::DecimalConverter
                 }
             },
+            column_ty: None,
             converter_value_count: 1,
             field_marker: format_ident!("{}", column_name),
             primary: false,
+            embedded: false,
         })
     }
 }
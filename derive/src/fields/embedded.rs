@@ -0,0 +1,61 @@
+use quote::{format_ident, quote, ToTokens};
+use syn::{Field, Result};
+
+use interface::FieldDefinition;
+
+use crate::fields::{FieldResolver, parse_field_name};
+use crate::resolved::ResolvedField;
+
+// Fields marked by `#[embedded]`, whose types implement `Embedded` by `#[derive(Embedded)]`
+pub struct EmbeddedFieldResolver;
+
+impl FieldResolver for EmbeddedFieldResolver {
+    fn can_resolve(&self, field: &Field) -> bool {
+        field
+            .attrs
+            .iter()
+            .any(|attr| attr.path.is_ident("embedded"))
+    }
+
+    fn resolve_field(&self, field: &Field) -> Result<ResolvedField> {
+        let ty = field.ty.to_token_stream();
+        let prefix = parse_field_name(field)?;
+
+        Ok(ResolvedField {
+            name: field.ident.clone().unwrap(),
+            definition: FieldDefinition {
+                name: field.ident.as_ref().unwrap().to_string(),
+                columns: vec![],
+                identity_column: prefix.clone(),
+                primary_key: false,
+            },
+            ty: ty.clone(),
+            column_ty: None,
+            view_construct: quote! {
+                <#ty as yukino::view::Embedded>::pure(alias, #prefix)
+            },
+            view_ty: quote! {
+                <#ty as yukino::view::Value>::ValueExprView
+            },
+            view_full_path: quote! {
+                <#ty as yukino::view::Value>::ValueExprView
+            },
+            vertical_ty: quote! {
+                yukino::view::VerticalExprView<#ty, yukino::view::TagsOfValueView<#ty>>
+            },
+            vertical_full_path: quote! {
+                yukino::view::VerticalExprView::<#ty, yukino::view::TagsOfValueView<#ty>>
+            },
+            tag_list: quote! {
+                yukino::view::TagsOfValueView<#ty>
+            },
+            converter_ty: quote! {
+                yukino::converter::EmbeddedConverter
+            },
+            converter_value_count: 0,
+            field_marker: format_ident!("{}", prefix),
+            primary: false,
+            embedded: true,
+        })
+    }
+}
@@ -0,0 +1,102 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{Field, parse_quote, PathArguments, Result, Type};
+
+use interface::{ColumnDefinition, DatabaseType, FieldDefinition};
+
+use crate::fields::{FieldResolver, match_ty, parse_field_name, unwrap_option};
+use crate::resolved::ResolvedField;
+
+// `serde_json::Value`, or `Json<T>` resolved by the last segment, e.g. `yukino::Json<T>` or
+// `sqlx::types::Json<T>` under any import path
+fn is_json_ty(ty: &Type) -> bool {
+    let json_value_ty: Type = parse_quote! {
+        serde_json::Value
+    };
+
+    match ty {
+        _ if match_ty(ty, &json_value_ty) => true,
+        Type::Path(path) => path.path.segments.last().is_some_and(|segment| {
+            segment.ident == "Json" && matches!(segment.arguments, PathArguments::AngleBracketed(_))
+        }),
+        _ => false,
+    }
+}
+
+fn match_json_ty(ty: &Type) -> Option<(TokenStream, bool)> {
+    let (nested, optional) = unwrap_option(ty);
+
+    if is_json_ty(nested) {
+        Some((ty.to_token_stream(), optional))
+    } else {
+        None
+    }
+}
+
+pub struct JsonFieldResolver;
+
+impl FieldResolver for JsonFieldResolver {
+    fn can_resolve(&self, field: &Field) -> bool {
+        match_json_ty(&field.ty).is_some()
+    }
+
+    fn resolve_field(&self, field: &Field) -> Result<ResolvedField> {
+        let (full_ty, optional) = match_json_ty(&field.ty).unwrap();
+        let column_name = parse_field_name(field)?;
+
+        Ok(ResolvedField {
+            name: field.ident.clone().unwrap(),
+            definition: FieldDefinition {
+                name: field.ident.as_ref().unwrap().to_string(),
+                columns: vec![ColumnDefinition {
+                    name: column_name.clone(),
+                    ty: DatabaseType::Json,
+                    optional,
+                    auto_increment: false,
+                }],
+                identity_column: column_name.clone(),
+                primary_key: false,
+            },
+            ty: full_ty.clone(),
+            column_ty: None,
+            view_construct: quote! {
+                {
+                    use yukino::view::AnyTagExprView;
+                    yukino::view::SingleExprView::from_exprs_with_tags(
+                        yukino::generic_array::arr![yukino::query_builder::Expr;
+                            alias.create_ident_expr(#column_name)
+                        ]
+                    )
+                }
+            },
+            view_ty: quote! {
+                yukino::view::ExprViewBox<#full_ty>
+            },
+            view_full_path: quote! {
+                yukino::view::SingleExprView::<#full_ty, yukino::view::TagsOfValueView<#full_ty>>
+            },
+            vertical_ty: quote! {
+                yukino::view::VerticalExprView<#full_ty, yukino::view::TagsOfValueView<#full_ty>>
+            },
+            vertical_full_path: quote! {
+                yukino::view::VerticalExprView::<#full_ty, yukino::view::TagsOfValueView<#full_ty>>
+            },
+            tag_list: quote! {
+                yukino::view::TagsOfValueView<#full_ty>
+            },
+            converter_ty: if optional {
+                quote! {
+                    yukino::converter::OptionalJsonConverter
+                }
+            } else {
+                quote! {
+                    yukino::converter::JsonConverter
+                }
+            },
+            converter_value_count: 1,
+            field_marker: format_ident!("{}", column_name),
+            primary: false,
+            embedded: false,
+        })
+    }
+}
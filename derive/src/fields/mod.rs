@@ -10,12 +10,18 @@ use syn::{
 pub use basic::*;
 pub use datetime::*;
 pub use decimal::*;
+pub use embedded::*;
+pub use json::*;
+pub use value::*;
 
 use crate::resolved::ResolvedField;
 
 mod basic;
 mod datetime;
 mod decimal;
+mod embedded;
+mod json;
+mod value;
 
 pub trait FieldResolver {
     fn can_resolve(&self, field: &Field) -> bool;
@@ -100,7 +106,23 @@ fn match_optional_ty<T>(input: &Type) -> TypeMatchResult {
     match_optional_ty_by_param(&target, input)
 }
 
-fn parse_field_name(field: &Field) -> Result<String> {
+pub fn unwrap_option(ty: &Type) -> (&Type, bool) {
+    if let Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            if path.path.segments.len() == 1 && segment.ident == "Option" {
+                if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(GenericArgument::Type(nested)) = args.args.first() {
+                        return (nested, true);
+                    }
+                }
+            }
+        }
+    }
+
+    (ty, false)
+}
+
+pub fn parse_field_name(field: &Field) -> Result<String> {
     field
         .attrs
         .iter()
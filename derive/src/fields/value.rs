@@ -0,0 +1,86 @@
+use quote::{format_ident, quote, ToTokens};
+use syn::{Error, Field, Result, Type};
+
+use interface::{ColumnDefinition, DatabaseType, FieldDefinition};
+
+use crate::fields::{FieldResolver, parse_field_name, unwrap_option};
+use crate::resolved::ResolvedField;
+
+// Fields marked by `#[value]`, the types of which implement `CustomValue`, usually by
+// `#[derive(YukinoValue)]`
+pub struct ValueFieldResolver;
+
+impl FieldResolver for ValueFieldResolver {
+    fn can_resolve(&self, field: &Field) -> bool {
+        field.attrs.iter().any(|attr| attr.path.is_ident("value"))
+    }
+
+    fn resolve_field(&self, field: &Field) -> Result<ResolvedField> {
+        let (nested, optional) = unwrap_option(&field.ty);
+        if !matches!(nested, Type::Path(_)) {
+            return Err(Error::new_spanned(
+                &field.ty,
+                "`#[value]` fields must be of a type implementing `CustomValue`",
+            ));
+        }
+        let full_ty = field.ty.to_token_stream();
+        let column_name = parse_field_name(field)?;
+
+        let auto_increment = field
+            .attrs
+            .iter()
+            .any(|attr| attr.path.is_ident("auto_increment"));
+        let primary_key = field.attrs.iter().any(|attr| attr.path.is_ident("id"));
+        Ok(ResolvedField {
+            name: field.ident.clone().unwrap(),
+            definition: FieldDefinition {
+                name: field.ident.as_ref().unwrap().to_string(),
+                columns: vec![ColumnDefinition {
+                    name: column_name.clone(),
+                    // replaced by `column_ty`, the actual type is only known after expansion
+                    ty: DatabaseType::String,
+                    optional,
+                    auto_increment,
+                }],
+                identity_column: column_name.clone(),
+                primary_key,
+            },
+            ty: full_ty.clone(),
+            column_ty: Some(quote! {
+                <#full_ty as yukino::view::ScalarValue>::database_type()
+            }),
+            view_construct: quote! {
+                {
+                    use yukino::view::AnyTagExprView;
+                    yukino::view::SingleExprView::from_exprs_with_tags(
+                        yukino::generic_array::arr![yukino::query_builder::Expr;
+                            alias.create_ident_expr(#column_name)
+                        ]
+                    )
+                }
+            },
+            view_ty: quote! {
+                yukino::view::ExprViewBox<#full_ty>
+            },
+            view_full_path: quote! {
+                yukino::view::SingleExprView::<#full_ty, yukino::view::TagsOfValueView<#full_ty>>
+            },
+            vertical_ty: quote! {
+                yukino::view::VerticalExprView<#full_ty, yukino::view::TagsOfValueView<#full_ty>>
+            },
+            vertical_full_path: quote! {
+                yukino::view::VerticalExprView::<#full_ty, yukino::view::TagsOfValueView<#full_ty>>
+            },
+            tag_list: quote! {
+                yukino::view::TagsOfValueView<#full_ty>
+            },
+            converter_ty: quote! {
+                yukino::converter::CustomValueConverter
+            },
+            converter_value_count: 1,
+            field_marker: format_ident!("{}", column_name),
+            primary: primary_key,
+            embedded: false,
+        })
+    }
+}
@@ -2,7 +2,7 @@ use proc_macro2::TokenStream;
 use quote::quote;
 
 use crate::impls::Implementor;
-use crate::resolved::ResolvedEntity;
+use crate::resolved::{ResolvedEntity, ResolvedKey};
 
 pub struct AssociationImplementor;
 
@@ -16,29 +16,39 @@ impl Implementor for AssociationImplementor {
             .iter()
             .map(|assoc| {
                 let target_entity_name = &assoc.ref_entity_path;
-                let ty = &assoc.ty;
-                let field_name = &assoc.foreign_key;
-                let column_name = &assoc.column_name;
-                let foreign_key_field = resolved.fields.iter().find(|f| f.name.eq(field_name)).unwrap();
-                let foreign_key_marker = &foreign_key_field.field_marker;
-                let tags = &foreign_key_field.tag_list;
+                let column_names = &assoc.column_names;
+                let foreign_key_marker = &assoc.field_marker;
+                let ResolvedKey {
+                    ty,
+                    tags,
+                    value,
+                    view,
+                    ..
+                } = ResolvedKey::from_fields(
+                    &resolved.fields_named(&assoc.foreign_keys),
+                    |name| quote!(self.#name.clone()),
+                );
                 quote! {
                     impl yukino::Association<#target_entity_name, #marker_mod::#foreign_key_marker> for #entity_name {
                         type ForeignKeyType = #ty;
-                        fn foreign_key(&self) -> &Self::ForeignKeyType {
-                            &self.#field_name
+                        fn foreign_key(&self) -> Self::ForeignKeyType {
+                            #value
                         }
 
-                        fn foreign_key_name() -> &'static str where Self: Sized {
-                            #column_name
+                        fn foreign_key_columns() -> &'static [&'static str] where Self: Sized {
+                            &[#(#column_names),*]
                         }
                     }
 
+                    impl yukino::ForeignKeyMarker for #marker_mod::#foreign_key_marker {
+                        type Parent = #target_entity_name;
+                    }
+
                     impl yukino::view::AssociatedView<#target_entity_name, #marker_mod::#foreign_key_marker> for #view_name {
                         type ForeignKeyType = #ty;
                         type ForeignKeyTags = #tags;
-                        fn foreign_key(&self) -> &yukino::view::ExprBoxOfAssociatedView<Self, #target_entity_name, #marker_mod::#foreign_key_marker> {
-                            &self.#field_name
+                        fn foreign_key(&self) -> yukino::view::ExprBoxOfAssociatedView<Self, #target_entity_name, #marker_mod::#foreign_key_marker> {
+                            #view
                         }
                     }
                 }
@@ -1,8 +1,12 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
+use interface::IndexDefinition;
+
 use crate::impls::Implementor;
-use crate::resolved::ResolvedEntity;
+use crate::resolved::{
+    ResolvedAssociation, ResolvedEntity, ResolvedField, ResolvedKey, ValueOffset,
+};
 
 pub struct EntityImplementor;
 
@@ -13,21 +17,16 @@ impl Implementor for EntityImplementor {
         let view_name = &resolved.view_name;
         let vertical_view_name = &resolved.vertical_name;
         let table_name = &resolved.table_name;
-        let value_count = {
-            let type_num = format_ident!(
-                "U{}",
-                resolved
-                    .fields
-                    .iter()
-                    .fold(0, |c, i| c + i.converter_value_count)
-            );
-
-            quote! {
-                yukino::generic_array::typenum::#type_num
-            }
-        };
+        let value_count = resolved
+            .fields
+            .iter()
+            .fold(ValueOffset::default(), |mut offset, field| {
+                offset.add(field);
+                offset
+            })
+            .ty();
         let (
-            count,
+            _,
             columns,
             deserialize_branches,
             serialize_tmp,
@@ -37,7 +36,7 @@ impl Implementor for EntityImplementor {
         ) =
             resolved.fields.iter().fold(
                 (
-                        0,
+                        ValueOffset::default(),
                         vec![],
                         vec![],
                         vec![],
@@ -52,26 +51,8 @@ impl Implementor for EntityImplementor {
                 |(mut c_count, mut c_columns, mut de, mut ser_tmp, ser, mut w_branches, binding), field| {
                     let field_name = &field.name;
                     let field_ty = &field.ty;
-                    let (s, c): (usize, Vec<_>) = field.definition.columns.iter().fold(
-                        (0, vec![]),
-                        |(mut c_s, mut c_c), c| {
-                            let column_name = &c.name;
-                            c_s += 1;
-
-                            c_c.push(quote! {
-                                #column_name.to_string()
-                            });
-
-                            (c_s, c_c)
-                        },
-                    );
-                    c_columns.extend(c);
-                    let offset = {
-                        let type_num = format_ident!("U{}", c_count);
-                        quote! {
-                            yukino::generic_array::typenum::#type_num
-                        }
-                    };
+                    c_columns.push(field.column_names());
+                    let offset = c_count.ty();
 
                     de.push(quote! {
                         #field_name: yukino::view::DBMapping::<
@@ -94,7 +75,7 @@ impl Implementor for EntityImplementor {
                     );
 
 
-                    c_count += s;
+                    c_count.add(field);
                     (
                         c_count,
                         c_columns,
@@ -111,11 +92,63 @@ impl Implementor for EntityImplementor {
                 },
             );
 
+        // Offsets behind embedded fields can not be used in where clauses, since they are not
+        // normalized there. Fields are mapped as nested tuples instead.
+        let (where_branches, from_result_body, binding_body) = if resolved
+            .fields
+            .iter()
+            .any(|f| f.embedded)
+        {
+            let fields: Vec<_> = resolved.fields.iter().collect();
+            let field_names: Vec<_> = fields.iter().map(|f| &f.name).collect();
+            let ResolvedKey { ty, pattern, .. } =
+                ResolvedKey::from_fields(&fields, |field_name| quote!(#field_name));
+
+            (
+                vec![quote!(#ty: yukino::view::DBMapping<'r, DB, H>)],
+                quote! {
+                    let #pattern = <#ty as yukino::view::DBMapping<'r, DB, H>>::from_result(values)?;
+                    Ok(#name {
+                        #(#field_names),*
+                    })
+                },
+                quote! {
+                    let #name { #(#field_names),* } = self;
+                    <#ty as yukino::view::DBMapping<'r, DB, H>>::bind_on_query(#pattern, query)
+                },
+            )
+        } else {
+            (
+                where_branches,
+                quote! {
+                    Ok(#name {
+                        #(#deserialize_branches),*
+                    })
+                },
+                binding_body,
+            )
+        };
+        let field_definitions = resolved.fields.iter().map(quote_field_definition);
+        let association_definitions = resolved
+            .associations
+            .iter()
+            .map(quote_association_definition);
+        let index_definitions = resolved.indexes.iter().map(quote_index_definition);
+
         vec![quote! {
             impl yukino::YukinoEntity for #name {
                 fn table_name() -> &'static str {
                     #table_name
                 }
+
+                fn definition() -> yukino::EntityDefinition {
+                    yukino::EntityDefinition {
+                        table_name: #table_name.to_string(),
+                        associations: vec![#(#association_definitions),*],
+                        fields: vec![#(#field_definitions),*],
+                        indexes: vec![#(#index_definitions),*],
+                    }
+                }
             }
 
             impl yukino::view::EntityWithView for #name {
@@ -149,9 +182,7 @@ impl Implementor for EntityImplementor {
                 ) -> yukino::view::ConvertResult<Self>
                     where Self: Sized
                 {
-                    Ok(#name {
-                        #(#deserialize_branches),*
-                    })
+                    #from_result_body
                 }
 
                 fn bind_on_query(
@@ -165,26 +196,28 @@ impl Implementor for EntityImplementor {
             impl<DB: sqlx::Database> yukino::view::Insertable<DB> for #name
                 where Self: for<'r> yukino::view::DBMapping::<'r, DB, yukino::generic_array::typenum::U0> {
                 type Entity = Self;
-                type Source = Vec<Self>;
+                type Source = yukino::query_builder::SingleSource<Self>;
 
                 fn insert(self) -> yukino::query_builder::InsertQuery<DB, Self::Source> {
                     use yukino::view::Value;
                     yukino::query_builder::Insert::into(
                         #table_name.to_string(),
                         <Self as yukino::view::Insertable<DB>>::columns(),
-                        vec![self]
+                        yukino::query_builder::SingleSource(self)
                     )
                 }
 
                 fn columns() -> Vec<String> where Self: Sized {
-                    vec![#(#columns),*]
+                    let columns: Vec<Vec<String>> = vec![#(#columns),*];
+                    columns.concat()
                 }
             }
 
             impl<'q, DB: sqlx::Database> yukino::query_builder::ArgSource<'q, DB> for #name
                 where Self: for<'r> yukino::view::DBMapping::<'r, DB, yukino::generic_array::typenum::U0> {
-                fn insert_value_count() -> usize {
-                    #count
+                fn insert_values(&self) -> Vec<yukino::query_builder::DatabaseValue> {
+                    use yukino::view::Value;
+                    self.clone().to_database_values().into_iter().collect()
                 }
 
                 fn bind_args(
@@ -197,3 +230,84 @@ impl Implementor for EntityImplementor {
         }]
     }
 }
+
+fn quote_field_definition(field: &ResolvedField) -> TokenStream {
+    let definition = &field.definition;
+    let name = &definition.name;
+    let identity_column = &definition.identity_column;
+    let primary_key = definition.primary_key;
+    if field.embedded {
+        let ty = &field.ty;
+        return quote! {
+            yukino::FieldDefinition {
+                name: #name.to_string(),
+                columns: <#ty as yukino::view::Embedded>::columns(#identity_column),
+                identity_column: #identity_column.to_string(),
+                primary_key: #primary_key,
+            }
+        };
+    }
+
+    let columns = definition.columns.iter().map(|c| {
+        let column_name = &c.name;
+        let ty = field.column_ty.clone().unwrap_or_else(|| {
+            let ty = format_ident!("{}", c.ty.to_string());
+            quote!(yukino::DatabaseType::#ty)
+        });
+        let optional = c.optional;
+        let auto_increment = c.auto_increment;
+
+        quote! {
+            yukino::ColumnDefinition {
+                name: #column_name.to_string(),
+                ty: #ty,
+                optional: #optional,
+                auto_increment: #auto_increment,
+            }
+        }
+    });
+
+    quote! {
+        yukino::FieldDefinition {
+            name: #name.to_string(),
+            columns: vec![#(#columns),*],
+            identity_column: #identity_column.to_string(),
+            primary_key: #primary_key,
+        }
+    }
+}
+
+fn quote_association_definition(association: &ResolvedAssociation) -> TokenStream {
+    let ref_entity_path = &association.ref_entity_path;
+    let ref_entity_name = quote!(#ref_entity_path).to_string();
+    let field_name = association.field_marker.to_string();
+    let column_names = &association.column_names;
+
+    quote! {
+        yukino::AssociationDefinition {
+            referenced_entity_name: #ref_entity_name.to_string(),
+            referenced_table_name: <#ref_entity_path as yukino::YukinoEntity>::table_name()
+                .to_string(),
+            referenced_column_names: <#ref_entity_path as yukino::WithPrimaryKey>::primary_key_columns()
+                .iter()
+                .map(|c| c.to_string())
+                .collect(),
+            field_name: #field_name.to_string(),
+            column_names: vec![#(#column_names.to_string()),*],
+        }
+    }
+}
+
+fn quote_index_definition(definition: &IndexDefinition) -> TokenStream {
+    let name = &definition.name;
+    let columns = &definition.columns;
+    let unique = definition.unique;
+
+    quote! {
+        yukino::IndexDefinition {
+            name: #name.to_string(),
+            columns: vec![#(#columns.to_string()),*],
+            unique: #unique,
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
+use quote::quote;
 
 use crate::impls::Implementor;
-use crate::resolved::ResolvedEntity;
+use crate::resolved::{ResolvedEntity, ValueOffset};
 
 pub struct InsertImplementor;
 
@@ -11,40 +11,23 @@ impl Implementor for InsertImplementor {
         let entity_name = &resolved.entity_name;
         let name = &resolved.new_entity_name;
         let table_name = &resolved.table_name;
-        let (count, fields, columns, where_clauses, binds) = resolved.fields.iter().fold(
-                (0, vec![], vec![], vec![], quote! {query}),
-                |(mut c_count, mut c_fields, mut c_columns, mut c_wheres, mut c_binds), field| {
+        let (_, fields, columns, values, where_clauses, binds) = resolved.fields.iter().fold(
+                (ValueOffset::default(), vec![], vec![], vec![], vec![], quote! {query}),
+                |(mut c_count, mut c_fields, mut c_columns, mut c_values, mut c_wheres, mut c_binds), field| {
                     let primary_field = field.primary;
 
                     if !primary_field {
                         let field_name = &field.name;
                         let ty = &field.ty;
-                        let (s, c): (usize, Vec<_>) = field
-                            .definition
-                            .columns
-                            .iter()
-                            .fold((0, vec![]), |(mut c_s, mut c_c), c| {
-                                let column_name = &c.name;
-                                c_s += 1;
-
-                                c_c.push(quote! {
-                                    #column_name.to_string()
-                                });
-
-                                (c_s, c_c)
-                            });
-
-                        let offset = {
-                            let type_num = format_ident!("U{}", c_count);
-                                quote! {
-                                yukino::generic_array::typenum::#type_num
-                            }
-                        };
+                        let offset = c_count.ty();
 
                         c_fields.push(quote! {pub #field_name: #ty});
 
-                        c_columns.extend(c);
-                        c_count += s;
+                        c_columns.push(field.column_names());
+                        c_values.push(quote! {
+                            self.#field_name.clone().to_database_values().into_iter().collect()
+                        });
+                        c_count.add(field);
                         c_wheres.push(quote! {
                             #ty: for<'r> yukino::view::DBMapping<'r, DB, #offset>
                         });
@@ -54,7 +37,7 @@ impl Implementor for InsertImplementor {
                         };
                     }
 
-                    (c_count, c_fields, c_columns, c_wheres, c_binds)
+                    (c_count, c_fields, c_columns, c_values, c_wheres, c_binds)
                 },
             );
 
@@ -67,26 +50,29 @@ impl Implementor for InsertImplementor {
             impl<DB: sqlx::Database> yukino::view::Insertable<DB> for #name
                 where Self: for<'q> yukino::query_builder::ArgSource<'q, DB>  {
                 type Entity = #entity_name;
-                type Source = Vec<Self>;
+                type Source = yukino::query_builder::SingleSource<Self>;
 
                 fn insert(self) -> yukino::query_builder::InsertQuery<DB, Self::Source>  where Self: Sized {
                     use yukino::view::Value;
                     yukino::query_builder::Insert::into(
                         #table_name.to_string(),
                         <Self as yukino::view::Insertable<DB>>::columns(),
-                        vec![self]
+                        yukino::query_builder::SingleSource(self)
                     )
                 }
 
                 fn columns() -> Vec<String> where Self: Sized {
-                    vec![#(#columns),*]
+                    let columns: Vec<Vec<String>> = vec![#(#columns),*];
+                    columns.concat()
                 }
             }
 
             impl<'q, DB: sqlx::Database> yukino::query_builder::ArgSource<'q, DB> for #name
                 where #(#where_clauses),* {
-                fn insert_value_count() -> usize {
-                    #count
+                fn insert_values(&self) -> Vec<yukino::query_builder::DatabaseValue> {
+                    use yukino::view::Value;
+                    let values: Vec<Vec<yukino::query_builder::DatabaseValue>> = vec![#(#values),*];
+                    values.concat()
                 }
 
                 fn bind_args(
@@ -2,7 +2,7 @@ use proc_macro2::TokenStream;
 use quote::quote;
 
 use crate::impls::Implementor;
-use crate::resolved::ResolvedEntity;
+use crate::resolved::{ResolvedEntity, ResolvedKey};
 
 pub struct FieldMarkerImplementor;
 
@@ -18,7 +18,21 @@ impl Implementor for FieldMarkerImplementor {
                 let ty = &f.ty;
                 let view_tags = &f.tag_list;
                 let fields_name = &f.name;
-                let columns: Vec<_> = f.definition.columns.iter().map(|d| &d.name).collect();
+                let (columns, view) = if f.embedded {
+                    let column_names = f.column_names();
+                    (
+                        quote! {
+                            yukino::generic_array::GenericArray::from_exact_iter(#column_names).unwrap()
+                        },
+                        quote!(Box::new(entity_view.#fields_name)),
+                    )
+                } else {
+                    let columns = f.definition.columns.iter().map(|d| &d.name);
+                    (
+                        quote!(yukino::generic_array::arr![String; #(#columns.to_string()),*]),
+                        quote!(entity_view.#fields_name),
+                    )
+                };
                 quote! {
                     pub struct #marker_name;
 
@@ -29,12 +43,12 @@ impl Implementor for FieldMarkerImplementor {
                             String,
                             <Self::FieldType as yukino::view::Value>::L
                         > where Self: Sized {
-                            yukino::generic_array::arr![String; #(#columns.to_string()),*]
+                            #columns
                         }
 
                         fn view(entity_view: <Self::Entity as yukino::view::EntityWithView>::View)
                             -> yukino::view::ExprViewBoxWithTag<Self::FieldType, Self::ViewTags> {
-                            entity_view.#fields_name
+                            #view
                         }
                     }
 
@@ -45,10 +59,52 @@ impl Implementor for FieldMarkerImplementor {
                 }
             })
             .collect();
+        let composite_markers = resolved
+            .associations
+            .iter()
+            .filter(|assoc| assoc.composite)
+            .map(|assoc| {
+                let marker_name = &assoc.field_marker;
+                let fields = resolved.fields_named(&assoc.foreign_keys);
+                let columns = fields
+                    .iter()
+                    .flat_map(|f| f.definition.columns.iter().map(|d| &d.name));
+                let ResolvedKey { ty, tags, view, .. } =
+                    ResolvedKey::from_fields(&fields, |name| quote!(entity_view.#name));
+                quote! {
+                    pub struct #marker_name;
+
+                    impl yukino::view::FieldMarkerWithView for #marker_name {
+                        type ViewTags = #tags;
+
+                        fn columns() -> yukino::generic_array::GenericArray<
+                            String,
+                            <Self::FieldType as yukino::view::Value>::L
+                        > where Self: Sized {
+                            yukino::generic_array::arr![String; #(#columns.to_string()),*]
+                        }
+
+                        fn view(entity_view: <Self::Entity as yukino::view::EntityWithView>::View)
+                            -> yukino::view::ExprViewBoxWithTag<Self::FieldType, Self::ViewTags> {
+                            #view
+                        }
+                    }
+
+                    impl yukino::FieldMarker for #marker_name {
+                        type Entity = super::#entity_name;
+                        type FieldType = #ty;
+                    }
+                }
+            });
 
         vec![quote! {
             pub mod #marker_mod {
+                // field types may refer to items in the scope of the entity
+                #[allow(unused_imports)]
+                use super::*;
+
                 #(#markers)*
+                #(#composite_markers)*
             }
         }]
     }
@@ -5,6 +5,7 @@ pub use entity::*;
 pub use insert::*;
 pub use marker::*;
 pub use primary::*;
+pub use relation::*;
 pub use view::*;
 
 use crate::resolved::ResolvedEntity;
@@ -14,6 +15,7 @@ mod entity;
 mod insert;
 mod marker;
 mod primary;
+mod relation;
 mod view;
 
 pub trait Implementor {
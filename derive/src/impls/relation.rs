@@ -0,0 +1,174 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::impls::Implementor;
+use crate::resolved::{RelationKind, ResolvedEntity, ResolvedRelation};
+
+pub struct RelationImplementor;
+
+struct RelationMethods {
+    view_method: TokenStream,
+    query_method: TokenStream,
+    load_signature: TokenStream,
+    load_body: TokenStream,
+}
+
+impl Implementor for RelationImplementor {
+    fn get_implements(&self, resolved: &ResolvedEntity) -> Vec<TokenStream> {
+        if resolved.relations.is_empty() {
+            return vec![];
+        }
+
+        let entity_name = &resolved.entity_name;
+        let view_name = &resolved.view_name;
+        let trait_name = format_ident!("{}Associations", entity_name);
+        let methods: Vec<_> = resolved
+            .relations
+            .iter()
+            .map(|relation| relation_methods(resolved, relation))
+            .collect();
+        let view_methods = methods.iter().map(|m| &m.view_method);
+        let query_methods = methods.iter().map(|m| &m.query_method);
+        let load_signatures = methods.iter().map(|m| &m.load_signature);
+        let load_methods = methods.iter().map(|m| {
+            let signature = &m.load_signature;
+            let body = &m.load_body;
+
+            quote! {
+                #signature {
+                    #body
+                }
+            }
+        });
+
+        vec![quote! {
+            impl #entity_name {
+                #(#query_methods)*
+            }
+
+            impl #view_name {
+                #(#view_methods)*
+            }
+
+            pub trait #trait_name: Sized {
+                #(#load_signatures;)*
+            }
+
+            impl #trait_name for Vec<#entity_name> {
+                #(#load_methods)*
+            }
+        }]
+    }
+}
+
+fn relation_methods(resolved: &ResolvedEntity, relation: &ResolvedRelation) -> RelationMethods {
+    let entity_name = &resolved.entity_name;
+    let target = &relation.ref_entity_path;
+    let marker = &relation.foreign_key_marker;
+    let method_name = &relation.method_name;
+    let query_method_name = format_ident!("{}_of", method_name);
+    let load_method_name = format_ident!("load_{}", method_name);
+
+    match &relation.kind {
+        RelationKind::ManyToMany {
+            through,
+            target_marker,
+        } => RelationMethods {
+            view_method: quote! {
+                pub fn #method_name(&self) -> yukino::query::FilteredQueryBuilder<#target> {
+                    <#through as yukino::query::BelongsToView<#entity_name>>::belonging_to_view::<
+                        #marker
+                    >(self).referenced::<#target_marker>()
+                }
+            },
+            query_method: quote! {
+                pub fn #query_method_name(
+                    query: yukino::query::FilteredQueryBuilder<Self>
+                ) -> yukino::query::FilteredQueryBuilder<#target> {
+                    <#through as yukino::query::BelongsToQuery<#entity_name>>::belonging_to_query::<
+                        #marker
+                    >(query).referenced::<#target_marker>()
+                }
+            },
+            load_signature: quote! {
+                fn #load_method_name<
+                    'c,
+                    DB: yukino::query_builder::HasDialect,
+                    A: 'c + sqlx::Acquire<'c, Database = DB> + Send
+                >(
+                    self,
+                    acquire: A
+                ) -> std::pin::Pin<Box<
+                    dyn std::future::Future<
+                        Output = Result<yukino::query::LoadedChildren<#entity_name, #target>, sqlx::Error>
+                    > + Send + 'c
+                >>
+                    where Self: yukino::query::LoadThrough<
+                        #target,
+                        #marker,
+                        #target_marker,
+                        DB,
+                        Parent = #entity_name
+                    >
+            },
+            load_body: quote! {
+                yukino::query::LoadThrough::<#target, #marker, #target_marker, DB>::load_through(
+                    self,
+                    acquire
+                )
+            },
+        },
+        kind => {
+            let (load_trait, load_fn, loaded) = match kind {
+                RelationKind::HasOne => (
+                    quote!(yukino::query::LoadChild),
+                    quote!(load_child),
+                    quote!(yukino::query::LoadedChild),
+                ),
+                _ => (
+                    quote!(yukino::query::LoadChildren),
+                    quote!(load_children),
+                    quote!(yukino::query::LoadedChildren),
+                ),
+            };
+
+            RelationMethods {
+                view_method: quote! {
+                    pub fn #method_name(&self) -> yukino::query::FilteredQueryBuilder<#target> {
+                        <#target as yukino::query::BelongsToView<#entity_name>>::belonging_to_view::<
+                            #marker
+                        >(self)
+                    }
+                },
+                query_method: quote! {
+                    pub fn #query_method_name(
+                        query: yukino::query::FilteredQueryBuilder<Self>
+                    ) -> yukino::query::FilteredQueryBuilder<#target> {
+                        <#target as yukino::query::BelongsToQuery<#entity_name>>::belonging_to_query::<
+                            #marker
+                        >(query)
+                    }
+                },
+                load_signature: quote! {
+                    fn #load_method_name<
+                        'c: 'e,
+                        'e,
+                        DB: yukino::query_builder::HasDialect,
+                        E: 'e + sqlx::Executor<'c, Database = DB>
+                    >(
+                        self,
+                        executor: E
+                    ) -> std::pin::Pin<Box<
+                        dyn std::future::Future<
+                            Output = Result<#loaded<#entity_name, #target>, sqlx::Error>
+                        > + Send + 'e
+                    >>
+                        where Self: #load_trait<#target, #marker, DB, Parent = #entity_name>
+                },
+                load_body: quote! {
+                    #load_trait::<#target, #marker, DB>::#load_fn(self, executor)
+                },
+            }
+        }
+    }
+}
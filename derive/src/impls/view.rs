@@ -1,5 +1,5 @@
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
+use quote::quote;
 
 use crate::impls::Implementor;
 use crate::resolved::ResolvedEntity;
@@ -49,12 +49,7 @@ impl Implementor for ViewImplementor {
             ),
              (index, f)| {
                 let field_name = &f.name;
-                let field_value_count = {
-                    let type_num = format_ident!("U{}", f.converter_value_count);
-                    quote! {
-                        yukino::generic_array::typenum::#type_num
-                    }
-                };
+                let field_value_count = f.value_count();
                 let view_path = &f.view_full_path;
                 let vertical_view_path = &f.vertical_full_path;
                 let view_ty = &f.view_ty;
@@ -81,8 +76,15 @@ impl Implementor for ViewImplementor {
                     });
                 }
 
-                expr_branch.push(quote! {
-                    #field_name: #view_path::from_exprs(#field_name)
+                expr_branch.push(if f.embedded {
+                    let ty = &f.ty;
+                    quote! {
+                        #field_name: <#ty as yukino::view::Embedded>::embedded_view(#field_name)
+                    }
+                } else {
+                    quote! {
+                        #field_name: #view_path::from_exprs(#field_name)
+                    }
                 });
 
                 clone.push(quote! {
@@ -97,8 +99,14 @@ impl Implementor for ViewImplementor {
                     pub #field_name: #vertical_view_ty
                 });
 
-                vertical_branches.push(quote! {
-                    #field_name: #vertical_view_path::create(self.#field_name, vec![])
+                vertical_branches.push(if f.embedded {
+                    quote! {
+                        #field_name: #vertical_view_path::create(Box::new(self.#field_name), vec![])
+                    }
+                } else {
+                    quote! {
+                        #field_name: #vertical_view_path::create(self.#field_name, vec![])
+                    }
                 });
 
                 (
@@ -2,28 +2,53 @@ use proc_macro::TokenStream;
 
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{Error, ExprTuple, parse_macro_input, parse_quote, TypeTuple};
+use syn::{DeriveInput, Error, ExprTuple, ItemStruct, parse_macro_input, parse_quote, TypeTuple};
 
+use crate::embedded::EmbeddedResolver;
 use crate::entity::EntityResolver;
-use crate::fields::{BasicFieldResolver, DateTimeFieldResolver, DecimalFieldResolver};
+use crate::fields::{
+    BasicFieldResolver, DateTimeFieldResolver, DecimalFieldResolver, EmbeddedFieldResolver,
+    JsonFieldResolver, ValueFieldResolver,
+};
 use crate::impls::{
     AssociationImplementor, EntityImplementor, FieldMarkerImplementor, InsertImplementor,
-    PrimaryImplementor, ViewImplementor,
+    PrimaryImplementor, RelationImplementor, ViewImplementor,
 };
+use crate::value::ValueResolver;
 
+mod embedded;
 mod entity;
 mod fields;
 mod impls;
 mod resolved;
+mod value;
 
-#[proc_macro_derive(Entity, attributes(name, belongs_to, auto_increment, id))]
+#[proc_macro_derive(
+    Entity,
+    attributes(
+        name,
+        belongs_to,
+        has_many,
+        has_one,
+        many_to_many,
+        auto_increment,
+        embedded,
+        id,
+        index,
+        unique,
+        value
+    )
+)]
 pub fn derive_entity(tokens: TokenStream) -> TokenStream {
     let item_struct = parse_macro_input!(tokens as syn::ItemStruct);
     let resolver = EntityResolver::create(
         vec![
+            Box::new(EmbeddedFieldResolver),
             Box::new(BasicFieldResolver),
             Box::new(DecimalFieldResolver),
             Box::new(DateTimeFieldResolver),
+            Box::new(JsonFieldResolver),
+            Box::new(ValueFieldResolver),
         ],
         vec![
             Box::new(EntityImplementor),
@@ -32,6 +57,7 @@ pub fn derive_entity(tokens: TokenStream) -> TokenStream {
             Box::new(AssociationImplementor),
             Box::new(FieldMarkerImplementor),
             Box::new(InsertImplementor),
+            Box::new(RelationImplementor),
         ],
     );
 
@@ -42,6 +68,24 @@ pub fn derive_entity(tokens: TokenStream) -> TokenStream {
     result.into()
 }
 
+#[proc_macro_derive(Embedded, attributes(name))]
+pub fn derive_embedded(tokens: TokenStream) -> TokenStream {
+    let item_struct = parse_macro_input!(tokens as ItemStruct);
+
+    EmbeddedResolver::get_implements(&item_struct)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(YukinoValue, attributes(store, name))]
+pub fn derive_value(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as DeriveInput);
+
+    ValueResolver::get_implements(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 #[proc_macro]
 pub fn tuple(tokens: TokenStream) -> TokenStream {
     let tokens2: TokenStream2 = tokens.into();
@@ -1,7 +1,8 @@
 use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
 use syn::Path;
 
-use interface::FieldDefinition;
+use interface::{FieldDefinition, IndexDefinition};
 
 pub struct ResolvedEntity {
     pub table_name: String,
@@ -12,20 +13,39 @@ pub struct ResolvedEntity {
     pub converter_name: Ident,
     pub fields: Vec<ResolvedField>,
     pub associations: Vec<ResolvedAssociation>,
+    pub relations: Vec<ResolvedRelation>,
+    pub indexes: Vec<IndexDefinition>,
     pub marker_mod: Ident,
 }
 
 pub struct ResolvedAssociation {
     pub ref_entity_path: Path,
-    pub foreign_key: Ident,
-    pub column_name: String,
-    pub ty: TokenStream,
+    pub foreign_keys: Vec<Ident>,
+    pub column_names: Vec<String>,
+    pub field_marker: Ident,
+    // Marker of a multi-column foreign key is generated besides the markers of fields
+    pub composite: bool,
+}
+
+pub enum RelationKind {
+    HasMany,
+    HasOne,
+    ManyToMany { through: Path, target_marker: Path },
+}
+
+pub struct ResolvedRelation {
+    pub kind: RelationKind,
+    pub ref_entity_path: Path,
+    pub foreign_key_marker: Path,
+    pub method_name: Ident,
 }
 
 pub struct ResolvedField {
     pub name: Ident,
     pub definition: FieldDefinition,
     pub ty: TokenStream,
+    // Overrides the database type of the column when it is only known after expansion
+    pub column_ty: Option<TokenStream>,
     pub view_construct: TokenStream,
     pub view_ty: TokenStream,
     pub view_full_path: TokenStream,
@@ -36,4 +56,127 @@ pub struct ResolvedField {
     pub converter_value_count: usize,
     pub field_marker: Ident,
     pub primary: bool,
+    // Columns of embedded fields are only known after expansion, `definition.columns` is empty
+    pub embedded: bool,
+}
+
+// Offset of a field in the values of its entity
+#[derive(Default)]
+pub struct ValueOffset {
+    fixed: usize,
+    embedded: Vec<TokenStream>,
+}
+
+// Key composed by fields, nested into tuples in the same way as `tuple!`
+pub struct ResolvedKey {
+    pub ty: TokenStream,
+    pub tags: TokenStream,
+    pub value: TokenStream,
+    pub view: TokenStream,
+    pub pattern: TokenStream,
+}
+
+impl ResolvedEntity {
+    pub fn primary_key_fields(&self) -> Vec<&ResolvedField> {
+        self.fields
+            .iter()
+            .filter(|f| f.definition.primary_key)
+            .collect()
+    }
+
+    pub fn fields_named(&self, names: &[Ident]) -> Vec<&ResolvedField> {
+        names
+            .iter()
+            .map(|name| self.fields.iter().find(|f| f.name == *name).unwrap())
+            .collect()
+    }
+}
+
+impl ResolvedField {
+    pub fn value_count(&self) -> TokenStream {
+        if self.embedded {
+            let ty = &self.ty;
+            quote!(<#ty as yukino::view::Value>::L)
+        } else {
+            let type_num = format_ident!("U{}", self.converter_value_count);
+            quote!(yukino::generic_array::typenum::#type_num)
+        }
+    }
+
+    // Expression of `Vec<String>`
+    pub fn column_names(&self) -> TokenStream {
+        if self.embedded {
+            let ty = &self.ty;
+            let prefix = &self.definition.identity_column;
+            quote! {
+                <#ty as yukino::view::Embedded>::columns(#prefix)
+                    .into_iter()
+                    .map(|c| c.name)
+                    .collect::<Vec<_>>()
+            }
+        } else {
+            let columns = self.definition.columns.iter().map(|c| &c.name);
+            quote!(vec![#(#columns.to_string()),*])
+        }
+    }
+}
+
+impl ValueOffset {
+    pub fn add(&mut self, field: &ResolvedField) {
+        if field.embedded {
+            self.embedded.push(field.ty.clone());
+        } else {
+            self.fixed += field.converter_value_count;
+        }
+    }
+
+    pub fn ty(&self) -> TokenStream {
+        let type_num = format_ident!("U{}", self.fixed);
+
+        self.embedded.iter().fold(
+            quote!(yukino::generic_array::typenum::#type_num),
+            |offset, ty| {
+                quote! {
+                    yukino::generic_array::typenum::Sum<#offset, <#ty as yukino::view::Value>::L>
+                }
+            },
+        )
+    }
+}
+
+impl ResolvedKey {
+    pub fn from_fields(fields: &[&ResolvedField], view: impl Fn(&Ident) -> TokenStream) -> Self {
+        let mut iter = fields.iter().map(|f| {
+            let name = &f.name;
+
+            ResolvedKey {
+                ty: f.ty.clone(),
+                tags: f.tag_list.clone(),
+                value: quote!(self.#name.clone()),
+                view: view(name),
+                pattern: quote!(#name),
+            }
+        });
+        let first = iter.next().unwrap();
+
+        iter.fold(first, |l, r| {
+            let (l_ty, r_ty) = (&l.ty, &r.ty);
+            let (l_tags, r_tags) = (&l.tags, &r.tags);
+            let (l_value, r_value) = (&l.value, &r.value);
+            let (l_view, r_view) = (&l.view, &r.view);
+            let (l_pattern, r_pattern) = (&l.pattern, &r.pattern);
+            let ty = quote!((#l_ty, #r_ty));
+            let tags = quote!(yukino::view::ConcreteList<#l_tags, #r_tags>);
+
+            ResolvedKey {
+                view: quote! {
+                    yukino::view::ExprViewBoxWithTag::<#ty, #tags>::from((#l_view, #r_view))
+                },
+                value: quote!((#l_value, #r_value)),
+                pattern: quote!((#l_pattern, #r_pattern)),
+                ty,
+                tags,
+            }
+        })
+    }
 }
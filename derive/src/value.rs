@@ -0,0 +1,143 @@
+use heck::SnakeCase;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Attribute, Data, DataEnum, DeriveInput, Error, Fields, Lit, Meta, Result};
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum EnumStore {
+    String,
+    Integer,
+}
+
+pub struct ValueResolver;
+
+impl ValueResolver {
+    pub fn get_implements(input: &DeriveInput) -> Result<TokenStream> {
+        let name = &input.ident;
+        let (inner, tags, from_inner, into_inner) = match &input.data {
+            Data::Enum(data) => Self::resolve_enum(input, data)?,
+            Data::Struct(data) => match &data.fields {
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    let inner = fields.unnamed.first().unwrap().ty.to_token_stream();
+
+                    (
+                        inner.clone(),
+                        quote!(yukino::view::TagsOfValueView<#inner>),
+                        quote!(Some(#name(inner))),
+                        quote!(self.0),
+                    )
+                }
+                _ => return Err(Error::new_spanned(
+                    input,
+                    "YukinoValue can only be derived for newtypes with exactly one unnamed field",
+                )),
+            },
+            Data::Union(_) => {
+                return Err(Error::new_spanned(
+                    input,
+                    "YukinoValue can not be derived for unions",
+                ))
+            }
+        };
+
+        Ok(quote! {
+            impl yukino::view::CustomValue for #name {
+                type Inner = #inner;
+                type Tags = #tags;
+
+                fn from_inner(inner: Self::Inner) -> Option<Self> where Self: Sized {
+                    #from_inner
+                }
+
+                fn into_inner(self) -> Self::Inner {
+                    #into_inner
+                }
+            }
+        })
+    }
+
+    fn resolve_enum(
+        input: &DeriveInput,
+        data: &DataEnum,
+    ) -> Result<(TokenStream, TokenStream, TokenStream, TokenStream)> {
+        let name = &input.ident;
+        if let Some(variant) = data.variants.iter().find(|v| !v.fields.is_empty()) {
+            return Err(Error::new_spanned(
+                variant,
+                "YukinoValue can only be derived for enums with unit variants",
+            ));
+        }
+        let variants: Vec<_> = data.variants.iter().map(|v| &v.ident).collect();
+
+        Ok(match Self::parse_store(&input.attrs)? {
+            EnumStore::String => {
+                let values = data
+                    .variants
+                    .iter()
+                    .map(|v| {
+                        Ok(Self::parse_str_attr(&v.attrs, "name")?
+                            .unwrap_or_else(|| v.ident.to_string().to_snake_case()))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                (
+                    quote!(String),
+                    quote!(yukino::view::EmptyTagList),
+                    quote! {
+                        match inner.as_str() {
+                            #(#values => Some(#name::#variants),)*
+                            _ => None
+                        }
+                    },
+                    quote! {
+                        match self {
+                            #(#name::#variants => #values.to_string()),*
+                        }
+                    },
+                )
+            }
+            EnumStore::Integer => (
+                quote!(i32),
+                quote!(yukino::view::TagsOfValueView<i32>),
+                quote! {
+                    match inner {
+                        #(x if x == #name::#variants as i32 => Some(#name::#variants),)*
+                        _ => None
+                    }
+                },
+                quote!(self as i32),
+            ),
+        })
+    }
+
+    fn parse_store(attrs: &[Attribute]) -> Result<EnumStore> {
+        match Self::parse_str_attr(attrs, "store")?.as_deref() {
+            None | Some("string") => Ok(EnumStore::String),
+            Some("integer") => Ok(EnumStore::Integer),
+            Some(_) => Err(Error::new_spanned(
+                attrs.iter().find(|attr| attr.path.is_ident("store")),
+                "`store` attribute must be \"string\" or \"integer\"",
+            )),
+        }
+    }
+
+    fn parse_str_attr(attrs: &[Attribute], attr_name: &str) -> Result<Option<String>> {
+        attrs
+            .iter()
+            .find(|attr| attr.path.is_ident(attr_name))
+            .map(|attr| match attr.parse_meta()? {
+                Meta::NameValue(v) => match v.lit {
+                    Lit::Str(s) => Ok(s.value()),
+                    _ => Err(Error::new_spanned(
+                        v,
+                        format!("`{}` attribute must be a str", attr_name),
+                    )),
+                },
+                _ => Err(Error::new_spanned(
+                    attr,
+                    format!("`{}` attribute must be a named value", attr_name),
+                )),
+            })
+            .transpose()
+    }
+}
@@ -1,19 +1,30 @@
 use crate::DatabaseType;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EntityDefinition {
     pub table_name: String,
     pub associations: Vec<AssociationDefinition>,
     pub fields: Vec<FieldDefinition>,
+    pub indexes: Vec<IndexDefinition>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AssociationDefinition {
     pub referenced_entity_name: String,
+    pub referenced_table_name: String,
+    pub referenced_column_names: Vec<String>,
     pub field_name: String,
+    pub column_names: Vec<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IndexDefinition {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub unique: bool,
+}
+
+#[derive(Debug, Clone)]
 pub struct FieldDefinition {
     pub name: String,
     pub columns: Vec<ColumnDefinition>,
@@ -21,10 +32,23 @@ pub struct FieldDefinition {
     pub primary_key: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ColumnDefinition {
     pub name: String,
     pub ty: DatabaseType,
     pub optional: bool,
     pub auto_increment: bool,
 }
+
+impl EntityDefinition {
+    pub fn columns(&self) -> impl Iterator<Item = &ColumnDefinition> {
+        self.fields.iter().flat_map(|f| f.columns.iter())
+    }
+
+    pub fn primary_key_columns(&self) -> impl Iterator<Item = &ColumnDefinition> {
+        self.fields
+            .iter()
+            .filter(|f| f.primary_key)
+            .flat_map(|f| f.columns.iter())
+    }
+}
@@ -1,7 +1,11 @@
 use std::hash::Hash;
 
+use crate::EntityDefinition;
+
 pub trait YukinoEntity: 'static {
     fn table_name() -> &'static str;
+
+    fn definition() -> EntityDefinition;
 }
 
 pub type TypeOfForeignField<A, P, F> = <A as Association<P, F>>::ForeignKeyType;
@@ -12,20 +16,28 @@ pub trait Association<
 >: YukinoEntity
 {
     type ForeignKeyType: 'static + Clone + Ord + Hash;
-    fn foreign_key(&self) -> &Self::ForeignKeyType;
+    fn foreign_key(&self) -> Self::ForeignKeyType;
 
-    fn foreign_key_name() -> &'static str
+    fn foreign_key_columns() -> &'static [&'static str]
     where
         Self: Sized;
 }
 
+pub type ParentOf<M> = <M as ForeignKeyMarker>::Parent;
+
+// Implemented for the marker of each `belongs_to` field, so the parent can be inferred from it
+pub trait ForeignKeyMarker: FieldMarker {
+    type Parent: YukinoEntity + WithPrimaryKey<PrimaryKeyType = Self::FieldType>;
+}
+
 pub type PrimaryKeyTypeOf<E> = <E as WithPrimaryKey>::PrimaryKeyType;
 
+// Composite primary keys are nested tuples of the `#[id]` fields, in declaration order
 pub trait WithPrimaryKey: YukinoEntity {
     type PrimaryKeyType: 'static + Clone + Hash + Ord;
-    fn primary_key(&self) -> &Self::PrimaryKeyType;
+    fn primary_key(&self) -> Self::PrimaryKeyType;
 
-    fn primary_key_name() -> &'static str
+    fn primary_key_columns() -> &'static [&'static str]
     where
         Self: Sized;
 }
@@ -0,0 +1,329 @@
+use std::fmt::{Result, Write};
+
+use sqlx::Database;
+
+use interface::{ColumnDefinition, DatabaseType};
+
+use crate::{
+    AggregateFunction, DatabaseValue, DateTimeFunctionCall, DateTimeUnit, Expr, GroupConcatFunctionCall,
+    NormalAggregateFunctionCall, OnConflict, PlaceHolder, QueryBuildState, ScalarFunction,
+    ScalarFunctionCall, SubqueryFunction, SubqueryFunctionCall, ToSql, WindowFunction,
+    WindowFunctionCall,
+};
+
+pub trait Dialect: Send + Sync {
+    fn quote_ident(&self, name: &str) -> String;
+
+    fn placeholder(&self, index: usize) -> PlaceHolder;
+
+    // Whether placeholders carry their index, so that one can be written again for the same argument
+    fn numbered_placeholders(&self) -> bool {
+        false
+    }
+
+    fn column_type(&self, ty: &DatabaseType) -> &'static str;
+
+    fn auto_increment(&self) -> Option<&'static str>;
+
+    // Target type of `CAST`, which is not always a valid column type
+    fn cast_type(&self, ty: &DatabaseType) -> &'static str {
+        self.column_type(ty)
+    }
+
+    // Type of an introspected column, `None` if it is not produced by `column_type`
+    fn parse_column_type(&self, ty: &str) -> Option<DatabaseType>;
+
+    // Rows of (table, column, type, nullable) with nullable being `YES` or `NO`
+    fn column_info_query(&self) -> &'static str;
+
+    // Rows of (table, index, column, unique) ordered by the position in the index, indexes
+    // backing primary keys and constraints are excluded
+    fn index_info_query(&self) -> &'static str;
+
+    fn supports_modify_column(&self) -> bool {
+        true
+    }
+
+    fn write_modify_column(
+        &self,
+        column: &ColumnDefinition,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        write!(state, "MODIFY COLUMN")?;
+        column.to_sql(state)
+    }
+
+    fn write_drop_index(&self, _table: &str, name: &str, state: &mut QueryBuildState) -> Result {
+        write!(state, "DROP INDEX")?;
+        state.write_str(&self.quote_ident(name))
+    }
+
+    // Rejects bound values the database cannot store, the query fails to render then
+    fn check_value(&self, _value: &DatabaseValue) -> Result {
+        Ok(())
+    }
+
+    fn bool_literal(&self, value: bool) -> &'static str {
+        if value {
+            "TRUE"
+        } else {
+            "FALSE"
+        }
+    }
+
+    fn write_limit_offset(
+        &self,
+        limit: Option<usize>,
+        offset: usize,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        if let Some(limit) = limit {
+            write!(state, "LIMIT {}", limit)?;
+        }
+
+        if offset != 0 {
+            write!(state, "OFFSET {}", offset)?;
+        }
+
+        Ok(())
+    }
+
+    fn supports_returning(&self) -> bool {
+        true
+    }
+
+    fn write_on_conflict(
+        &self,
+        _columns: &[String],
+        on_conflict: &OnConflict,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        write!(state, "ON CONFLICT")?;
+        if !on_conflict.target().is_empty() {
+            write!(state, "(")?;
+            state.join_by(
+                on_conflict.target(),
+                |s, c| s.write_str(&self.quote_ident(c)),
+                |s| write!(s, ","),
+            )?;
+            write!(state, ")")?;
+        }
+
+        match on_conflict {
+            OnConflict::DoNothing { .. } => write!(state, "DO NOTHING"),
+            OnConflict::DoUpdate { assignments, .. } => {
+                write!(state, "DO UPDATE SET")?;
+                state.join(assignments, |s| write!(s, ","))
+            }
+        }
+    }
+
+    fn aggregate_fn_name(&self, function: &AggregateFunction) -> &'static str {
+        match function {
+            AggregateFunction::Average => "AVG",
+            AggregateFunction::Sum => "SUM",
+            AggregateFunction::BitAnd => "BIT_AND",
+            AggregateFunction::BitOr => "BIT_OR",
+            AggregateFunction::BitXor => "BIT_XOR",
+            AggregateFunction::Count | AggregateFunction::CountDistinct => "COUNT",
+            AggregateFunction::Max => "MAX",
+            AggregateFunction::Min => "MIN",
+            AggregateFunction::GroupConcat => "GROUP_CONCAT",
+        }
+    }
+
+    fn convert_normal_aggregate_fn_call(
+        &self,
+        fn_call: &NormalAggregateFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        state.write_str(self.aggregate_fn_name(&fn_call.function))?;
+        write!(state, "(")?;
+        if let AggregateFunction::CountDistinct = fn_call.function {
+            write!(state, "DISTINCT")?;
+        }
+
+        fn_call.param.to_sql(state)?;
+        write!(state, ")")
+    }
+
+    fn convert_group_concat(
+        &self,
+        fn_call: &GroupConcatFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result;
+
+    fn convert_subquery_fn(
+        &self,
+        fn_call: &SubqueryFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        match &fn_call.function {
+            SubqueryFunction::Any => write!(state, "ANY"),
+            SubqueryFunction::All => write!(state, "ALL"),
+        }?;
+        write!(state, "(")?;
+        fn_call.subquery.to_sql(state)?;
+        write!(state, ")")
+    }
+
+    fn scalar_fn_name(&self, function: &ScalarFunction) -> &'static str {
+        match function {
+            ScalarFunction::JsonExtract => "JSON_EXTRACT",
+            ScalarFunction::Length => "LENGTH",
+            ScalarFunction::Lower => "LOWER",
+            ScalarFunction::Upper => "UPPER",
+            ScalarFunction::Trim => "TRIM",
+            ScalarFunction::CharLength => "CHAR_LENGTH",
+            ScalarFunction::Concat => "CONCAT",
+            ScalarFunction::Substring => "SUBSTRING",
+            ScalarFunction::Coalesce => "COALESCE",
+            ScalarFunction::Abs => "ABS",
+            ScalarFunction::Round => "ROUND",
+            ScalarFunction::Floor => "FLOOR",
+            ScalarFunction::Ceil => "CEIL",
+            ScalarFunction::Power => "POWER",
+            ScalarFunction::Sqrt => "SQRT",
+            ScalarFunction::Greatest => "GREATEST",
+            ScalarFunction::Least => "LEAST",
+        }
+    }
+
+    fn convert_scalar_fn_call(
+        &self,
+        fn_call: &ScalarFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        match fn_call.function {
+            // `CONCAT` of Postgres skips nulls, `||` results in null as in other databases
+            ScalarFunction::Concat => write_concat(&fn_call.params, state),
+            function => write_fn_call(self.scalar_fn_name(&function), &fn_call.params, state),
+        }
+    }
+
+    // Results of `Truncate` and `AddInterval` are cast to `ty` by the caller, so they do not have to
+    // be of the same type as the operand
+    fn convert_datetime_fn_call(
+        &self,
+        fn_call: &DateTimeFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result;
+
+    fn window_fn_name(&self, function: &WindowFunction) -> &'static str {
+        match function {
+            WindowFunction::RowNumber => "ROW_NUMBER",
+            WindowFunction::Rank => "RANK",
+            WindowFunction::DenseRank => "DENSE_RANK",
+            WindowFunction::Lag => "LAG",
+            WindowFunction::Lead => "LEAD",
+            WindowFunction::FirstValue => "FIRST_VALUE",
+            WindowFunction::Aggregate(function) => self.aggregate_fn_name(function),
+        }
+    }
+
+    fn convert_window_fn_call(
+        &self,
+        fn_call: &WindowFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        write_window_fn_call(self.window_fn_name(&fn_call.function), fn_call, state)
+    }
+
+    // Escape character of `LIKE` patterns as a string literal
+    fn like_escape(&self) -> &'static str {
+        "'\\'"
+    }
+
+    fn convert_comparison(
+        &self,
+        l: &Expr,
+        operator: &str,
+        r: &Expr,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        write_comparison(l, operator, r, state)
+    }
+}
+
+pub trait HasDialect: Database {
+    fn dialect() -> &'static dyn Dialect;
+
+    // Only required by databases not supporting `RETURNING`
+    fn last_insert_id(_result: &<Self as Database>::QueryResult) -> Option<u64> {
+        None
+    }
+}
+
+pub(crate) fn write_comparison(
+    l: &Expr,
+    operator: &str,
+    r: &Expr,
+    state: &mut QueryBuildState,
+) -> Result {
+    write!(state, "(")?;
+    l.to_sql(state)?;
+    state.write_str(operator)?;
+    r.to_sql(state)?;
+    write!(state, ")")
+}
+
+pub(crate) fn write_concat(params: &[Expr], state: &mut QueryBuildState) -> Result {
+    write!(state, "(")?;
+    state.join(params, |s| write!(s, "||"))?;
+    write!(state, ")")
+}
+
+pub(crate) fn datetime_unit_name(unit: &DateTimeUnit) -> &'static str {
+    match unit {
+        DateTimeUnit::Year => "YEAR",
+        DateTimeUnit::Month => "MONTH",
+        DateTimeUnit::Day => "DAY",
+        DateTimeUnit::Hour => "HOUR",
+        DateTimeUnit::Minute => "MINUTE",
+        DateTimeUnit::Second => "SECOND",
+    }
+}
+
+pub(crate) fn write_extract(
+    unit: &DateTimeUnit,
+    expr: &Expr,
+    state: &mut QueryBuildState,
+) -> Result {
+    write!(state, "EXTRACT(")?;
+    state.write_str(datetime_unit_name(unit))?;
+    write!(state, "FROM")?;
+    expr.to_sql(state)?;
+    write!(state, ")")
+}
+
+pub(crate) fn write_fn_call(name: &str, params: &[Expr], state: &mut QueryBuildState) -> Result {
+    state.write_str(name)?;
+    write!(state, "(")?;
+    state.join(params, |s| write!(s, ","))?;
+    write!(state, ")")
+}
+
+pub(crate) fn write_window_fn_call(
+    name: &str,
+    fn_call: &WindowFunctionCall,
+    state: &mut QueryBuildState,
+) -> Result {
+    state.write_str(name)?;
+    write!(state, "(")?;
+    if !fn_call.params.is_empty() {
+        state.join(&fn_call.params, |s| write!(s, ","))?;
+    }
+    write!(state, ")")?;
+
+    write!(state, "OVER")?;
+    write!(state, "(")?;
+    if !fn_call.partition_by.is_empty() {
+        write!(state, "PARTITION BY")?;
+        state.join(&fn_call.partition_by, |s| write!(s, ","))?;
+    }
+    if !fn_call.order_by.is_empty() {
+        write!(state, "ORDER BY")?;
+        state.join(&fn_call.order_by, |s| write!(s, ","))?;
+    }
+    write!(state, ")")
+}
@@ -1,5 +1,7 @@
+pub use dialect::*;
 pub use state::*;
 pub use to_sql::*;
 
+mod dialect;
 mod state;
 mod to_sql;
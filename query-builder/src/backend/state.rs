@@ -1,18 +1,72 @@
 use std::fmt::{Display, Formatter, Result, Write};
 
-use crate::ToSql;
+use crate::{Dialect, ToSql};
+use crate::drivers::default_dialect;
 
 pub type Token = String;
 pub type PlaceHolder = String;
 
-#[derive(Default)]
 pub struct QueryBuildState {
+    dialect: &'static dyn Dialect,
     tokens: Vec<Token>,
+    param_count: usize,
+    // Count of params before each item in the select list of the query being rendered
+    select_param_offsets: Vec<usize>,
 }
 
 impl QueryBuildState {
+    pub fn create(dialect: &'static dyn Dialect) -> Self {
+        QueryBuildState {
+            dialect,
+            tokens: vec![],
+            param_count: 0,
+            select_param_offsets: vec![],
+        }
+    }
+
+    pub fn dialect(&self) -> &'static dyn Dialect {
+        self.dialect
+    }
+
     pub fn append_param(&mut self) -> Result {
-        write!(self, "?")
+        self.param_count += 1;
+        let placeholder: PlaceHolder = self.dialect.placeholder(self.param_count);
+        self.write_str(&placeholder)
+    }
+
+    pub fn append_bool(&mut self, value: bool) -> Result {
+        let literal = self.dialect.bool_literal(value);
+        self.write_str(literal)
+    }
+
+    pub fn param_count(&self) -> usize {
+        self.param_count
+    }
+
+    pub fn set_param_count(&mut self, count: usize) {
+        self.param_count = count;
+    }
+
+    pub fn select_param_offset(&self, index: usize) -> Option<usize> {
+        self.select_param_offsets.get(index).copied()
+    }
+
+    // Returns the offsets of the enclosing query, which are restored after a nested one is rendered
+    pub fn replace_select_param_offsets(&mut self, offsets: Vec<usize>) -> Vec<usize> {
+        std::mem::replace(&mut self.select_param_offsets, offsets)
+    }
+
+    pub fn fork(&self) -> Self {
+        QueryBuildState {
+            dialect: self.dialect,
+            tokens: vec![],
+            param_count: self.param_count,
+            select_param_offsets: vec![],
+        }
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.tokens.extend(other.tokens);
     }
 
     pub fn join<T: ToSql>(
@@ -43,6 +97,12 @@ impl QueryBuildState {
     }
 }
 
+impl Default for QueryBuildState {
+    fn default() -> Self {
+        QueryBuildState::create(default_dialect())
+    }
+}
+
 impl Write for QueryBuildState {
     fn write_str(&mut self, s: &str) -> Result {
         self.tokens.push(s.to_string());
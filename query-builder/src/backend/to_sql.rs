@@ -1,11 +1,10 @@
-use std::fmt::Result;
-use std::iter::repeat;
+use std::fmt::{Result, Write};
 
 use sqlx::Database;
 use sqlx::database::HasArguments;
 use sqlx::query::Query;
 
-use crate::QueryBuildState;
+use crate::{DatabaseValue, QueryBuildState};
 
 pub trait ToSql {
     fn to_sql(&self, state: &mut QueryBuildState) -> Result;
@@ -14,15 +13,18 @@ pub trait ToSql {
 pub type QueryOf<'q, DB> = Query<'q, DB, <DB as HasArguments<'q>>::Arguments>;
 
 pub trait ArgSource<'q, DB: Database> {
-    fn insert_value_count() -> usize;
+    fn insert_values(&self) -> Vec<DatabaseValue>;
 
     fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB>
     where
         Self: Sized;
 }
 
+// A single row inserted by `Insertable::insert`, batch inserts use `Vec<S>` instead
+pub struct SingleSource<S>(pub S);
+
 pub trait ArgSourceList<'q, DB: Database> {
-    fn query_part(&self) -> String;
+    fn query_part(&self, state: &mut QueryBuildState) -> Result;
 
     fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB>
     where
@@ -42,17 +44,32 @@ impl<'q, DB: Database, I: BindArgs<'q, DB>, L: IntoIterator<Item = I>> BindArgs<
     }
 }
 
+fn write_value_tuple(values: &[DatabaseValue], state: &mut QueryBuildState) -> Result {
+    write!(state, "(")?;
+    state.join(values, |s| write!(s, ","))?;
+    write!(state, ")")
+}
+
+impl<'q, DB: Database, S: ArgSource<'q, DB>> ArgSourceList<'q, DB> for SingleSource<S> {
+    fn query_part(&self, state: &mut QueryBuildState) -> Result {
+        write_value_tuple(&self.0.insert_values(), state)
+    }
+
+    fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB>
+    where
+        Self: Sized,
+    {
+        self.0.bind_args(query)
+    }
+}
+
 impl<'q, DB: Database, S: ArgSource<'q, DB>> ArgSourceList<'q, DB> for Vec<S> {
-    fn query_part(&self) -> String {
-        let row = format!(
-            "({})",
-            repeat("?")
-                .take(S::insert_value_count())
-                .collect::<Vec<_>>()
-                .join(",")
-        );
-
-        repeat(row).take(self.len()).collect::<Vec<_>>().join(",")
+    fn query_part(&self, state: &mut QueryBuildState) -> Result {
+        state.join_by(
+            self,
+            |s, source| write_value_tuple(&source.insert_values(), s),
+            |s| write!(s, ","),
+        )
     }
 
     fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB>
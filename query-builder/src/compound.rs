@@ -0,0 +1,125 @@
+use std::fmt::{Display, Formatter, Write};
+
+use sqlx::Database;
+
+use crate::{
+    Alias, AppendToArgs, BindArgs, DatabaseValue, QueryBuildState, QueryOf, SelectQuery, ToSql,
+};
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum SetOperator {
+    Union,
+    UnionAll,
+    Intersect,
+    Except,
+}
+
+// `(left OP right) alias`, the rows of which are selected by the columns of `left`
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompoundSelect {
+    pub operator: SetOperator,
+    pub left: SelectQuery,
+    pub right: SelectQuery,
+    pub alias: Alias,
+}
+
+unsafe impl Send for CompoundSelect {}
+unsafe impl Sync for CompoundSelect {}
+
+impl CompoundSelect {
+    // Operands with their own `WITH`, `ORDER BY` or `LIMIT` can not be placed in compound selects
+    // directly in SQLite, they are selected from derived tables instead
+    fn operands(&self) -> [(&SelectQuery, Option<Alias>); 2] {
+        let derived = |operand: &SelectQuery, index: usize| {
+            (!operand.is_simple()).then(|| Alias {
+                name: format!("{}_{}", self.alias.name, index),
+            })
+        };
+
+        [
+            (&self.left, derived(&self.left, 0)),
+            (&self.right, derived(&self.right, 1)),
+        ]
+    }
+}
+
+fn write_operand(
+    (operand, alias): &(&SelectQuery, Option<Alias>),
+    state: &mut QueryBuildState,
+) -> std::fmt::Result {
+    match alias {
+        Some(alias) => {
+            write!(state, "SELECT")?;
+            write!(state, "*")?;
+            write!(state, "FROM")?;
+            write!(state, "(")?;
+            operand.to_sql(state)?;
+            write!(state, ")")?;
+            alias.to_sql(state)
+        }
+        None => operand.to_sql(state),
+    }
+}
+
+fn fmt_operand(
+    (operand, alias): &(&SelectQuery, Option<Alias>),
+    f: &mut Formatter<'_>,
+) -> std::fmt::Result {
+    match alias {
+        Some(alias) => write!(f, "SELECT * FROM ({}) {}", operand, alias),
+        None => write!(f, "{}", operand),
+    }
+}
+
+impl Display for SetOperator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SetOperator::Union => write!(f, "UNION"),
+            SetOperator::UnionAll => write!(f, "UNION ALL"),
+            SetOperator::Intersect => write!(f, "INTERSECT"),
+            SetOperator::Except => write!(f, "EXCEPT"),
+        }
+    }
+}
+
+impl Display for CompoundSelect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let [left, right] = self.operands();
+
+        write!(f, "FROM (")?;
+        fmt_operand(&left, f)?;
+        write!(f, " {} ", self.operator)?;
+        fmt_operand(&right, f)?;
+        write!(f, ") {}", self.alias)
+    }
+}
+
+impl ToSql for SetOperator {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        write!(state, "{}", self)
+    }
+}
+
+impl ToSql for CompoundSelect {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        let [left, right] = self.operands();
+
+        write!(state, "FROM")?;
+        write!(state, "(")?;
+        write_operand(&left, state)?;
+        self.operator.to_sql(state)?;
+        write_operand(&right, state)?;
+        write!(state, ")")?;
+
+        self.alias.to_sql(state)
+    }
+}
+
+impl<'q, DB: Database> BindArgs<'q, DB> for CompoundSelect
+where
+    DatabaseValue: for<'p> AppendToArgs<'p, DB>,
+{
+    fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
+        self.right.bind_args(self.left.bind_args(query))
+    }
+}
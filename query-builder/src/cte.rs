@@ -0,0 +1,85 @@
+use std::fmt::{Display, Formatter, Write};
+
+use sqlx::Database;
+
+use crate::{AppendToArgs, BindArgs, DatabaseValue, QueryBuildState, QueryOf, SelectQuery, ToSql};
+
+// `name (columns) AS (query UNION ALL recursive)`, `recursive` references the CTE itself and is
+// evaluated repeatedly on the rows produced by the previous step until no row is produced
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommonTableExpr {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub query: SelectQuery,
+    pub recursive: Option<SelectQuery>,
+}
+
+unsafe impl Send for CommonTableExpr {}
+unsafe impl Sync for CommonTableExpr {}
+
+impl Display for CommonTableExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}({}) AS ({}",
+            self.name,
+            self.columns.join(", "),
+            self.query
+        )?;
+        if let Some(recursive) = &self.recursive {
+            write!(f, " UNION ALL {}", recursive)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl ToSql for CommonTableExpr {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        state.write_str(&self.name)?;
+        write!(state, "(")?;
+        state.join_by(
+            &self.columns,
+            |s, column| {
+                let column = s.dialect().quote_ident(column);
+                s.write_str(&column)
+            },
+            |s| write!(s, ","),
+        )?;
+        write!(state, ")")?;
+
+        write!(state, "AS")?;
+        write!(state, "(")?;
+        self.query.to_sql(state)?;
+        if let Some(recursive) = &self.recursive {
+            write!(state, "UNION ALL")?;
+            recursive.to_sql(state)?;
+        }
+        write!(state, ")")
+    }
+}
+
+impl<'q, DB: Database> BindArgs<'q, DB> for CommonTableExpr
+where
+    DatabaseValue: for<'p> AppendToArgs<'p, DB>,
+{
+    fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
+        self.recursive.bind_args(self.query.bind_args(query))
+    }
+}
+
+// `WITH` clause of a query, which is rendered before `SELECT`
+pub(crate) fn write_with_clause(
+    ctes: &[CommonTableExpr],
+    state: &mut QueryBuildState,
+) -> std::fmt::Result {
+    if ctes.is_empty() {
+        return Ok(());
+    }
+
+    write!(state, "WITH")?;
+    if ctes.iter().any(|cte| cte.recursive.is_some()) {
+        write!(state, "RECURSIVE")?;
+    }
+
+    state.join(ctes, |s| write!(s, ","))
+}
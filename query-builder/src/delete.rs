@@ -70,11 +70,7 @@ impl ToSql for DeleteQuery {
             state.join(&self.order_by, |s| write!(s, ","))?;
         }
 
-        if let Some(l) = self.limit {
-            write!(state, "LIMIT {}", l)?;
-        }
-
-        Ok(())
+        state.dialect().write_limit_offset(self.limit, 0, state)
     }
 }
 
@@ -1,5 +1,28 @@
-#[cfg(feature = "mysql")]
-pub use mysql::*;
+use crate::Dialect;
+
+pub use mysql::MySqlDialect;
+pub use pgsql::PostgresDialect;
+pub use sqlite::SqliteDialect;
 
-#[cfg(feature = "mysql")]
 mod mysql;
+mod pgsql;
+mod sqlite;
+
+#[cfg(feature = "mysql")]
+pub(crate) fn default_dialect() -> &'static dyn Dialect {
+    &MySqlDialect
+}
+
+#[cfg(all(feature = "pgsql", not(feature = "mysql")))]
+pub(crate) fn default_dialect() -> &'static dyn Dialect {
+    &PostgresDialect
+}
+
+#[cfg(all(feature = "sqlite", not(any(feature = "mysql", feature = "pgsql"))))]
+pub(crate) fn default_dialect() -> &'static dyn Dialect {
+    &SqliteDialect
+}
+
+fn null_of<T>() -> Option<T> {
+    None
+}
@@ -1,61 +1,319 @@
 use std::fmt::{Result, Write};
 
+use interface::DatabaseType;
+
 use crate::{
-    AggregateFunction, GroupConcatFunctionCall, NormalAggregateFunctionCall, QueryBuildState,
-    SubqueryFunction, SubqueryFunctionCall, ToSql,
+    DateTimeFunction, DateTimeFunctionCall, DateTimeUnit, Dialect, GroupConcatFunctionCall,
+    OnConflict, PlaceHolder, QueryBuildState, ScalarFunction, ScalarFunctionCall, ToSql,
+    datetime_unit_name, write_extract, write_fn_call,
 };
 
-pub fn convert_group_concat(
-    fn_call: &GroupConcatFunctionCall,
-    state: &mut QueryBuildState,
-) -> Result {
-    write!(state, "GROUP_CONCAT(")?;
-    fn_call.expr.to_sql(state)?;
-    if !fn_call.order_by.is_empty() {
-        write!(state, "ORDER BY")?;
+#[derive(Clone, Copy, Debug)]
+pub struct MySqlDialect;
 
-        state.join(&fn_call.order_by, |s| write!(s, ","))?;
+impl Dialect for MySqlDialect {
+    fn quote_ident(&self, name: &str) -> String {
+        format!("`{}`", name.replace('`', "``"))
     }
 
-    if let Some(separator) = &fn_call.separator {
-        write!(state, "SEPARATOR")?;
-        let sp = format!("'{}'", separator);
-        state.write_str(&sp)?;
+    fn placeholder(&self, _index: usize) -> PlaceHolder {
+        "?".to_string()
     }
 
-    write!(state, ")")
-}
+    fn column_type(&self, ty: &DatabaseType) -> &'static str {
+        match ty {
+            DatabaseType::Bool => "BOOL",
+            DatabaseType::SmallInteger => "SMALLINT",
+            DatabaseType::UnsignedSmallInteger => "SMALLINT UNSIGNED",
+            DatabaseType::Integer => "INT",
+            DatabaseType::UnsignedInteger => "INT UNSIGNED",
+            DatabaseType::BigInteger => "BIGINT",
+            DatabaseType::UnsignedBigInteger => "BIGINT UNSIGNED",
+            DatabaseType::Float => "FLOAT",
+            DatabaseType::Double => "DOUBLE",
+            DatabaseType::Decimal => "DECIMAL(65, 30)",
+            DatabaseType::Binary => "BLOB",
+            DatabaseType::Time => "TIME",
+            DatabaseType::Date => "DATE",
+            DatabaseType::DateTime => "DATETIME",
+            DatabaseType::String => "VARCHAR(255)",
+            DatabaseType::Json => "JSON",
+        }
+    }
+
+    fn auto_increment(&self) -> Option<&'static str> {
+        Some("AUTO_INCREMENT")
+    }
+
+    fn cast_type(&self, ty: &DatabaseType) -> &'static str {
+        match ty {
+            DatabaseType::Bool
+            | DatabaseType::SmallInteger
+            | DatabaseType::Integer
+            | DatabaseType::BigInteger => "SIGNED",
+            DatabaseType::UnsignedSmallInteger
+            | DatabaseType::UnsignedInteger
+            | DatabaseType::UnsignedBigInteger => "UNSIGNED",
+            DatabaseType::Binary => "BINARY",
+            DatabaseType::String => "CHAR",
+            ty => self.column_type(ty),
+        }
+    }
+
+    fn supports_returning(&self) -> bool {
+        false
+    }
+
+    // The conflict target is implied by the unique keys, `DoNothing` is rendered as a no-op
+    // assignment of the first target column, or the first inserted column without a target,
+    // because `INSERT IGNORE` would suppress other errors as well
+    // `DoUpdate` refers to the inserted row by the `AS excluded` row alias, which requires MySQL
+    // 8.0.19 or later, older servers reject the query as a syntax error
+    fn write_on_conflict(
+        &self,
+        columns: &[String],
+        on_conflict: &OnConflict,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        match on_conflict {
+            OnConflict::DoNothing { target } => {
+                let column = target
+                    .first()
+                    .or_else(|| columns.first())
+                    .ok_or(std::fmt::Error)?;
+                let column = self.quote_ident(column);
+                write!(state, "ON DUPLICATE KEY UPDATE {}={}", column, column)
+            }
+            OnConflict::DoUpdate { assignments, .. } => {
+                write!(state, "AS")?;
+                OnConflict::excluded().to_sql(state)?;
+                write!(state, "ON DUPLICATE KEY UPDATE")?;
+                state.join(assignments, |s| write!(s, ","))
+            }
+        }
+    }
+
+    fn parse_column_type(&self, ty: &str) -> Option<DatabaseType> {
+        let ty = ty.to_lowercase();
+        if ty.starts_with("tinyint(1)") {
+            return Some(DatabaseType::Bool);
+        }
+
+        let unsigned = ty.ends_with("unsigned");
+        let name = ty.split(['(', ' ']).next().unwrap_or_default();
+
+        match (name, unsigned) {
+            ("bool" | "boolean", false) => Some(DatabaseType::Bool),
+            ("smallint", false) => Some(DatabaseType::SmallInteger),
+            ("smallint", true) => Some(DatabaseType::UnsignedSmallInteger),
+            ("int" | "integer", false) => Some(DatabaseType::Integer),
+            ("int" | "integer", true) => Some(DatabaseType::UnsignedInteger),
+            ("bigint", false) => Some(DatabaseType::BigInteger),
+            ("bigint", true) => Some(DatabaseType::UnsignedBigInteger),
+            ("float", false) => Some(DatabaseType::Float),
+            ("double", false) => Some(DatabaseType::Double),
+            ("decimal", false) => Some(DatabaseType::Decimal),
+            ("blob", false) => Some(DatabaseType::Binary),
+            ("time", false) => Some(DatabaseType::Time),
+            ("date", false) => Some(DatabaseType::Date),
+            ("datetime", false) => Some(DatabaseType::DateTime),
+            ("varchar", false) => Some(DatabaseType::String),
+            ("json", false) => Some(DatabaseType::Json),
+            _ => None,
+        }
+    }
+
+    fn column_info_query(&self) -> &'static str {
+        "SELECT CAST(TABLE_NAME AS CHAR), CAST(COLUMN_NAME AS CHAR), CAST(COLUMN_TYPE AS CHAR), \
+        CAST(IS_NULLABLE AS CHAR) FROM information_schema.COLUMNS \
+        WHERE TABLE_SCHEMA = DATABASE() ORDER BY TABLE_NAME, ORDINAL_POSITION"
+    }
+
+    fn index_info_query(&self) -> &'static str {
+        "SELECT CAST(s.TABLE_NAME AS CHAR), CAST(s.INDEX_NAME AS CHAR), CAST(s.COLUMN_NAME AS CHAR), \
+        CASE WHEN s.NON_UNIQUE = 0 THEN 'YES' ELSE 'NO' END FROM information_schema.STATISTICS s \
+        WHERE s.TABLE_SCHEMA = DATABASE() AND s.INDEX_NAME <> 'PRIMARY' AND NOT EXISTS (\
+        SELECT 1 FROM information_schema.TABLE_CONSTRAINTS c WHERE c.TABLE_SCHEMA = s.TABLE_SCHEMA \
+        AND c.TABLE_NAME = s.TABLE_NAME AND c.CONSTRAINT_NAME = s.INDEX_NAME \
+        AND c.CONSTRAINT_TYPE = 'FOREIGN KEY') ORDER BY s.TABLE_NAME, s.INDEX_NAME, s.SEQ_IN_INDEX"
+    }
+
+    fn write_drop_index(&self, table: &str, name: &str, state: &mut QueryBuildState) -> Result {
+        write!(state, "DROP INDEX")?;
+        state.write_str(&self.quote_ident(name))?;
+        write!(state, "ON")?;
+        state.write_str(&self.quote_ident(table))
+    }
 
-pub fn convert_subquery_fn(fn_call: &SubqueryFunctionCall, state: &mut QueryBuildState) -> Result {
-    match &fn_call.function {
-        SubqueryFunction::Any => write!(state, "ANY"),
-        SubqueryFunction::All => write!(state, "ALL"),
-    }?;
-    write!(state, "(")?;
-    fn_call.subquery.to_sql(state)?;
-    write!(state, ")")
+    // OFFSET can not be used without LIMIT in MySQL
+    fn write_limit_offset(
+        &self,
+        limit: Option<usize>,
+        offset: usize,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        match limit {
+            Some(limit) => write!(state, "LIMIT {}", limit),
+            None if offset != 0 => write!(state, "LIMIT {}", u64::MAX),
+            None => Ok(()),
+        }?;
+
+        if offset != 0 {
+            write!(state, "OFFSET {}", offset)?;
+        }
+
+        Ok(())
+    }
+
+    fn convert_group_concat(
+        &self,
+        fn_call: &GroupConcatFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        write!(state, "GROUP_CONCAT(")?;
+        fn_call.expr.to_sql(state)?;
+        if !fn_call.order_by.is_empty() {
+            write!(state, "ORDER BY")?;
+
+            state.join(&fn_call.order_by, |s| write!(s, ","))?;
+        }
+
+        if let Some(separator) = &fn_call.separator {
+            write!(state, "SEPARATOR")?;
+            let sp = format!("'{}'", separator.replace('\'', "''"));
+            state.write_str(&sp)?;
+        }
+
+        write!(state, ")")
+    }
+
+    // JSON_EXTRACT keeps strings quoted, the same as `->` while `->>` unquotes them
+    fn convert_scalar_fn_call(
+        &self,
+        fn_call: &ScalarFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        match fn_call.function {
+            ScalarFunction::JsonExtract => {
+                write!(state, "JSON_UNQUOTE(")?;
+                write_fn_call("JSON_EXTRACT", &fn_call.params, state)?;
+                write!(state, ")")
+            }
+            function => write_fn_call(self.scalar_fn_name(&function), &fn_call.params, state),
+        }
+    }
+
+    // There is no DATE_TRUNC, the truncated value is formatted as a string instead
+    fn convert_datetime_fn_call(
+        &self,
+        fn_call: &DateTimeFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        let params = &fn_call.params;
+        match fn_call.function {
+            DateTimeFunction::Now => write!(state, "CURRENT_TIMESTAMP"),
+            DateTimeFunction::Extract(unit) => write_extract(&unit, &params[0], state),
+            DateTimeFunction::Truncate(unit) => {
+                write!(state, "DATE_FORMAT(")?;
+                params[0].to_sql(state)?;
+                write!(state, ",")?;
+                state.write_str(match unit {
+                    DateTimeUnit::Year => "'%Y-01-01 00:00:00'",
+                    DateTimeUnit::Month => "'%Y-%m-01 00:00:00'",
+                    DateTimeUnit::Day => "'%Y-%m-%d 00:00:00'",
+                    DateTimeUnit::Hour => "'%Y-%m-%d %H:00:00'",
+                    DateTimeUnit::Minute => "'%Y-%m-%d %H:%i:00'",
+                    DateTimeUnit::Second => "'%Y-%m-%d %H:%i:%s'",
+                })?;
+                write!(state, ")")
+            }
+            DateTimeFunction::AddInterval(unit) => {
+                write!(state, "DATE_ADD(")?;
+                params[0].to_sql(state)?;
+                write!(state, ", INTERVAL")?;
+                params[1].to_sql(state)?;
+                state.write_str(datetime_unit_name(&unit))?;
+                write!(state, ")")
+            }
+            // TIMESTAMPDIFF(SECOND, l, r) is `r - l`, it is negated to keep the order of arguments
+            DateTimeFunction::DiffInSeconds => {
+                write!(state, "(")?;
+                write!(state, "- TIMESTAMPDIFF(SECOND,")?;
+                state.join(params, |s| write!(s, ","))?;
+                write!(state, ")")?;
+                write!(state, ")")
+            }
+        }
+    }
+
+    // Backslashes are escaped in string literals
+    fn like_escape(&self) -> &'static str {
+        "'\\\\'"
+    }
 }
 
-pub fn convert_normal_aggregate_fn_call(
-    fn_call: &NormalAggregateFunctionCall,
-    state: &mut QueryBuildState,
-) -> Result {
-    match &fn_call.function {
-        AggregateFunction::Average => write!(state, "AVG"),
-        AggregateFunction::Sum => write!(state, "SUM"),
-        AggregateFunction::BitAnd => write!(state, "BIT_AND"),
-        AggregateFunction::BitOr => write!(state, "BIT_OR"),
-        AggregateFunction::BitXor => write!(state, "BIT_XOR"),
-        AggregateFunction::Count | AggregateFunction::CountDistinct => write!(state, "COUNT"),
-        AggregateFunction::Max => write!(state, "Max"),
-        AggregateFunction::Min => write!(state, "Min"),
-        _ => unreachable!(),
-    }?;
-    write!(state, "(")?;
-    if let AggregateFunction::CountDistinct = fn_call.function {
-        write!(state, "DISTINCT")?;
-    }
-
-    fn_call.param.to_sql(state)?;
-    write!(state, ")")
+#[cfg(feature = "mysql")]
+mod database {
+    use serde_json::Value;
+    use sqlx::MySql;
+    use sqlx::mysql::MySqlQueryResult;
+    use sqlx::types::Decimal;
+    use sqlx::types::time::{Date, PrimitiveDateTime, Time};
+
+    use interface::DatabaseType;
+
+    use crate::{AppendToArgs, Binary, DatabaseValue, Dialect, HasDialect, QueryOf};
+    use crate::drivers::{MySqlDialect, null_of};
+
+    impl HasDialect for MySql {
+        fn dialect() -> &'static dyn Dialect {
+            &MySqlDialect
+        }
+
+        fn last_insert_id(result: &MySqlQueryResult) -> Option<u64> {
+            Some(result.last_insert_id())
+        }
+    }
+
+    impl<'q> AppendToArgs<'q, MySql> for DatabaseValue {
+        fn bind_on(self, query: QueryOf<'q, MySql>) -> QueryOf<'q, MySql> {
+            match self {
+                DatabaseValue::Bool(b) => query.bind(b),
+                DatabaseValue::SmallInteger(i) => query.bind(i),
+                DatabaseValue::UnsignedSmallInteger(i) => query.bind(i),
+                DatabaseValue::Integer(i) => query.bind(i),
+                DatabaseValue::UnsignedInteger(i) => query.bind(i),
+                DatabaseValue::BigInteger(i) => query.bind(i),
+                DatabaseValue::UnsignedBigInteger(i) => query.bind(i),
+                DatabaseValue::Float(f) => query.bind(f),
+                DatabaseValue::Double(f) => query.bind(f),
+                DatabaseValue::Decimal(f) => query.bind(f),
+                DatabaseValue::Binary(b) => query.bind(b),
+                DatabaseValue::Time(t) => query.bind(t),
+                DatabaseValue::Date(t) => query.bind(t),
+                DatabaseValue::DateTime(t) => query.bind(t),
+                DatabaseValue::String(s) => query.bind(s),
+                DatabaseValue::Json(j) => query.bind(j),
+                DatabaseValue::Invalid(t, _) => DatabaseValue::Null(t).bind_on(query),
+                DatabaseValue::Null(t) => match t {
+                    DatabaseType::Bool => query.bind(null_of::<i16>()),
+                    DatabaseType::SmallInteger => query.bind(null_of::<i16>()),
+                    DatabaseType::UnsignedSmallInteger => query.bind(null_of::<u16>()),
+                    DatabaseType::Integer => query.bind(null_of::<i32>()),
+                    DatabaseType::UnsignedInteger => query.bind(null_of::<u32>()),
+                    DatabaseType::BigInteger => query.bind(null_of::<i64>()),
+                    DatabaseType::UnsignedBigInteger => query.bind(null_of::<u64>()),
+                    DatabaseType::Float => query.bind(null_of::<f32>()),
+                    DatabaseType::Double => query.bind(null_of::<f64>()),
+                    DatabaseType::Binary => query.bind(null_of::<Binary>()),
+                    DatabaseType::Decimal => query.bind(null_of::<Decimal>()),
+                    DatabaseType::Time => query.bind(null_of::<Time>()),
+                    DatabaseType::Date => query.bind(null_of::<Date>()),
+                    DatabaseType::DateTime => query.bind(null_of::<PrimitiveDateTime>()),
+                    DatabaseType::String => query.bind(null_of::<String>()),
+                    DatabaseType::Json => query.bind(null_of::<Value>()),
+                },
+            }
+        }
+    }
 }
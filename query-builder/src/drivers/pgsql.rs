@@ -0,0 +1,344 @@
+use std::fmt::{Result, Write};
+
+use interface::{ColumnDefinition, DatabaseType};
+
+use crate::{
+    AggregateFunction, DateTimeFunction, DateTimeFunctionCall, DateTimeUnit, Dialect,
+    GroupConcatFunctionCall, NormalAggregateFunctionCall, PlaceHolder, QueryBuildState,
+    ScalarFunction, ScalarFunctionCall, ToSql, WindowFunction, WindowFunctionCall,
+    datetime_unit_name, write_concat, write_extract, write_fn_call, write_window_fn_call,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct PostgresDialect;
+
+impl Dialect for PostgresDialect {
+    fn quote_ident(&self, name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    fn placeholder(&self, index: usize) -> PlaceHolder {
+        format!("${}", index)
+    }
+
+    fn numbered_placeholders(&self) -> bool {
+        true
+    }
+
+    // Unsigned types are widened to the next signed type, see `AppendToArgs` below
+    fn column_type(&self, ty: &DatabaseType) -> &'static str {
+        match ty {
+            DatabaseType::Bool => "BOOLEAN",
+            DatabaseType::SmallInteger => "SMALLINT",
+            DatabaseType::UnsignedSmallInteger => "INTEGER",
+            DatabaseType::Integer => "INTEGER",
+            DatabaseType::UnsignedInteger => "BIGINT",
+            DatabaseType::BigInteger => "BIGINT",
+            DatabaseType::UnsignedBigInteger => "NUMERIC(20)",
+            DatabaseType::Float => "REAL",
+            DatabaseType::Double => "DOUBLE PRECISION",
+            DatabaseType::Decimal => "NUMERIC",
+            DatabaseType::Binary => "BYTEA",
+            DatabaseType::Time => "TIME",
+            DatabaseType::Date => "DATE",
+            DatabaseType::DateTime => "TIMESTAMP",
+            DatabaseType::String => "TEXT",
+            DatabaseType::Json => "JSON",
+        }
+    }
+
+    fn auto_increment(&self) -> Option<&'static str> {
+        Some("GENERATED BY DEFAULT AS IDENTITY")
+    }
+
+    fn parse_column_type(&self, ty: &str) -> Option<DatabaseType> {
+        match ty.to_lowercase().as_str() {
+            "boolean" => Some(DatabaseType::Bool),
+            "smallint" => Some(DatabaseType::SmallInteger),
+            "integer" => Some(DatabaseType::Integer),
+            "bigint" => Some(DatabaseType::BigInteger),
+            "numeric(20)" => Some(DatabaseType::UnsignedBigInteger),
+            "real" => Some(DatabaseType::Float),
+            "double precision" => Some(DatabaseType::Double),
+            "bytea" => Some(DatabaseType::Binary),
+            "time without time zone" => Some(DatabaseType::Time),
+            "date" => Some(DatabaseType::Date),
+            "timestamp without time zone" => Some(DatabaseType::DateTime),
+            "text" | "character varying" => Some(DatabaseType::String),
+            "json" => Some(DatabaseType::Json),
+            numeric if numeric.starts_with("numeric") => Some(DatabaseType::Decimal),
+            _ => None,
+        }
+    }
+
+    fn column_info_query(&self) -> &'static str {
+        "SELECT table_name::text, column_name::text, CASE WHEN data_type = 'numeric' \
+        AND numeric_precision IS NOT NULL THEN 'numeric(' || numeric_precision || ')' \
+        ELSE data_type::text END, is_nullable::text FROM information_schema.columns \
+        WHERE table_schema = current_schema() ORDER BY table_name, ordinal_position"
+    }
+
+    // information_schema has no view of indexes
+    fn index_info_query(&self) -> &'static str {
+        "SELECT t.relname::text, i.relname::text, a.attname::text, \
+        CASE WHEN ix.indisunique THEN 'YES' ELSE 'NO' END FROM pg_index ix \
+        JOIN pg_class t ON t.oid = ix.indrelid JOIN pg_class i ON i.oid = ix.indexrelid \
+        JOIN pg_namespace n ON n.oid = t.relnamespace \
+        JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = ANY(ix.indkey) \
+        WHERE n.nspname = current_schema() AND NOT ix.indisprimary AND NOT EXISTS (\
+        SELECT 1 FROM pg_constraint c WHERE c.conindid = ix.indexrelid \
+        AND c.contype IN ('p', 'u', 'x')) \
+        ORDER BY t.relname, i.relname, array_position(ix.indkey::int2[], a.attnum)"
+    }
+
+    fn write_modify_column(
+        &self,
+        column: &ColumnDefinition,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        let name = self.quote_ident(&column.name);
+        let ty = self.column_type(&column.ty);
+        write!(
+            state,
+            "ALTER COLUMN {} TYPE {} USING {}::{},",
+            name, ty, name, ty
+        )?;
+        write!(state, "ALTER COLUMN {}", name)?;
+        if column.optional {
+            write!(state, "DROP NOT NULL")
+        } else {
+            write!(state, "SET NOT NULL")
+        }
+    }
+
+    // AVG and SUM return bigint or numeric depending on the input type in Postgres,
+    // cast them so that they can always be decoded as Decimal.
+    fn convert_normal_aggregate_fn_call(
+        &self,
+        fn_call: &NormalAggregateFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        let cast_to_numeric = matches!(
+            fn_call.function,
+            AggregateFunction::Average | AggregateFunction::Sum
+        );
+
+        if cast_to_numeric {
+            write!(state, "CAST(")?;
+        }
+
+        state.write_str(self.aggregate_fn_name(&fn_call.function))?;
+        write!(state, "(")?;
+        if let AggregateFunction::CountDistinct = fn_call.function {
+            write!(state, "DISTINCT")?;
+        }
+
+        fn_call.param.to_sql(state)?;
+        write!(state, ")")?;
+
+        if cast_to_numeric {
+            write!(state, "AS NUMERIC)")?;
+        }
+
+        Ok(())
+    }
+
+    // Cast for the same reason as aggregate functions
+    fn convert_window_fn_call(
+        &self,
+        fn_call: &WindowFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        let cast_to_numeric = matches!(
+            fn_call.function,
+            WindowFunction::Aggregate(AggregateFunction::Average | AggregateFunction::Sum)
+        );
+
+        if cast_to_numeric {
+            write!(state, "CAST(")?;
+        }
+
+        write_window_fn_call(self.window_fn_name(&fn_call.function), fn_call, state)?;
+
+        if cast_to_numeric {
+            write!(state, "AS NUMERIC)")?;
+        }
+
+        Ok(())
+    }
+
+    fn convert_group_concat(
+        &self,
+        fn_call: &GroupConcatFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        write!(state, "STRING_AGG(")?;
+        write!(state, "CAST(")?;
+        fn_call.expr.to_sql(state)?;
+        write!(state, "AS TEXT)")?;
+        write!(state, ",")?;
+
+        let separator = fn_call.separator.as_deref().unwrap_or(",");
+        let sp = format!("'{}'", separator.replace('\'', "''"));
+        state.write_str(&sp)?;
+
+        if !fn_call.order_by.is_empty() {
+            write!(state, "ORDER BY")?;
+
+            state.join(&fn_call.order_by, |s| write!(s, ","))?;
+        }
+
+        write!(state, ")")
+    }
+
+    // Paths are in the SQL/JSON path language, `#>> '{}'` unquotes the extracted value
+    fn convert_scalar_fn_call(
+        &self,
+        fn_call: &ScalarFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        match fn_call.function {
+            ScalarFunction::JsonExtract => {
+                write!(state, "(")?;
+                write!(state, "JSONB_PATH_QUERY_FIRST(")?;
+                write!(state, "CAST(")?;
+                fn_call.params[0].to_sql(state)?;
+                write!(state, "AS JSONB)")?;
+                write!(state, ",")?;
+                write!(state, "CAST(")?;
+                fn_call.params[1].to_sql(state)?;
+                write!(state, "AS JSONPATH)")?;
+                write!(state, ")")?;
+                write!(state, "#>> '{{}}'")?;
+                write!(state, ")")
+            }
+            ScalarFunction::Concat => write_concat(&fn_call.params, state),
+            // ROUND with a precision is only defined for NUMERIC
+            ScalarFunction::Round => {
+                write!(state, "ROUND(")?;
+                write!(state, "CAST(")?;
+                fn_call.params[0].to_sql(state)?;
+                write!(state, "AS NUMERIC)")?;
+                write!(state, ",")?;
+                fn_call.params[1].to_sql(state)?;
+                write!(state, ")")
+            }
+            function => write_fn_call(self.scalar_fn_name(&function), &fn_call.params, state),
+        }
+    }
+
+    // CURRENT_TIMESTAMP is with time zone. The difference of dates is in days instead of an
+    // interval. Seconds are extracted with the fractional part, which is floored as in other
+    // databases instead of being rounded by the cast
+    fn convert_datetime_fn_call(
+        &self,
+        fn_call: &DateTimeFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        let params = &fn_call.params;
+        match fn_call.function {
+            DateTimeFunction::Now => write!(state, "LOCALTIMESTAMP"),
+            DateTimeFunction::Extract(DateTimeUnit::Second) => {
+                write!(state, "FLOOR(")?;
+                write_extract(&DateTimeUnit::Second, &params[0], state)?;
+                write!(state, ")")
+            }
+            DateTimeFunction::Extract(unit) => write_extract(&unit, &params[0], state),
+            DateTimeFunction::Truncate(unit) => {
+                write!(state, "DATE_TRUNC(")?;
+                state.write_str(&format!("'{}'", datetime_unit_name(&unit)))?;
+                write!(state, ",")?;
+                params[0].to_sql(state)?;
+                write!(state, ")")
+            }
+            DateTimeFunction::AddInterval(unit) => {
+                write!(state, "(")?;
+                params[0].to_sql(state)?;
+                write!(state, "+")?;
+                write!(state, "(")?;
+                params[1].to_sql(state)?;
+                write!(state, "* INTERVAL")?;
+                state.write_str(&format!("'1 {}'", datetime_unit_name(&unit)))?;
+                write!(state, ")")?;
+                write!(state, ")")
+            }
+            DateTimeFunction::DiffInSeconds if fn_call.ty == DatabaseType::Date => {
+                write!(state, "(")?;
+                write!(state, "(")?;
+                state.join(params, |s| write!(s, "-"))?;
+                write!(state, ")")?;
+                write!(state, "* 86400")?;
+                write!(state, ")")
+            }
+            DateTimeFunction::DiffInSeconds => {
+                write!(state, "EXTRACT(EPOCH FROM")?;
+                write!(state, "(")?;
+                state.join(params, |s| write!(s, "-"))?;
+                write!(state, ")")?;
+                write!(state, ")")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "pgsql")]
+mod database {
+    use serde_json::Value;
+    use sqlx::Postgres;
+    use sqlx::types::Decimal;
+    use sqlx::types::time::{Date, PrimitiveDateTime, Time};
+
+    use interface::DatabaseType;
+
+    use crate::{AppendToArgs, Binary, DatabaseValue, Dialect, HasDialect, QueryOf};
+    use crate::drivers::{PostgresDialect, null_of};
+
+    impl HasDialect for Postgres {
+        fn dialect() -> &'static dyn Dialect {
+            &PostgresDialect
+        }
+    }
+
+    // Postgres has no unsigned integer types, unsigned values are widened to the next signed type.
+    impl<'q> AppendToArgs<'q, Postgres> for DatabaseValue {
+        fn bind_on(self, query: QueryOf<'q, Postgres>) -> QueryOf<'q, Postgres> {
+            match self {
+                DatabaseValue::Bool(b) => query.bind(b),
+                DatabaseValue::SmallInteger(i) => query.bind(i),
+                DatabaseValue::UnsignedSmallInteger(i) => query.bind(i as i32),
+                DatabaseValue::Integer(i) => query.bind(i),
+                DatabaseValue::UnsignedInteger(i) => query.bind(i as i64),
+                DatabaseValue::BigInteger(i) => query.bind(i),
+                DatabaseValue::UnsignedBigInteger(i) => query.bind(Decimal::from(i)),
+                DatabaseValue::Float(f) => query.bind(f),
+                DatabaseValue::Double(f) => query.bind(f),
+                DatabaseValue::Decimal(f) => query.bind(f),
+                DatabaseValue::Binary(b) => query.bind(b),
+                DatabaseValue::Time(t) => query.bind(t),
+                DatabaseValue::Date(t) => query.bind(t),
+                DatabaseValue::DateTime(t) => query.bind(t),
+                DatabaseValue::String(s) => query.bind(s),
+                DatabaseValue::Json(j) => query.bind(j),
+                DatabaseValue::Invalid(t, _) => DatabaseValue::Null(t).bind_on(query),
+                DatabaseValue::Null(t) => match t {
+                    DatabaseType::Bool => query.bind(null_of::<bool>()),
+                    DatabaseType::SmallInteger => query.bind(null_of::<i16>()),
+                    DatabaseType::UnsignedSmallInteger => query.bind(null_of::<i32>()),
+                    DatabaseType::Integer => query.bind(null_of::<i32>()),
+                    DatabaseType::UnsignedInteger => query.bind(null_of::<i64>()),
+                    DatabaseType::BigInteger => query.bind(null_of::<i64>()),
+                    DatabaseType::UnsignedBigInteger => query.bind(null_of::<Decimal>()),
+                    DatabaseType::Float => query.bind(null_of::<f32>()),
+                    DatabaseType::Double => query.bind(null_of::<f64>()),
+                    DatabaseType::Binary => query.bind(null_of::<Binary>()),
+                    DatabaseType::Decimal => query.bind(null_of::<Decimal>()),
+                    DatabaseType::Time => query.bind(null_of::<Time>()),
+                    DatabaseType::Date => query.bind(null_of::<Date>()),
+                    DatabaseType::DateTime => query.bind(null_of::<PrimitiveDateTime>()),
+                    DatabaseType::String => query.bind(null_of::<String>()),
+                    DatabaseType::Json => query.bind(null_of::<Value>()),
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,497 @@
+use std::convert::TryFrom;
+use std::fmt::{Error, Result, Write};
+
+use interface::DatabaseType;
+
+use crate::{
+    AggregateFunction, DatabaseValue, DateTimeFunction, DateTimeFunctionCall, DateTimeUnit, Dialect, Expr,
+    FunctionCall, GroupConcatFunctionCall, Ident, NormalAggregateFunctionCall, PlaceHolder,
+    QueryBuildState, ScalarFunction, ScalarFunctionCall, SubqueryFunction, SubqueryFunctionCall,
+    ToSql, write_comparison, write_concat, write_fn_call,
+};
+
+const SUBQUERY_COLUMN: &str = "__value";
+
+#[derive(Clone, Copy, Debug)]
+pub struct SqliteDialect;
+
+impl Dialect for SqliteDialect {
+    fn quote_ident(&self, name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    // Placeholders are numbered, so that a fragment can be rendered in a different order than its
+    // arguments are bound.
+    fn placeholder(&self, index: usize) -> PlaceHolder {
+        format!("?{}", index)
+    }
+
+    fn numbered_placeholders(&self) -> bool {
+        true
+    }
+
+    // Decimal and time values are stored as text, see `AppendToArgs` below
+    fn column_type(&self, ty: &DatabaseType) -> &'static str {
+        match ty {
+            DatabaseType::Bool
+            | DatabaseType::SmallInteger
+            | DatabaseType::UnsignedSmallInteger
+            | DatabaseType::Integer
+            | DatabaseType::UnsignedInteger
+            | DatabaseType::BigInteger
+            | DatabaseType::UnsignedBigInteger => "INTEGER",
+            DatabaseType::Float | DatabaseType::Double => "REAL",
+            DatabaseType::Binary => "BLOB",
+            DatabaseType::Decimal
+            | DatabaseType::Time
+            | DatabaseType::Date
+            | DatabaseType::DateTime
+            | DatabaseType::String
+            | DatabaseType::Json => "TEXT",
+        }
+    }
+
+    // An INTEGER PRIMARY KEY column is an alias of the rowid, it is assigned automatically
+    fn auto_increment(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn parse_column_type(&self, ty: &str) -> Option<DatabaseType> {
+        match ty.to_uppercase().as_str() {
+            "INTEGER" => Some(DatabaseType::BigInteger),
+            "REAL" => Some(DatabaseType::Double),
+            "BLOB" => Some(DatabaseType::Binary),
+            "TEXT" => Some(DatabaseType::String),
+            _ => None,
+        }
+    }
+
+    // There is no information_schema in SQLite, the table-valued pragma functions are used instead
+    fn column_info_query(&self) -> &'static str {
+        "SELECT m.name, p.name, p.type, CASE WHEN p.\"notnull\" = 0 THEN 'YES' ELSE 'NO' END \
+        FROM sqlite_master m JOIN pragma_table_info(m.name) p \
+        WHERE m.type = 'table' AND m.name NOT LIKE 'sqlite_%' ORDER BY m.name, p.cid"
+    }
+
+    fn index_info_query(&self) -> &'static str {
+        "SELECT m.name, l.name, i.name, CASE WHEN l.\"unique\" = 0 THEN 'NO' ELSE 'YES' END \
+        FROM sqlite_master m JOIN pragma_index_list(m.name) l JOIN pragma_index_info(l.name) i \
+        WHERE m.type = 'table' AND l.origin = 'c' ORDER BY m.name, l.name, i.seqno"
+    }
+
+    // ALTER TABLE of SQLite cannot change the definition of a column
+    fn supports_modify_column(&self) -> bool {
+        false
+    }
+
+    // u64 is stored as i64, see `AppendToArgs` below
+    fn check_value(&self, value: &DatabaseValue) -> Result {
+        match value {
+            DatabaseValue::UnsignedBigInteger(i) if i64::try_from(*i).is_err() => Err(Error),
+            _ => Ok(()),
+        }
+    }
+
+    fn bool_literal(&self, value: bool) -> &'static str {
+        if value {
+            "1"
+        } else {
+            "0"
+        }
+    }
+
+    // OFFSET can not be used without LIMIT in SQLite, a negative LIMIT means no limit
+    fn write_limit_offset(
+        &self,
+        limit: Option<usize>,
+        offset: usize,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        match limit {
+            Some(limit) => write!(state, "LIMIT {}", limit),
+            None if offset != 0 => write!(state, "LIMIT -1"),
+            None => Ok(()),
+        }?;
+
+        if offset != 0 {
+            write!(state, "OFFSET {}", offset)?;
+        }
+
+        Ok(())
+    }
+
+    fn convert_normal_aggregate_fn_call(
+        &self,
+        fn_call: &NormalAggregateFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        match &fn_call.function {
+            AggregateFunction::BitAnd | AggregateFunction::BitOr | AggregateFunction::BitXor => {
+                return convert_bit_aggregate_fn_call(fn_call, state);
+            }
+            function => state.write_str(self.aggregate_fn_name(function)),
+        }?;
+        write!(state, "(")?;
+        if let AggregateFunction::CountDistinct = fn_call.function {
+            write!(state, "DISTINCT")?;
+        }
+
+        fn_call.param.to_sql(state)?;
+        write!(state, ")")
+    }
+
+    // SQLite does not support ordered aggregation, `order_by` is ignored.
+    fn convert_group_concat(
+        &self,
+        fn_call: &GroupConcatFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        write!(state, "GROUP_CONCAT(")?;
+        fn_call.expr.to_sql(state)?;
+
+        if let Some(separator) = &fn_call.separator {
+            write!(state, ",")?;
+            let sp = format!("'{}'", separator.replace('\'', "''"));
+            state.write_str(&sp)?;
+        }
+
+        write!(state, ")")
+    }
+
+    // There is no CHAR_LENGTH, but LENGTH counts characters of text. SUBSTRING is only an alias
+    // of SUBSTR since 3.34
+    fn scalar_fn_name(&self, function: &ScalarFunction) -> &'static str {
+        match function {
+            ScalarFunction::JsonExtract => "JSON_EXTRACT",
+            ScalarFunction::Length | ScalarFunction::CharLength => "LENGTH",
+            ScalarFunction::Lower => "LOWER",
+            ScalarFunction::Upper => "UPPER",
+            ScalarFunction::Trim => "TRIM",
+            ScalarFunction::Concat => "CONCAT",
+            ScalarFunction::Substring => "SUBSTR",
+            ScalarFunction::Coalesce => "COALESCE",
+            ScalarFunction::Abs => "ABS",
+            ScalarFunction::Round => "ROUND",
+            ScalarFunction::Floor => "FLOOR",
+            ScalarFunction::Ceil => "CEIL",
+            ScalarFunction::Power => "POWER",
+            ScalarFunction::Sqrt => "SQRT",
+            ScalarFunction::Greatest => "MAX",
+            ScalarFunction::Least => "MIN",
+        }
+    }
+
+    // Math functions other than ABS and ROUND are only built in with SQLITE_ENABLE_MATH_FUNCTIONS,
+    // which the bundled SQLite of sqlx is not. FLOOR and CEIL are emulated by CAST, which
+    // truncates towards zero, POWER and SQRT can not be emulated and are rejected
+    fn convert_scalar_fn_call(
+        &self,
+        fn_call: &ScalarFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        match fn_call.function {
+            ScalarFunction::Concat => write_concat(&fn_call.params, state),
+            ScalarFunction::Floor => write_truncation(&fn_call.params[0], "<", "-", state),
+            ScalarFunction::Ceil => write_truncation(&fn_call.params[0], ">", "+", state),
+            ScalarFunction::Power | ScalarFunction::Sqrt => Err(std::fmt::Error),
+            function => write_fn_call(self.scalar_fn_name(&function), &fn_call.params, state),
+        }
+    }
+
+    // Time values are text in the formats of `AppendToArgs` below, which are kept by the results.
+    // Adding months overflows instead of clamping to the end of the month, e.g. 01-31 => 03-02
+    fn convert_datetime_fn_call(
+        &self,
+        fn_call: &DateTimeFunctionCall,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        let params = &fn_call.params;
+        match fn_call.function {
+            DateTimeFunction::Now => write!(state, "CURRENT_TIMESTAMP"),
+            DateTimeFunction::Extract(unit) => {
+                let format = match unit {
+                    DateTimeUnit::Year => "'%Y'",
+                    DateTimeUnit::Month => "'%m'",
+                    DateTimeUnit::Day => "'%d'",
+                    DateTimeUnit::Hour => "'%H'",
+                    DateTimeUnit::Minute => "'%M'",
+                    DateTimeUnit::Second => "'%S'",
+                };
+                write_strftime(format, &params[0], state)
+            }
+            DateTimeFunction::Truncate(unit) => {
+                let format = match (unit, &fn_call.ty) {
+                    (DateTimeUnit::Year, DatabaseType::Date) => "'%Y-01-01'",
+                    (DateTimeUnit::Month, DatabaseType::Date) => "'%Y-%m-01'",
+                    (_, DatabaseType::Date) => "'%Y-%m-%d'",
+                    (DateTimeUnit::Year, _) => "'%Y-01-01 00:00:00'",
+                    (DateTimeUnit::Month, _) => "'%Y-%m-01 00:00:00'",
+                    (DateTimeUnit::Day, _) => "'%Y-%m-%d 00:00:00'",
+                    (DateTimeUnit::Hour, _) => "'%Y-%m-%d %H:00:00'",
+                    (DateTimeUnit::Minute, _) => "'%Y-%m-%d %H:%M:00'",
+                    (DateTimeUnit::Second, _) => "'%Y-%m-%d %H:%M:%S'",
+                };
+                write_strftime(format, &params[0], state)
+            }
+            DateTimeFunction::AddInterval(unit) => {
+                state.write_str(match fn_call.ty {
+                    DatabaseType::Date => "DATE",
+                    DatabaseType::Time => "TIME",
+                    _ => "DATETIME",
+                })?;
+                write!(state, "(")?;
+                params[0].to_sql(state)?;
+                write!(state, ",")?;
+                write!(state, "(")?;
+                params[1].to_sql(state)?;
+                write!(state, "||")?;
+                state.write_str(match unit {
+                    DateTimeUnit::Year => "' years'",
+                    DateTimeUnit::Month => "' months'",
+                    DateTimeUnit::Day => "' days'",
+                    DateTimeUnit::Hour => "' hours'",
+                    DateTimeUnit::Minute => "' minutes'",
+                    DateTimeUnit::Second => "' seconds'",
+                })?;
+                write!(state, ")")?;
+                write!(state, ")")
+            }
+            DateTimeFunction::DiffInSeconds => {
+                write!(state, "(")?;
+                write_strftime("'%s'", &params[0], state)?;
+                write!(state, "-")?;
+                write_strftime("'%s'", &params[1], state)?;
+                write!(state, ")")
+            }
+        }
+    }
+
+    // ANY and ALL are rewritten in `convert_comparison`, they can not be used alone.
+    fn convert_subquery_fn(
+        &self,
+        _fn_call: &SubqueryFunctionCall,
+        _state: &mut QueryBuildState,
+    ) -> Result {
+        Err(std::fmt::Error)
+    }
+
+    // SQLite has no ANY and ALL, rewrite them to EXISTS:
+    // `l < ANY(s)` => `EXISTS(WITH t(v) AS (s) SELECT 1 FROM t WHERE l < t.v)`
+    // `l < ALL(s)` => `NOT EXISTS(WITH t(v) AS (s) SELECT 1 FROM t WHERE NOT (l < t.v))`
+    fn convert_comparison(
+        &self,
+        l: &Expr,
+        operator: &str,
+        r: &Expr,
+        state: &mut QueryBuildState,
+    ) -> Result {
+        if let Some(fn_call) = subquery_fn_call_of(l) {
+            let column = subquery_column("__subquery_l");
+            write_exists(fn_call, "__subquery_l", state, |s| {
+                self.convert_comparison(&column, operator, r, s)
+            })
+        } else if let Some(fn_call) = subquery_fn_call_of(r) {
+            // `l` is bound before the subquery, render it ahead to take the leading placeholders
+            let mut lhs = state.fork();
+            l.to_sql(&mut lhs)?;
+            state.set_param_count(lhs.param_count());
+
+            let column = subquery_column("__subquery_r");
+            write_exists(fn_call, "__subquery_r", state, |s| {
+                s.merge(lhs);
+                s.write_str(operator)?;
+                column.to_sql(s)
+            })
+        } else {
+            write_comparison(l, operator, r, state)
+        }
+    }
+}
+
+// SQLite has no bitwise aggregate functions, every bit of the param is aggregated separately:
+// BIT_AND takes the MIN of the bit, BIT_OR takes the MAX and BIT_XOR takes the parity of the SUM.
+fn convert_bit_aggregate_fn_call(
+    fn_call: &NormalAggregateFunctionCall,
+    state: &mut QueryBuildState,
+) -> Result {
+    let start = state.param_count();
+    let mut end = start;
+
+    write!(state, "(")?;
+    for bit in 0..64 {
+        if bit != 0 {
+            write!(state, "|")?;
+        }
+
+        // the param is repeated for each bit, but bound once
+        state.set_param_count(start);
+        write!(state, "((")?;
+        match &fn_call.function {
+            AggregateFunction::BitAnd => write!(state, "MIN"),
+            AggregateFunction::BitOr => write!(state, "MAX"),
+            AggregateFunction::BitXor => write!(state, "SUM"),
+            _ => unreachable!(),
+        }?;
+        write!(state, "((")?;
+        fn_call.param.to_sql(state)?;
+        write!(state, ">> {}) & 1)", bit)?;
+        if let AggregateFunction::BitXor = fn_call.function {
+            write!(state, "% 2")?;
+        }
+        write!(state, ") << {})", bit)?;
+        end = state.param_count();
+    }
+    state.set_param_count(end);
+
+    write!(state, ")")
+}
+
+fn subquery_fn_call_of(expr: &Expr) -> Option<&SubqueryFunctionCall> {
+    match expr {
+        Expr::FunctionCall(f) => match f.as_ref() {
+            FunctionCall::Subquery(fn_call) => Some(fn_call),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn subquery_column(table: &str) -> Expr {
+    Expr::Ident(Ident {
+        seg: vec![table.to_string(), SUBQUERY_COLUMN.to_string()],
+    })
+}
+
+fn write_exists(
+    fn_call: &SubqueryFunctionCall,
+    table: &str,
+    state: &mut QueryBuildState,
+    condition: impl FnOnce(&mut QueryBuildState) -> Result,
+) -> Result {
+    write!(state, "(")?;
+    match &fn_call.function {
+        SubqueryFunction::Any => write!(state, "EXISTS"),
+        SubqueryFunction::All => write!(state, "NOT EXISTS"),
+    }?;
+    write!(state, "(")?;
+    write!(state, "WITH")?;
+    state.write_str(&SqliteDialect.quote_ident(table))?;
+    write!(state, "(")?;
+    state.write_str(&SqliteDialect.quote_ident(SUBQUERY_COLUMN))?;
+    write!(state, ") AS (")?;
+    fn_call.subquery.to_sql(state)?;
+    write!(state, ")")?;
+    write!(state, "SELECT 1 FROM")?;
+    state.write_str(&SqliteDialect.quote_ident(table))?;
+    write!(state, "WHERE")?;
+    if let SubqueryFunction::All = fn_call.function {
+        write!(state, "NOT")?;
+    }
+    write!(state, "(")?;
+    condition(state)?;
+    write!(state, ")")?;
+    write!(state, ")")?;
+    write!(state, ")")
+}
+
+// `( CAST(e AS INTEGER) - ( e < CAST(e AS INTEGER) ) )` for FLOOR. `e` is rendered repeatedly with
+// the same placeholders, which is fine since they are numbered
+fn write_truncation(
+    expr: &Expr,
+    comparison: &str,
+    operator: &str,
+    state: &mut QueryBuildState,
+) -> Result {
+    let render = |state: &QueryBuildState| {
+        let mut fork = state.fork();
+        expr.to_sql(&mut fork).map(|_| fork)
+    };
+    let param_count = render(state)?.param_count();
+
+    write!(state, "(")?;
+    write!(state, "CAST(")?;
+    state.merge(render(state)?);
+    write!(state, "AS INTEGER)")?;
+    state.write_str(operator)?;
+    write!(state, "(")?;
+    state.merge(render(state)?);
+    state.write_str(comparison)?;
+    write!(state, "CAST(")?;
+    state.merge(render(state)?);
+    write!(state, "AS INTEGER)")?;
+    write!(state, ")")?;
+    write!(state, ")")?;
+
+    state.set_param_count(param_count);
+    Ok(())
+}
+
+fn write_strftime(format: &str, expr: &Expr, state: &mut QueryBuildState) -> Result {
+    write!(state, "STRFTIME(")?;
+    state.write_str(format)?;
+    write!(state, ",")?;
+    expr.to_sql(state)?;
+    write!(state, ")")
+}
+
+#[cfg(feature = "sqlite")]
+mod database {
+    use std::convert::TryFrom;
+
+    use sqlx::Sqlite;
+
+    use interface::DatabaseType;
+
+    use crate::{AppendToArgs, Binary, DatabaseValue, Dialect, HasDialect, QueryOf};
+    use crate::drivers::{SqliteDialect, null_of};
+
+    impl HasDialect for Sqlite {
+        fn dialect() -> &'static dyn Dialect {
+            &SqliteDialect
+        }
+    }
+
+    // SQLite has no decimal and time types, they are stored as text. u64 is stored as i64, values
+    // out of the range of i64 are rejected by `check_value` when the query is rendered.
+    impl<'q> AppendToArgs<'q, Sqlite> for DatabaseValue {
+        fn bind_on(self, query: QueryOf<'q, Sqlite>) -> QueryOf<'q, Sqlite> {
+            match self {
+                DatabaseValue::Bool(b) => query.bind(b),
+                DatabaseValue::SmallInteger(i) => query.bind(i),
+                DatabaseValue::UnsignedSmallInteger(i) => query.bind(i),
+                DatabaseValue::Integer(i) => query.bind(i),
+                DatabaseValue::UnsignedInteger(i) => query.bind(i),
+                DatabaseValue::BigInteger(i) => query.bind(i),
+                DatabaseValue::UnsignedBigInteger(i) => query.bind(i64::try_from(i).ok()),
+                DatabaseValue::Float(f) => query.bind(f),
+                DatabaseValue::Double(f) => query.bind(f),
+                DatabaseValue::Decimal(f) => query.bind(f.to_string()),
+                DatabaseValue::Binary(b) => query.bind(b),
+                DatabaseValue::Time(t) => query.bind(t.format("%T")),
+                DatabaseValue::Date(t) => query.bind(t.format("%F")),
+                DatabaseValue::DateTime(t) => query.bind(t.format("%F %T")),
+                DatabaseValue::String(s) => query.bind(s),
+                DatabaseValue::Json(j) => query.bind(j.to_string()),
+                DatabaseValue::Invalid(t, _) => DatabaseValue::Null(t).bind_on(query),
+                DatabaseValue::Null(t) => match t {
+                    DatabaseType::Bool => query.bind(null_of::<bool>()),
+                    DatabaseType::SmallInteger => query.bind(null_of::<i16>()),
+                    DatabaseType::UnsignedSmallInteger => query.bind(null_of::<u16>()),
+                    DatabaseType::Integer => query.bind(null_of::<i32>()),
+                    DatabaseType::UnsignedInteger => query.bind(null_of::<u32>()),
+                    DatabaseType::BigInteger => query.bind(null_of::<i64>()),
+                    DatabaseType::UnsignedBigInteger => query.bind(null_of::<i64>()),
+                    DatabaseType::Float => query.bind(null_of::<f32>()),
+                    DatabaseType::Double => query.bind(null_of::<f64>()),
+                    DatabaseType::Binary => query.bind(null_of::<Binary>()),
+                    DatabaseType::Decimal
+                    | DatabaseType::Time
+                    | DatabaseType::Date
+                    | DatabaseType::DateTime
+                    | DatabaseType::String
+                    | DatabaseType::Json => query.bind(null_of::<String>()),
+                },
+            }
+        }
+    }
+}
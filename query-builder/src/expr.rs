@@ -2,19 +2,22 @@ use std::fmt::{Display, Formatter, Result as FmtResult, Write};
 
 use sqlx::Database;
 
+use interface::DatabaseType;
+
 use crate::{
     AppendToArgs, BindArgs, DatabaseValue, FunctionCall, Ident, QueryBuildState, QueryOf,
-    SelectQuery, ToSql,
+    SelectQuery, SelectRef, ToSql,
 };
 
 pub type ExprBox = Box<Expr>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Expr {
     Ident(Ident),
     Lit(DatabaseValue),
     FunctionCall(Box<FunctionCall>),
     Subquery(SelectQuery),
+    Tuple(Vec<Expr>),
     BitInverse(ExprBox),
     BitXor(ExprBox, ExprBox),
     Mul(ExprBox, ExprBox),
@@ -39,6 +42,12 @@ pub enum Expr {
     InArr(ExprBox, Vec<DatabaseValue>),
     Exists(SelectQuery),
     NotExists(SelectQuery),
+    Cast(ExprBox, DatabaseType),
+    Like(ExprBox, ExprBox),
+    IsNull(ExprBox),
+    IsNotNull(ExprBox),
+    Case(Vec<(Expr, Expr)>, ExprBox),
+    SelectRef(SelectRef),
 }
 
 impl Display for Expr {
@@ -48,6 +57,15 @@ impl Display for Expr {
             Expr::Lit(l) => l.fmt(f),
             Expr::FunctionCall(c) => Display::fmt(&c, f),
             Expr::Subquery(s) => write!(f, "({})", s),
+            Expr::Tuple(items) => write!(
+                f,
+                "({})",
+                items
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
             Expr::BitInverse(e) => write!(f, "~{}", e),
             Expr::BitXor(l, r) => write!(f, "{} ^ {}", l, r),
             Expr::Mul(l, r) => write!(f, "{} * {}", l, r),
@@ -80,6 +98,21 @@ impl Display for Expr {
             ),
             Expr::Exists(s) => write!(f, "EXISTS ({})", s),
             Expr::NotExists(s) => write!(f, "NOT EXISTS ({})", s),
+            Expr::Cast(e, ty) => write!(f, "CAST({} AS {})", e, ty),
+            Expr::Like(l, r) => write!(f, "{} LIKE {}", l, r),
+            Expr::IsNull(e) => write!(f, "{} IS NULL", e),
+            Expr::IsNotNull(e) => write!(f, "{} IS NOT NULL", e),
+            Expr::Case(branches, otherwise) => write!(
+                f,
+                "CASE {} ELSE {} END",
+                branches
+                    .iter()
+                    .map(|(c, v)| format!("WHEN {} THEN {}", c, v))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                otherwise
+            ),
+            Expr::SelectRef(r) => write!(f, "{}", r.alias),
         }
     }
 }
@@ -95,6 +128,11 @@ impl ToSql for Expr {
                 query.to_sql(state)?;
                 write!(state, ")")
             }
+            Expr::Tuple(items) => {
+                write!(state, "(")?;
+                state.join(items, |s| write!(s, ","))?;
+                write!(state, ")")
+            }
             Expr::BitInverse(e) => {
                 write!(state, "(")?;
                 write!(state, "~")?;
@@ -171,48 +209,12 @@ impl ToSql for Expr {
                 r.to_sql(state)?;
                 write!(state, ")")
             }
-            Expr::Bte(l, r) => {
-                write!(state, "(")?;
-                l.to_sql(state)?;
-                write!(state, ">=")?;
-                r.to_sql(state)?;
-                write!(state, ")")
-            }
-            Expr::Lte(l, r) => {
-                write!(state, "(")?;
-                l.to_sql(state)?;
-                write!(state, "<=")?;
-                r.to_sql(state)?;
-                write!(state, ")")
-            }
-            Expr::Neq(l, r) => {
-                write!(state, "(")?;
-                l.to_sql(state)?;
-                write!(state, "!=")?;
-                r.to_sql(state)?;
-                write!(state, ")")
-            }
-            Expr::Bt(l, r) => {
-                write!(state, "(")?;
-                l.to_sql(state)?;
-                write!(state, ">")?;
-                r.to_sql(state)?;
-                write!(state, ")")
-            }
-            Expr::Lt(l, r) => {
-                write!(state, "(")?;
-                l.to_sql(state)?;
-                write!(state, "<")?;
-                r.to_sql(state)?;
-                write!(state, ")")
-            }
-            Expr::Eq(l, r) => {
-                write!(state, "(")?;
-                l.to_sql(state)?;
-                write!(state, "=")?;
-                r.to_sql(state)?;
-                write!(state, ")")
-            }
+            Expr::Bte(l, r) => state.dialect().convert_comparison(l, ">=", r, state),
+            Expr::Lte(l, r) => state.dialect().convert_comparison(l, "<=", r, state),
+            Expr::Neq(l, r) => state.dialect().convert_comparison(l, "!=", r, state),
+            Expr::Bt(l, r) => state.dialect().convert_comparison(l, ">", r, state),
+            Expr::Lt(l, r) => state.dialect().convert_comparison(l, "<", r, state),
+            Expr::Eq(l, r) => state.dialect().convert_comparison(l, "=", r, state),
             Expr::Not(e) => {
                 write!(state, "(")?;
                 write!(state, "Not")?;
@@ -242,6 +244,14 @@ impl ToSql for Expr {
                 write!(state, ")")?;
                 write!(state, ")")
             }
+            Expr::InArr(e, arr) if arr.is_empty() => {
+                // `IN ()` is not valid, but `e` still has to be rendered for its arguments
+                write!(state, "(")?;
+                e.to_sql(state)?;
+                write!(state, "IS NULL AND")?;
+                state.append_bool(false)?;
+                write!(state, ")")
+            }
             Expr::InArr(e, arr) => {
                 write!(state, "(")?;
                 e.to_sql(state)?;
@@ -267,6 +277,47 @@ impl ToSql for Expr {
                 write!(state, ")")?;
                 write!(state, ")")
             }
+            Expr::Cast(e, ty) => {
+                write!(state, "CAST(")?;
+                e.to_sql(state)?;
+                write!(state, "AS")?;
+                state.write_str(state.dialect().cast_type(ty))?;
+                write!(state, ")")
+            }
+            Expr::Like(l, r) => {
+                write!(state, "(")?;
+                l.to_sql(state)?;
+                write!(state, "LIKE")?;
+                r.to_sql(state)?;
+                write!(state, "ESCAPE")?;
+                state.write_str(state.dialect().like_escape())?;
+                write!(state, ")")
+            }
+            Expr::IsNull(e) => {
+                write!(state, "(")?;
+                e.to_sql(state)?;
+                write!(state, "IS NULL")?;
+                write!(state, ")")
+            }
+            Expr::IsNotNull(e) => {
+                write!(state, "(")?;
+                e.to_sql(state)?;
+                write!(state, "IS NOT NULL")?;
+                write!(state, ")")
+            }
+            Expr::Case(branches, otherwise) => {
+                write!(state, "CASE")?;
+                for (c, v) in branches {
+                    write!(state, "WHEN")?;
+                    c.to_sql(state)?;
+                    write!(state, "THEN")?;
+                    v.to_sql(state)?;
+                }
+                write!(state, "ELSE")?;
+                otherwise.to_sql(state)?;
+                write!(state, "END")
+            }
+            Expr::SelectRef(r) => r.to_sql(state),
         }
     }
 }
@@ -281,6 +332,7 @@ where
             Expr::Lit(l) => l.bind_args(query),
             Expr::FunctionCall(f) => f.bind_args(query),
             Expr::Subquery(s) => s.bind_args(query),
+            Expr::Tuple(items) => items.bind_args(query),
             Expr::BitInverse(e) => e.bind_args(query),
             Expr::BitXor(l, r) => r.bind_args(l.bind_args(query)),
             Expr::Mul(l, r) => r.bind_args(l.bind_args(query)),
@@ -305,6 +357,16 @@ where
             Expr::InArr(l, a) => a.bind_args(l.bind_args(query)),
             Expr::Exists(s) => s.bind_args(query),
             Expr::NotExists(s) => s.bind_args(query),
+            Expr::Cast(e, _) => e.bind_args(query),
+            Expr::Like(l, r) => r.bind_args(l.bind_args(query)),
+            Expr::IsNull(e) => e.bind_args(query),
+            Expr::IsNotNull(e) => e.bind_args(query),
+            Expr::Case(branches, otherwise) => otherwise.bind_args(
+                branches
+                    .into_iter()
+                    .fold(query, |q, (c, v)| v.bind_args(c.bind_args(q))),
+            ),
+            Expr::SelectRef(_) => query,
         }
     }
 }
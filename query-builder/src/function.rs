@@ -2,13 +2,14 @@ use std::fmt::{Debug, Display, Formatter};
 
 use sqlx::Database;
 
+use interface::DatabaseType;
+
 use crate::{
     AppendToArgs, BindArgs, DatabaseValue, Expr, OrderByItem, QueryBuildState, QueryOf,
     SelectQuery, ToSql,
 };
-use crate::drivers::{convert_group_concat, convert_normal_aggregate_fn_call, convert_subquery_fn};
 
-#[derive(Clone, Debug, Copy)]
+#[derive(Clone, Debug, Copy, PartialEq)]
 pub enum AggregateFunction {
     Average,
     Sum,
@@ -25,7 +26,7 @@ pub enum AggregateFunction {
 unsafe impl Send for AggregateFunction {}
 unsafe impl Sync for AggregateFunction {}
 
-#[derive(Clone, Debug, Copy)]
+#[derive(Clone, Debug, Copy, PartialEq)]
 pub enum SubqueryFunction {
     Any,
     All,
@@ -34,28 +35,97 @@ pub enum SubqueryFunction {
 unsafe impl Send for SubqueryFunction {}
 unsafe impl Sync for SubqueryFunction {}
 
-#[derive(Clone, Debug, Copy)]
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum ScalarFunction {
+    JsonExtract,
+    Length,
+    Lower,
+    Upper,
+    Trim,
+    CharLength,
+    Concat,
+    Substring,
+    Coalesce,
+    Abs,
+    Round,
+    Floor,
+    Ceil,
+    Power,
+    Sqrt,
+    Greatest,
+    Least,
+}
+
+unsafe impl Send for ScalarFunction {}
+unsafe impl Sync for ScalarFunction {}
+
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum DateTimeUnit {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+unsafe impl Send for DateTimeUnit {}
+unsafe impl Sync for DateTimeUnit {}
+
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum DateTimeFunction {
+    Now,
+    Extract(DateTimeUnit),
+    Truncate(DateTimeUnit),
+    AddInterval(DateTimeUnit),
+    DiffInSeconds,
+}
+
+unsafe impl Send for DateTimeFunction {}
+unsafe impl Sync for DateTimeFunction {}
+
+#[derive(Clone, Debug, Copy, PartialEq)]
+pub enum WindowFunction {
+    RowNumber,
+    Rank,
+    DenseRank,
+    Lag,
+    Lead,
+    FirstValue,
+    Aggregate(AggregateFunction),
+}
+
+unsafe impl Send for WindowFunction {}
+unsafe impl Sync for WindowFunction {}
+
+#[derive(Clone, Debug, Copy, PartialEq)]
 pub enum Function {
     Aggregate(AggregateFunction),
     Subquery(SubqueryFunction),
+    Scalar(ScalarFunction),
+    DateTime(DateTimeFunction),
+    Window(WindowFunction),
 }
 
 unsafe impl Send for Function {}
 unsafe impl Sync for Function {}
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum FunctionCall {
     Aggregate(AggregateFunctionCall),
     Subquery(SubqueryFunctionCall),
+    Scalar(ScalarFunctionCall),
+    DateTime(DateTimeFunctionCall),
+    Window(WindowFunctionCall),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum AggregateFunctionCall {
     Normal(NormalAggregateFunctionCall),
     GroupConcat(GroupConcatFunctionCall),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct NormalAggregateFunctionCall {
     pub function: AggregateFunction,
     pub param: Expr,
@@ -64,7 +134,7 @@ pub struct NormalAggregateFunctionCall {
 unsafe impl Send for NormalAggregateFunctionCall {}
 unsafe impl Sync for NormalAggregateFunctionCall {}
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct GroupConcatFunctionCall {
     pub expr: Expr,
     pub order_by: Vec<OrderByItem>,
@@ -74,7 +144,7 @@ pub struct GroupConcatFunctionCall {
 unsafe impl Send for GroupConcatFunctionCall {}
 unsafe impl Sync for GroupConcatFunctionCall {}
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SubqueryFunctionCall {
     pub function: SubqueryFunction,
     pub subquery: SelectQuery,
@@ -83,6 +153,38 @@ pub struct SubqueryFunctionCall {
 unsafe impl Send for SubqueryFunctionCall {}
 unsafe impl Sync for SubqueryFunctionCall {}
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScalarFunctionCall {
+    pub function: ScalarFunction,
+    pub params: Vec<Expr>,
+}
+
+unsafe impl Send for ScalarFunctionCall {}
+unsafe impl Sync for ScalarFunctionCall {}
+
+// `ty` is the type of the first param, which decides the functions to use in some databases
+#[derive(Clone, Debug, PartialEq)]
+pub struct DateTimeFunctionCall {
+    pub function: DateTimeFunction,
+    pub params: Vec<Expr>,
+    pub ty: DatabaseType,
+}
+
+unsafe impl Send for DateTimeFunctionCall {}
+unsafe impl Sync for DateTimeFunctionCall {}
+
+// `function(params) OVER (PARTITION BY partition_by ORDER BY order_by)`
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowFunctionCall {
+    pub function: WindowFunction,
+    pub params: Vec<Expr>,
+    pub partition_by: Vec<Expr>,
+    pub order_by: Vec<OrderByItem>,
+}
+
+unsafe impl Send for WindowFunctionCall {}
+unsafe impl Sync for WindowFunctionCall {}
+
 impl Display for NormalAggregateFunctionCall {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self.function {
@@ -102,7 +204,9 @@ impl Display for NormalAggregateFunctionCall {
 
 impl ToSql for NormalAggregateFunctionCall {
     fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
-        convert_normal_aggregate_fn_call(self, state)
+        state
+            .dialect()
+            .convert_normal_aggregate_fn_call(self, state)
     }
 }
 
@@ -136,7 +240,7 @@ impl Display for GroupConcatFunctionCall {
 
 impl ToSql for GroupConcatFunctionCall {
     fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
-        convert_group_concat(self, state)
+        state.dialect().convert_group_concat(self, state)
     }
 }
 
@@ -157,7 +261,7 @@ impl Display for SubqueryFunctionCall {
 
 impl ToSql for SubqueryFunctionCall {
     fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
-        convert_subquery_fn(self, state)
+        state.dialect().convert_subquery_fn(self, state)
     }
 }
 
@@ -170,6 +274,127 @@ where
     }
 }
 
+impl Display for ScalarFunctionCall {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}({})",
+            self.function,
+            self.params
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+impl ToSql for ScalarFunctionCall {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        state.dialect().convert_scalar_fn_call(self, state)
+    }
+}
+
+impl<'q, DB: Database> BindArgs<'q, DB> for ScalarFunctionCall
+where
+    DatabaseValue: for<'p> AppendToArgs<'p, DB>,
+{
+    fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
+        self.params.bind_args(query)
+    }
+}
+
+impl Display for DateTimeFunctionCall {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<_> = self.params.iter().map(|p| p.to_string()).collect();
+        match self.function {
+            DateTimeFunction::Now => write!(f, "NOW()"),
+            DateTimeFunction::Extract(unit) => write!(f, "EXTRACT({} FROM {})", unit, params[0]),
+            DateTimeFunction::Truncate(unit) => write!(f, "DATE_TRUNC({}, {})", unit, params[0]),
+            DateTimeFunction::AddInterval(unit) => write!(
+                f,
+                "DATE_ADD({}, INTERVAL {} {})",
+                params[0], params[1], unit
+            ),
+            DateTimeFunction::DiffInSeconds => {
+                write!(f, "DIFF_IN_SECONDS({}, {})", params[0], params[1])
+            }
+        }
+    }
+}
+
+impl ToSql for DateTimeFunctionCall {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        state.dialect().convert_datetime_fn_call(self, state)
+    }
+}
+
+impl<'q, DB: Database> BindArgs<'q, DB> for DateTimeFunctionCall
+where
+    DatabaseValue: for<'p> AppendToArgs<'p, DB>,
+{
+    fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
+        self.params.bind_args(query)
+    }
+}
+
+impl Display for WindowFunctionCall {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}({}) OVER (",
+            self.function,
+            self.params
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        if !self.partition_by.is_empty() {
+            write!(
+                f,
+                "PARTITION BY {}",
+                self.partition_by
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
+        if !self.order_by.is_empty() {
+            if !self.partition_by.is_empty() {
+                write!(f, " ")?;
+            }
+            write!(
+                f,
+                "ORDER BY {}",
+                self.order_by
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl ToSql for WindowFunctionCall {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        state.dialect().convert_window_fn_call(self, state)
+    }
+}
+
+impl<'q, DB: Database> BindArgs<'q, DB> for WindowFunctionCall
+where
+    DatabaseValue: for<'p> AppendToArgs<'p, DB>,
+{
+    fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
+        self.order_by
+            .bind_args(self.partition_by.bind_args(self.params.bind_args(query)))
+    }
+}
+
 impl ToSql for AggregateFunctionCall {
     fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
         match self {
@@ -196,6 +421,9 @@ impl ToSql for FunctionCall {
         match self {
             FunctionCall::Aggregate(a) => a.to_sql(state),
             FunctionCall::Subquery(s) => s.to_sql(state),
+            FunctionCall::Scalar(s) => s.to_sql(state),
+            FunctionCall::DateTime(d) => d.to_sql(state),
+            FunctionCall::Window(w) => w.to_sql(state),
         }
     }
 }
@@ -208,6 +436,9 @@ where
         match self {
             FunctionCall::Aggregate(a) => a.bind_args(query),
             FunctionCall::Subquery(s) => s.bind_args(query),
+            FunctionCall::Scalar(s) => s.bind_args(query),
+            FunctionCall::DateTime(d) => d.bind_args(query),
+            FunctionCall::Window(w) => w.bind_args(query),
         }
     }
 }
@@ -224,6 +455,33 @@ impl Display for SubqueryFunction {
     }
 }
 
+impl Display for ScalarFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Display for DateTimeUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Display for DateTimeFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Display for WindowFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFunction::Aggregate(function) => write!(f, "{}", function),
+            function => write!(f, "{:?}", function),
+        }
+    }
+}
+
 impl Display for Function {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self)
@@ -244,6 +502,9 @@ impl Display for FunctionCall {
         match self {
             FunctionCall::Aggregate(a) => write!(f, "{}", a),
             FunctionCall::Subquery(s) => write!(f, "{}", s),
+            FunctionCall::Scalar(s) => write!(f, "{}", s),
+            FunctionCall::DateTime(d) => write!(f, "{}", d),
+            FunctionCall::Window(w) => write!(f, "{}", w),
         }
     }
 }
@@ -9,12 +9,12 @@ pub struct Ident {
     pub seg: Vec<String>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Alias {
     pub name: String,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct AliasedTable {
     pub table: String,
     pub alias: Alias,
@@ -38,6 +38,19 @@ impl Alias {
         Expr::Ident(self.create_ident(column))
     }
 
+    // Row value of the columns, a single column is not wrapped
+    pub fn create_columns_expr(&self, columns: &[&str]) -> Expr {
+        match columns {
+            [column] => self.create_ident_expr(column),
+            _ => Expr::Tuple(
+                columns
+                    .iter()
+                    .map(|column| self.create_ident_expr(column))
+                    .collect(),
+            ),
+        }
+    }
+
     pub fn single_seg_ident(&self) -> Ident {
         Ident {
             seg: vec![self.name.clone()],
@@ -77,7 +90,7 @@ impl ToSql for Ident {
     fn to_sql(&self, state: &mut QueryBuildState) -> FmtResult {
         let last_index = self.seg.len() - 1;
         for (index, item) in self.seg.iter().enumerate() {
-            let ident_seg = format!("`{}`", item);
+            let ident_seg = state.dialect().quote_ident(item);
             state.write_str(&ident_seg)?;
             if index != last_index {
                 write!(state, ".")?;
@@ -107,7 +120,7 @@ impl ToSql for AliasedTable {
 
 impl ToSql for Alias {
     fn to_sql(&self, state: &mut QueryBuildState) -> FmtResult {
-        let alias = format!("`{}`", self.name);
+        let alias = state.dialect().quote_ident(&self.name);
         state.write_str(&alias)
     }
 }
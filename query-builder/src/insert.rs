@@ -4,16 +4,31 @@ use std::marker::PhantomData;
 use sqlx::Database;
 
 use crate::{
-    AppendToArgs, ArgSourceList, BindArgs, DatabaseValue, QueryBuildState, QueryOf, ToSql,
-    YukinoQuery,
+    Alias, AppendToArgs, ArgSourceList, AssignmentItem, BindArgs, DatabaseValue, QueryBuildState,
+    QueryOf, SelectItem, ToSql, YukinoQuery,
 };
 
 pub struct Insert;
 
+pub enum OnConflict {
+    DoNothing {
+        target: Vec<String>,
+    },
+    DoUpdate {
+        target: Vec<String>,
+        assignments: Vec<AssignmentItem>,
+    },
+}
+
+unsafe impl Send for OnConflict {}
+unsafe impl Sync for OnConflict {}
+
 pub struct InsertQuery<DB: Database, S: for<'q> ArgSourceList<'q, DB>> {
     table: String,
     columns: Vec<String>,
     values: S,
+    on_conflict: Option<OnConflict>,
+    returning: Vec<SelectItem>,
     _db: PhantomData<DB>,
 }
 
@@ -30,17 +45,86 @@ impl Insert {
             table,
             columns,
             values,
+            on_conflict: None,
+            returning: vec![],
             _db: Default::default(),
         }
     }
 }
 
+impl OnConflict {
+    // Alias of the row proposed for insertion, usable in the assignments of `DoUpdate`
+    pub fn excluded() -> Alias {
+        Alias {
+            name: "excluded".to_string(),
+        }
+    }
+
+    pub fn target(&self) -> &[String] {
+        match self {
+            OnConflict::DoNothing { target } | OnConflict::DoUpdate { target, .. } => target,
+        }
+    }
+}
+
+impl<DB: Database, S: for<'q> ArgSourceList<'q, DB>> InsertQuery<DB, S> {
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    pub fn values(&self) -> &S {
+        &self.values
+    }
+
+    pub fn conflict(&self) -> Option<&OnConflict> {
+        self.on_conflict.as_ref()
+    }
+
+    pub fn on_conflict(&mut self, on_conflict: OnConflict) -> &mut Self {
+        self.on_conflict = Some(on_conflict);
+
+        self
+    }
+
+    pub fn returning(&mut self, items: Vec<SelectItem>) -> &mut Self {
+        self.returning.extend(items);
+
+        self
+    }
+}
+
+impl<'q, DB: Database> BindArgs<'q, DB> for OnConflict
+where
+    DatabaseValue: for<'p> AppendToArgs<'p, DB>,
+{
+    fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
+        match self {
+            OnConflict::DoNothing { .. } => query,
+            OnConflict::DoUpdate { assignments, .. } => assignments.bind_args(query),
+        }
+    }
+}
+
 impl<DB: Database, S: for<'q> ArgSourceList<'q, DB>> ToSql for InsertQuery<DB, S> {
     fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
         write!(state, "INSERT INTO {} (", self.table)?;
         state.join_by(&self.columns, |s, c| write!(s, "{}", c), |s| write!(s, ","))?;
 
-        write!(state, ") VALUES {} ;", self.values.query_part())
+        write!(state, ") VALUES")?;
+        self.values.query_part(state)?;
+
+        if let Some(on_conflict) = &self.on_conflict {
+            state
+                .dialect()
+                .write_on_conflict(&self.columns, on_conflict, state)?;
+        }
+
+        if !self.returning.is_empty() {
+            write!(state, "RETURNING")?;
+            state.join(&self.returning, |s| write!(s, ","))?;
+        }
+
+        write!(state, ";")
     }
 }
 
@@ -49,7 +133,8 @@ where
     DatabaseValue: for<'p> AppendToArgs<'p, DB>,
 {
     fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
-        self.values.bind_args(query)
+        self.returning
+            .bind_args(self.on_conflict.bind_args(self.values.bind_args(query)))
     }
 }
 
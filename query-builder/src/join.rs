@@ -6,7 +6,7 @@ use crate::{
     AliasedTable, AppendToArgs, BindArgs, DatabaseValue, Expr, QueryBuildState, QueryOf, ToSql,
 };
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum JoinType {
     InnerJoin,
     LeftJoin,
@@ -16,7 +16,7 @@ pub enum JoinType {
 unsafe impl Send for JoinType {}
 unsafe impl Sync for JoinType {}
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Join {
     pub ty: JoinType,
     pub table: AliasedTable,
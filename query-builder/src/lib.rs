@@ -1,7 +1,10 @@
 #![feature(vec_into_raw_parts)]
 
 pub use backend::*;
+pub use compound::*;
+pub use cte::*;
 pub use delete::*;
+pub use drivers::*;
 pub use err::*;
 pub use expr::*;
 pub use function::*;
@@ -9,11 +12,14 @@ pub use ident::*;
 pub use insert::*;
 pub use join::*;
 pub use query::*;
+pub use schema::*;
 pub use select::*;
 pub use update::*;
 pub use value::*;
 
 mod backend;
+mod compound;
+mod cte;
 mod delete;
 mod drivers;
 mod err;
@@ -23,6 +29,7 @@ mod ident;
 mod insert;
 mod join;
 mod query;
+mod schema;
 mod select;
 mod update;
 mod value;
@@ -0,0 +1,390 @@
+use std::fmt::{Display, Formatter, Write};
+
+use sqlx::Database;
+
+use interface::{ColumnDefinition, EntityDefinition, IndexDefinition};
+
+use crate::{AppendToArgs, BindArgs, DatabaseValue, QueryBuildState, QueryOf, ToSql, YukinoQuery};
+
+pub struct CreateTable;
+
+pub struct DropTable;
+
+pub struct AlterTable;
+
+pub struct CreateIndex;
+
+pub struct DropIndex;
+
+#[derive(Clone, Debug)]
+pub struct ForeignKey {
+    pub columns: Vec<String>,
+    pub referenced_table: String,
+    pub referenced_columns: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateTableQuery {
+    table: String,
+    columns: Vec<ColumnDefinition>,
+    primary_key: Vec<String>,
+    foreign_keys: Vec<ForeignKey>,
+    if_not_exists: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct DropTableQuery {
+    table: String,
+    if_exists: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum AlterTableAction {
+    AddColumn(ColumnDefinition),
+    ModifyColumn(ColumnDefinition),
+    DropColumn(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct AlterTableQuery {
+    table: String,
+    action: AlterTableAction,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateIndexQuery {
+    table: String,
+    index: IndexDefinition,
+}
+
+#[derive(Clone, Debug)]
+pub struct DropIndexQuery {
+    table: String,
+    name: String,
+}
+
+unsafe impl Send for CreateTableQuery {}
+unsafe impl Sync for CreateTableQuery {}
+unsafe impl Send for DropTableQuery {}
+unsafe impl Sync for DropTableQuery {}
+unsafe impl Send for AlterTableQuery {}
+unsafe impl Sync for AlterTableQuery {}
+unsafe impl Send for CreateIndexQuery {}
+unsafe impl Sync for CreateIndexQuery {}
+unsafe impl Send for DropIndexQuery {}
+unsafe impl Sync for DropIndexQuery {}
+
+impl CreateTable {
+    pub fn from_definition(definition: &EntityDefinition) -> CreateTableQuery {
+        CreateTableQuery {
+            table: definition.table_name.clone(),
+            columns: definition.columns().cloned().collect(),
+            primary_key: definition
+                .primary_key_columns()
+                .map(|c| c.name.clone())
+                .collect(),
+            foreign_keys: definition
+                .associations
+                .iter()
+                .map(|a| ForeignKey {
+                    columns: a.column_names.clone(),
+                    referenced_table: a.referenced_table_name.clone(),
+                    referenced_columns: a.referenced_column_names.clone(),
+                })
+                .collect(),
+            if_not_exists: false,
+        }
+    }
+}
+
+impl DropTable {
+    pub fn named(table: String) -> DropTableQuery {
+        DropTableQuery {
+            table,
+            if_exists: false,
+        }
+    }
+
+    pub fn from_definition(definition: &EntityDefinition) -> DropTableQuery {
+        Self::named(definition.table_name.clone())
+    }
+}
+
+impl AlterTable {
+    pub fn add_column(table: String, column: ColumnDefinition) -> AlterTableQuery {
+        AlterTableQuery {
+            table,
+            action: AlterTableAction::AddColumn(column),
+        }
+    }
+
+    pub fn modify_column(table: String, column: ColumnDefinition) -> AlterTableQuery {
+        AlterTableQuery {
+            table,
+            action: AlterTableAction::ModifyColumn(column),
+        }
+    }
+
+    pub fn drop_column(table: String, column: String) -> AlterTableQuery {
+        AlterTableQuery {
+            table,
+            action: AlterTableAction::DropColumn(column),
+        }
+    }
+}
+
+impl CreateIndex {
+    pub fn from_definition(table: String, index: IndexDefinition) -> CreateIndexQuery {
+        CreateIndexQuery { table, index }
+    }
+}
+
+impl DropIndex {
+    pub fn named(table: String, name: String) -> DropIndexQuery {
+        DropIndexQuery { table, name }
+    }
+}
+
+impl CreateTableQuery {
+    pub fn if_not_exists(&mut self) -> &mut Self {
+        self.if_not_exists = true;
+
+        self
+    }
+}
+
+impl DropTableQuery {
+    pub fn if_exists(&mut self) -> &mut Self {
+        self.if_exists = true;
+
+        self
+    }
+}
+
+impl ToSql for ColumnDefinition {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        let dialect = state.dialect();
+        state.write_str(&dialect.quote_ident(&self.name))?;
+        state.write_str(dialect.column_type(&self.ty))?;
+
+        if !self.optional {
+            write!(state, "NOT NULL")?;
+        }
+
+        match dialect.auto_increment() {
+            Some(auto_increment) if self.auto_increment => state.write_str(auto_increment),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl ToSql for ForeignKey {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        let dialect = state.dialect();
+        write!(state, "FOREIGN KEY (")?;
+        state.join_by(
+            &self.columns,
+            |s, c| s.write_str(&dialect.quote_ident(c)),
+            |s| write!(s, ","),
+        )?;
+        write!(state, ") REFERENCES")?;
+        state.write_str(&dialect.quote_ident(&self.referenced_table))?;
+        write!(state, "(")?;
+        state.join_by(
+            &self.referenced_columns,
+            |s, c| s.write_str(&dialect.quote_ident(c)),
+            |s| write!(s, ","),
+        )?;
+        write!(state, ")")
+    }
+}
+
+impl ToSql for CreateTableQuery {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        let dialect = state.dialect();
+        write!(state, "CREATE TABLE")?;
+        if self.if_not_exists {
+            write!(state, "IF NOT EXISTS")?;
+        }
+        state.write_str(&dialect.quote_ident(&self.table))?;
+        write!(state, "(")?;
+
+        state.join(&self.columns, |s| write!(s, ","))?;
+
+        if !self.primary_key.is_empty() {
+            write!(state, ", PRIMARY KEY (")?;
+            state.join_by(
+                &self.primary_key,
+                |s, c| s.write_str(&dialect.quote_ident(c)),
+                |s| write!(s, ","),
+            )?;
+            write!(state, ")")?;
+        }
+
+        for foreign_key in &self.foreign_keys {
+            write!(state, ",")?;
+            foreign_key.to_sql(state)?;
+        }
+
+        write!(state, ");")
+    }
+}
+
+impl ToSql for DropTableQuery {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        write!(state, "DROP TABLE")?;
+        if self.if_exists {
+            write!(state, "IF EXISTS")?;
+        }
+        let table = state.dialect().quote_ident(&self.table);
+        state.write_str(&table)?;
+        write!(state, ";")
+    }
+}
+
+impl ToSql for AlterTableQuery {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        let dialect = state.dialect();
+        write!(state, "ALTER TABLE")?;
+        state.write_str(&dialect.quote_ident(&self.table))?;
+
+        match &self.action {
+            AlterTableAction::AddColumn(column) => {
+                write!(state, "ADD COLUMN")?;
+                column.to_sql(state)
+            }
+            AlterTableAction::ModifyColumn(column) => dialect.write_modify_column(column, state),
+            AlterTableAction::DropColumn(column) => {
+                write!(state, "DROP COLUMN")?;
+                state.write_str(&dialect.quote_ident(column))
+            }
+        }?;
+
+        write!(state, ";")
+    }
+}
+
+impl ToSql for CreateIndexQuery {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        let dialect = state.dialect();
+        write!(state, "CREATE")?;
+        if self.index.unique {
+            write!(state, "UNIQUE")?;
+        }
+        write!(state, "INDEX")?;
+        state.write_str(&dialect.quote_ident(&self.index.name))?;
+        write!(state, "ON")?;
+        state.write_str(&dialect.quote_ident(&self.table))?;
+        write!(state, "(")?;
+        state.join_by(
+            &self.index.columns,
+            |s, c| s.write_str(&dialect.quote_ident(c)),
+            |s| write!(s, ","),
+        )?;
+        write!(state, ");")
+    }
+}
+
+impl ToSql for DropIndexQuery {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        state
+            .dialect()
+            .write_drop_index(&self.table, &self.name, state)?;
+        write!(state, ";")
+    }
+}
+
+impl<'q, DB: Database> BindArgs<'q, DB> for CreateTableQuery {
+    fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
+        query
+    }
+}
+
+impl<'q, DB: Database> BindArgs<'q, DB> for DropTableQuery {
+    fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
+        query
+    }
+}
+
+impl<'q, DB: Database> BindArgs<'q, DB> for AlterTableQuery {
+    fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
+        query
+    }
+}
+
+impl<'q, DB: Database> BindArgs<'q, DB> for CreateIndexQuery {
+    fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
+        query
+    }
+}
+
+impl<'q, DB: Database> BindArgs<'q, DB> for DropIndexQuery {
+    fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
+        query
+    }
+}
+
+impl Display for CreateTableQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut state = QueryBuildState::default();
+        self.to_sql(&mut state)?;
+        Display::fmt(state.to_string().as_str(), f)
+    }
+}
+
+impl Display for DropTableQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut state = QueryBuildState::default();
+        self.to_sql(&mut state)?;
+        Display::fmt(state.to_string().as_str(), f)
+    }
+}
+
+impl Display for AlterTableQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut state = QueryBuildState::default();
+        self.to_sql(&mut state)?;
+        Display::fmt(state.to_string().as_str(), f)
+    }
+}
+
+impl Display for CreateIndexQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut state = QueryBuildState::default();
+        self.to_sql(&mut state)?;
+        Display::fmt(state.to_string().as_str(), f)
+    }
+}
+
+impl Display for DropIndexQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut state = QueryBuildState::default();
+        self.to_sql(&mut state)?;
+        Display::fmt(state.to_string().as_str(), f)
+    }
+}
+
+impl<DB: Database> YukinoQuery<DB> for CreateTableQuery where
+    DatabaseValue: for<'q> AppendToArgs<'q, DB>
+{
+}
+
+impl<DB: Database> YukinoQuery<DB> for DropTableQuery where
+    DatabaseValue: for<'q> AppendToArgs<'q, DB>
+{
+}
+
+impl<DB: Database> YukinoQuery<DB> for AlterTableQuery where
+    DatabaseValue: for<'q> AppendToArgs<'q, DB>
+{
+}
+
+impl<DB: Database> YukinoQuery<DB> for CreateIndexQuery where
+    DatabaseValue: for<'q> AppendToArgs<'q, DB>
+{
+}
+
+impl<DB: Database> YukinoQuery<DB> for DropIndexQuery where
+    DatabaseValue: for<'q> AppendToArgs<'q, DB>
+{
+}
@@ -3,8 +3,9 @@ use std::fmt::{Debug, Display, Formatter, Write};
 use sqlx::Database;
 
 use crate::{
-    Alias, AliasedTable, AppendToArgs, BindArgs, DatabaseValue, Delete, Expr, Join,
-    QueryBuildState, QueryOf, ToSql, Update, UpdateQuery, YukinoQuery,
+    Alias, AliasedTable, AppendToArgs, BindArgs, CommonTableExpr, CompoundSelect, DatabaseValue,
+    Delete, Expr, ExprBox, FunctionCall, Join, QueryBuildState, QueryOf, ToSql, Update,
+    UpdateQuery, YukinoQuery, write_with_clause,
 };
 use crate::delete::DeleteQuery;
 
@@ -17,21 +18,41 @@ pub enum Order {
 #[derive(Clone, Debug)]
 pub struct Select;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SelectFrom {
     table: AliasedTable,
     join: Vec<Join>,
     where_clauses: Vec<Expr>,
+    // CTEs referenced by the source, they belong to the query selecting from it
+    with: Vec<CommonTableExpr>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct GroupSelect {
     base: SelectFrom,
-    group_by: Vec<Expr>,
+    group_by: Vec<GroupByItem>,
     having: Vec<Expr>,
 }
 
-#[derive(Clone, Debug)]
+// Grouped expressions which are also selected are referred by their position in the select list,
+// so that their arguments are not bound twice with different placeholders
+#[derive(Clone, Debug, PartialEq)]
+enum GroupByItem {
+    Expr(ExprBox),
+    Position(usize),
+}
+
+// A selected expression used again in HAVING or ORDER BY of a grouped query. It is written with the
+// placeholders of the select list if the dialect numbers them, otherwise by its alias, so the
+// database sees the grouped expression and its arguments are only bound by the select list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectRef {
+    pub index: usize,
+    pub alias: String,
+    pub expr: ExprBox,
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct SelectQuery {
     base: SelectSource,
     select: Vec<SelectItem>,
@@ -40,22 +61,23 @@ pub struct SelectQuery {
     offset: usize,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SelectItem {
     pub expr: Expr,
     pub alias: Option<String>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct OrderByItem {
     pub expr: Expr,
     pub order: Order,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum SelectSource {
     From(SelectFrom),
     Group(GroupSelect),
+    Compound(Box<CompoundSelect>),
 }
 
 pub trait IntoSelectSource {
@@ -136,6 +158,15 @@ impl IntoSelectSource for GroupSelect {
     }
 }
 
+impl IntoSelectSource for CompoundSelect {
+    fn source(self) -> SelectSource
+    where
+        Self: Sized,
+    {
+        SelectSource::Compound(Box::new(self))
+    }
+}
+
 impl Select {
     pub fn from(table: String, alias: Alias) -> SelectFrom {
         SelectFrom::create(table, alias)
@@ -151,8 +182,15 @@ impl SelectFrom {
             },
             join: vec![],
             where_clauses: vec![],
+            with: vec![],
         }
     }
+
+    pub fn with(&mut self, cte: CommonTableExpr) -> &mut Self {
+        self.with.push(cte);
+
+        self
+    }
     pub fn and_where(&mut self, expr: Expr) -> &mut Self {
         self.where_clauses.push(expr);
 
@@ -168,7 +206,10 @@ impl SelectFrom {
     pub fn group_by(self, columns: Vec<Expr>) -> GroupSelect {
         GroupSelect {
             base: self,
-            group_by: columns,
+            group_by: columns
+                .into_iter()
+                .map(|c| GroupByItem::Expr(Box::new(c)))
+                .collect(),
             having: vec![],
         }
     }
@@ -180,6 +221,97 @@ impl GroupSelect {
 
         self
     }
+
+    fn refer_select_positions(&mut self, select: &[SelectItem]) {
+        for item in self.group_by.iter_mut() {
+            if let GroupByItem::Expr(expr) = item {
+                if let Some(index) = select.iter().position(|s| &s.expr == expr.as_ref()) {
+                    *item = GroupByItem::Position(index + 1);
+                }
+            }
+        }
+
+        for expr in self.having.iter_mut() {
+            refer_selected(expr, select);
+        }
+    }
+}
+
+// Replaces selected expressions in `expr` by references to the select list. Arguments of
+// aggregates, windows and subqueries are evaluated on rows instead of groups and are left as is.
+fn refer_selected(expr: &mut Expr, select: &[SelectItem]) {
+    let selected = select
+        .iter()
+        .enumerate()
+        .find_map(|(index, item)| match &item.alias {
+            Some(alias) if &item.expr == expr => Some(SelectRef {
+                index,
+                alias: alias.clone(),
+                expr: Box::new(item.expr.clone()),
+            }),
+            _ => None,
+        });
+    if let Some(selected) = selected {
+        *expr = Expr::SelectRef(selected);
+        return;
+    }
+
+    match expr {
+        Expr::Tuple(items) => refer_all_selected(items, select),
+        Expr::BitInverse(e)
+        | Expr::Not(e)
+        | Expr::Cast(e, _)
+        | Expr::IsNull(e)
+        | Expr::IsNotNull(e)
+        | Expr::In(e, _)
+        | Expr::InArr(e, _) => refer_selected(e, select),
+        Expr::BitXor(l, r)
+        | Expr::Mul(l, r)
+        | Expr::Div(l, r)
+        | Expr::Rem(l, r)
+        | Expr::Add(l, r)
+        | Expr::Sub(l, r)
+        | Expr::LeftShift(l, r)
+        | Expr::RightShift(l, r)
+        | Expr::BitAnd(l, r)
+        | Expr::BitOr(l, r)
+        | Expr::Bte(l, r)
+        | Expr::Lte(l, r)
+        | Expr::Neq(l, r)
+        | Expr::Bt(l, r)
+        | Expr::Lt(l, r)
+        | Expr::Eq(l, r)
+        | Expr::And(l, r)
+        | Expr::Or(l, r)
+        | Expr::Like(l, r) => {
+            refer_selected(l, select);
+            refer_selected(r, select);
+        }
+        Expr::FunctionCall(call) => match call.as_mut() {
+            FunctionCall::Scalar(call) => refer_all_selected(&mut call.params, select),
+            FunctionCall::DateTime(call) => refer_all_selected(&mut call.params, select),
+            FunctionCall::Aggregate(_) | FunctionCall::Subquery(_) | FunctionCall::Window(_) => {}
+        },
+        Expr::Case(branches, otherwise) => {
+            for (condition, value) in branches.iter_mut() {
+                refer_selected(condition, select);
+                refer_selected(value, select);
+            }
+            refer_selected(otherwise, select);
+        }
+        Expr::Ident(_)
+        | Expr::Lit(_)
+        | Expr::Subquery(_)
+        | Expr::Exists(_)
+        | Expr::NotExists(_)
+        | Expr::SelectRef(_) => {}
+    }
+}
+
+fn refer_all_selected(exprs: &mut [Expr], select: &[SelectItem]) {
+    for expr in exprs.iter_mut() {
+        refer_selected(expr, select);
+    }
 }
 
 unsafe impl Send for SelectFrom {}
@@ -188,6 +320,24 @@ unsafe impl Sync for SelectFrom {}
 unsafe impl Send for GroupSelect {}
 unsafe impl Sync for GroupSelect {}
 
+impl SelectSource {
+    pub fn ctes(&self) -> &[CommonTableExpr] {
+        match self {
+            SelectSource::From(from) => &from.with,
+            SelectSource::Group(group) => &group.base.with,
+            SelectSource::Compound(_) => &[],
+        }
+    }
+
+    fn take_ctes(&mut self) -> Vec<CommonTableExpr> {
+        match self {
+            SelectSource::From(from) => std::mem::take(&mut from.with),
+            SelectSource::Group(group) => std::mem::take(&mut group.base.with),
+            SelectSource::Compound(_) => vec![],
+        }
+    }
+}
+
 impl SelectQuery {
     pub fn create(
         base: SelectSource,
@@ -196,22 +346,37 @@ impl SelectQuery {
         limit: Option<usize>,
         offset: usize,
     ) -> Self {
-        SelectQuery {
+        let mut query = SelectQuery {
             base,
             select,
             order_by,
             limit,
             offset,
-        }
+        };
+        query.refer_select_positions();
+
+        query
     }
     pub fn append_select(&mut self, items: Vec<SelectItem>) -> &mut Self {
         self.select.extend(items);
+        self.refer_select_positions();
 
         self
     }
 
+    fn refer_select_positions(&mut self) {
+        if let SelectSource::Group(group) = &mut self.base {
+            group.refer_select_positions(&self.select);
+
+            for item in self.order_by.iter_mut() {
+                refer_selected(&mut item.expr, &self.select);
+            }
+        }
+    }
+
     pub fn append_order_by(&mut self, items: Vec<OrderByItem>) -> &mut Self {
         self.order_by.extend(items);
+        self.refer_select_positions();
 
         self
     }
@@ -227,6 +392,14 @@ impl SelectQuery {
 
         self
     }
+
+    // Whether clauses of the query only apply to itself when it is an operand of compound selects
+    pub(crate) fn is_simple(&self) -> bool {
+        self.base.ctes().is_empty()
+            && self.order_by.is_empty()
+            && self.limit.is_none()
+            && self.offset == 0
+    }
 }
 
 impl Display for Order {
@@ -280,6 +453,15 @@ impl Display for SelectFrom {
     }
 }
 
+impl Display for GroupByItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GroupByItem::Expr(expr) => write!(f, "{}", expr),
+            GroupByItem::Position(position) => write!(f, "{}", position),
+        }
+    }
+}
+
 impl Display for GroupSelect {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let group_by_clauses = if self.group_by.is_empty() {
@@ -335,6 +517,19 @@ impl Display for SelectQuery {
             .limit
             .map(|l| format!("LIMIT {}", l))
             .unwrap_or_default();
+        let ctes = self.base.ctes();
+        if !ctes.is_empty() {
+            let recursive = ctes.iter().any(|cte| cte.recursive.is_some());
+            write!(
+                f,
+                "WITH {}{} ",
+                if recursive { "RECURSIVE " } else { "" },
+                ctes.iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
         write!(
             f,
             "SELECT {} {} {} {} OFFSET {}",
@@ -348,6 +543,7 @@ impl Display for SelectSource {
         match self {
             SelectSource::From(from) => write!(f, "{}", from),
             SelectSource::Group(group) => write!(f, "{}", group),
+            SelectSource::Compound(compound) => write!(f, "{}", compound),
         }
     }
 }
@@ -400,6 +596,7 @@ impl ToSql for SelectSource {
         match self {
             SelectSource::From(from) => from.to_sql(state),
             SelectSource::Group(group) => group.to_sql(state),
+            SelectSource::Compound(compound) => compound.to_sql(state),
         }
     }
 }
@@ -412,6 +609,7 @@ where
         match self {
             SelectSource::From(from) => from.bind_args(query),
             SelectSource::Group(group) => group.bind_args(query),
+            SelectSource::Compound(compound) => compound.bind_args(query),
         }
     }
 }
@@ -425,6 +623,27 @@ where
     }
 }
 
+impl ToSql for GroupByItem {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        match self {
+            GroupByItem::Expr(expr) => expr.to_sql(state),
+            GroupByItem::Position(position) => write!(state, "{}", position),
+        }
+    }
+}
+
+impl<'q, DB: Database> BindArgs<'q, DB> for GroupByItem
+where
+    DatabaseValue: for<'p> AppendToArgs<'p, DB>,
+{
+    fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
+        match self {
+            GroupByItem::Expr(expr) => expr.bind_args(query),
+            GroupByItem::Position(_) => query,
+        }
+    }
+}
+
 impl ToSql for GroupSelect {
     fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
         self.base.to_sql(state)?;
@@ -453,12 +672,33 @@ where
     }
 }
 
+impl ToSql for SelectRef {
+    fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        match state.select_param_offset(self.index) {
+            Some(offset) if state.dialect().numbered_placeholders() => {
+                let param_count = state.param_count();
+                state.set_param_count(offset);
+                self.expr.to_sql(state)?;
+                state.set_param_count(param_count);
+
+                Ok(())
+            }
+            _ => {
+                let alias = state.dialect().quote_ident(&self.alias);
+                state.write_str(&alias)
+            }
+        }
+    }
+}
+
 impl ToSql for SelectItem {
     fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
         self.expr.to_sql(state)?;
 
         if let Some(alias) = &self.alias {
-            write!(state, "AS {}", alias)?;
+            write!(state, "AS")?;
+            let alias = state.dialect().quote_ident(alias);
+            state.write_str(&alias)?;
         }
 
         Ok(())
@@ -476,9 +716,19 @@ where
 
 impl ToSql for SelectQuery {
     fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        write_with_clause(self.base.ctes(), state)?;
+
         write!(state, "SELECT")?;
 
-        state.join(&self.select, |s| write!(s, ","))?;
+        let mut param_offsets = Vec::with_capacity(self.select.len());
+        for (index, item) in self.select.iter().enumerate() {
+            if index != 0 {
+                write!(state, ",")?;
+            }
+            param_offsets.push(state.param_count());
+            item.to_sql(state)?;
+        }
+        let outer_offsets = state.replace_select_param_offsets(param_offsets);
 
         self.base.to_sql(state)?;
 
@@ -488,13 +738,10 @@ impl ToSql for SelectQuery {
             state.join(&self.order_by, |s| write!(s, ","))?;
         }
 
-        if let Some(limit) = &self.limit {
-            write!(state, "LIMIT {}", limit)?;
-        }
-
-        if self.offset != 0 {
-            write!(state, "OFFSET {}", self.offset)?;
-        }
+        state
+            .dialect()
+            .write_limit_offset(self.limit, self.offset, state)?;
+        state.replace_select_param_offsets(outer_offsets);
 
         Ok(())
     }
@@ -504,8 +751,10 @@ impl<'q, DB: Database> BindArgs<'q, DB> for SelectQuery
 where
     DatabaseValue: for<'p> AppendToArgs<'p, DB>,
 {
-    fn bind_args(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
-        let after_select = self.select.bind_args(query);
+    fn bind_args(mut self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
+        let after_with = self.base.take_ctes().bind_args(query);
+
+        let after_select = self.select.bind_args(after_with);
 
         let after_source = self.base.bind_args(after_select);
         self.order_by.bind_args(after_source)
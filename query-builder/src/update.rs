@@ -102,7 +102,7 @@ where
 
 impl ToSql for AssignmentItem {
     fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
-        let column = format!("`{}`", self.column);
+        let column = state.dialect().quote_ident(&self.column);
         write!(state, "{}=", column)?;
         self.value.to_sql(state)
     }
@@ -135,11 +135,7 @@ impl ToSql for UpdateQuery {
             state.join(&self.order_by, |s| write!(s, ","))?;
         }
 
-        if let Some(l) = self.limit {
-            write!(state, "LIMIT {}", l)?;
-        }
-
-        Ok(())
+        state.dialect().write_limit_offset(self.limit, 0, state)
     }
 }
 
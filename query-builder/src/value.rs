@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
 use serde_json::Value;
-use sqlx::{Database, Encode, Type};
+use sqlx::Database;
 use sqlx::database::HasValueRef;
 use sqlx::types::Decimal;
 use sqlx::types::time::{Date, PrimitiveDateTime, Time};
@@ -15,7 +15,7 @@ pub type Binary = Vec<u8>;
 
 pub type ValuePack = HashMap<String, DatabaseValue>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum DatabaseValue {
     Bool(bool),
     SmallInteger(i16),
@@ -39,6 +39,9 @@ pub enum DatabaseValue {
 
     Json(Value),
     Null(DatabaseType),
+    // Value failed to convert to the database type, the reason is kept for reporting, queries
+    // binding it are rejected when rendered
+    Invalid(DatabaseType, String),
 }
 
 impl Default for DatabaseValue {
@@ -71,6 +74,7 @@ impl Display for DatabaseValue {
             DatabaseValue::String(v) => write!(f, "\"{}\"", v),
             DatabaseValue::Json(v) => v.fmt(f),
             DatabaseValue::Null(_) => write!(f, "NULL"),
+            DatabaseValue::Invalid(_, reason) => write!(f, "INVALID({})", reason),
         }
     }
 }
@@ -96,13 +100,17 @@ impl From<&DatabaseValue> for DatabaseType {
             DatabaseValue::DateTime(_) => DatabaseType::DateTime,
             DatabaseValue::String(_) => DatabaseType::String,
             DatabaseValue::Json(_) => DatabaseType::Json,
-            DatabaseValue::Null(ty) => *ty,
+            DatabaseValue::Null(ty) | DatabaseValue::Invalid(ty, _) => *ty,
         }
     }
 }
 
 impl ToSql for DatabaseValue {
     fn to_sql(&self, state: &mut QueryBuildState) -> std::fmt::Result {
+        if let DatabaseValue::Invalid(..) = self {
+            return Err(std::fmt::Error);
+        }
+        state.dialect().check_value(self)?;
         state.append_param()
     }
 }
@@ -116,86 +124,6 @@ where
     }
 }
 
-impl<'q, DB: Database> AppendToArgs<'q, DB> for DatabaseValue
-where
-    bool: Encode<'q, DB> + Type<DB>,
-    Option<bool>: Encode<'q, DB> + Type<DB>,
-    u16: Encode<'q, DB> + Type<DB>,
-    Option<u16>: Encode<'q, DB> + Type<DB>,
-    i16: Encode<'q, DB> + Type<DB>,
-    Option<i16>: Encode<'q, DB> + Type<DB>,
-    u32: Encode<'q, DB> + Type<DB>,
-    Option<u32>: Encode<'q, DB> + Type<DB>,
-    i32: Encode<'q, DB> + Type<DB>,
-    Option<i32>: Encode<'q, DB> + Type<DB>,
-    u64: Encode<'q, DB> + Type<DB>,
-    Option<u64>: Encode<'q, DB> + Type<DB>,
-    i64: Encode<'q, DB> + Type<DB>,
-    Option<i64>: Encode<'q, DB> + Type<DB>,
-    f32: Encode<'q, DB> + Type<DB>,
-    Option<f32>: Encode<'q, DB> + Type<DB>,
-    f64: Encode<'q, DB> + Type<DB>,
-    Option<f64>: Encode<'q, DB> + Type<DB>,
-    Decimal: Encode<'q, DB> + Type<DB>,
-    Option<Decimal>: Encode<'q, DB> + Type<DB>,
-    Time: Encode<'q, DB> + Type<DB>,
-    Option<Time>: Encode<'q, DB> + Type<DB>,
-    Date: Encode<'q, DB> + Type<DB>,
-    Option<Date>: Encode<'q, DB> + Type<DB>,
-    PrimitiveDateTime: Encode<'q, DB> + Type<DB>,
-    Option<PrimitiveDateTime>: Encode<'q, DB> + Type<DB>,
-    Value: Encode<'q, DB> + Type<DB>,
-    Option<Value>: Encode<'q, DB> + Type<DB>,
-    String: Encode<'q, DB> + Type<DB>,
-    Option<String>: Encode<'q, DB> + Type<DB>,
-    Binary: Encode<'q, DB> + Type<DB>,
-    Option<Binary>: Encode<'q, DB> + Type<DB>,
-{
-    fn bind_on(self, query: QueryOf<'q, DB>) -> QueryOf<'q, DB> {
-        match self {
-            DatabaseValue::Bool(b) => query.bind(b),
-            DatabaseValue::SmallInteger(i) => query.bind(i),
-            DatabaseValue::UnsignedSmallInteger(i) => query.bind(i),
-            DatabaseValue::Integer(i) => query.bind(i),
-            DatabaseValue::UnsignedInteger(i) => query.bind(i),
-            DatabaseValue::BigInteger(i) => query.bind(i),
-            DatabaseValue::UnsignedBigInteger(i) => query.bind(i),
-            DatabaseValue::Float(f) => query.bind(f),
-            DatabaseValue::Double(f) => query.bind(f),
-            DatabaseValue::Decimal(f) => query.bind(f),
-            DatabaseValue::Binary(b) => query.bind(b),
-            DatabaseValue::Time(t) => query.bind(t),
-            DatabaseValue::Date(t) => query.bind(t),
-            DatabaseValue::DateTime(t) => query.bind(t),
-            DatabaseValue::String(s) => query.bind(s),
-            DatabaseValue::Json(j) => query.bind(j),
-            DatabaseValue::Null(t) => {
-                fn null_of<T>() -> Option<T> {
-                    None
-                }
-                match t {
-                    DatabaseType::Bool => query.bind(null_of::<i16>()),
-                    DatabaseType::SmallInteger => query.bind(null_of::<i16>()),
-                    DatabaseType::UnsignedSmallInteger => query.bind(null_of::<u16>()),
-                    DatabaseType::Integer => query.bind(null_of::<i32>()),
-                    DatabaseType::UnsignedInteger => query.bind(null_of::<u32>()),
-                    DatabaseType::BigInteger => query.bind(null_of::<i64>()),
-                    DatabaseType::UnsignedBigInteger => query.bind(null_of::<u64>()),
-                    DatabaseType::Float => query.bind(null_of::<f32>()),
-                    DatabaseType::Double => query.bind(null_of::<f64>()),
-                    DatabaseType::Binary => query.bind(null_of::<Binary>()),
-                    DatabaseType::Decimal => query.bind(null_of::<Decimal>()),
-                    DatabaseType::Time => query.bind(null_of::<Time>()),
-                    DatabaseType::Date => query.bind(null_of::<Date>()),
-                    DatabaseType::DateTime => query.bind(null_of::<PrimitiveDateTime>()),
-                    DatabaseType::String => query.bind(null_of::<String>()),
-                    DatabaseType::Json => query.bind(null_of::<Value>()),
-                }
-            }
-        }
-    }
-}
-
 pub type ValueRefOf<'r, DB> = <DB as HasValueRef<'r>>::ValueRef;
 pub type RowOf<DB> = <DB as Database>::Row;
 pub type ColumnOf<DB> = <DB as Database>::Column;
@@ -3,22 +3,26 @@ pub extern crate lazy_static;
 pub extern crate query_builder;
 
 pub use core::*;
-pub use derive::Entity;
+pub use core::view::Json;
+pub use derive::{Embedded, Entity, YukinoValue};
 pub use interface::*;
 
 pub mod prelude {
-    pub use derive::{Entity, make_tuple, tuple};
+    pub use derive::{Embedded, Entity, make_tuple, tuple, YukinoValue};
 
     pub use crate::{and, bt, bte, eq, lt, lte, neq, or};
     pub use crate::operator::{
-        InSubquery, SortOrder, SubqueryExists, VerticalAverage, VerticalBitAnd, VerticalBitOr,
-        VerticalBitXor, VerticalCount, VerticalCountDistinct, VerticalJoin, VerticalMax,
-        VerticalMin, VerticalSum,
+        case, Cast, coalesce, DateFunctions, DateTimeFunctions, InSubquery, JsonExtract, Length,
+        Like, MathFunctions, now, Nullable, SortOrder, StringFunctions, SubqueryExists,
+        TimeFunctions, UnwrapOr, VerticalAverage, VerticalBitAnd, VerticalBitOr, VerticalBitXor,
+        VerticalCount, VerticalCountDistinct, VerticalJoin, VerticalMax, VerticalMin, VerticalSum,
+        window,
     };
     pub use crate::query::{
-        BatchInsert, BelongsToEntities, BelongsToQuery, BelongsToView, Delete, Executable,
-        FetchMulti, FetchOne, Filter, Filter2, Fold, Fold2, GroupBy, GroupFold, JoinChildren, Map,
-        Map2, RightSideData, Sort, Sort2, Update,
+        BatchInsert, BelongsToEntities, BelongsToQuery, BelongsToView, Compound, Delete,
+        Executable, FetchMulti, FetchOne, Filter, Filter2, Fold, Fold2, GenerateQueryFor, GroupBy,
+        GroupFold, InsertReturning, JoinChildren, Map, Map2, RightSideData, Sort, Sort2,
+        transaction, Update, Upsert, UpsertAssignments, with, with_recursive,
     };
     pub use crate::view::{
         Deletable, EntityWithView, ExprView, Identifiable, Insertable, SingleRowSubqueryView,
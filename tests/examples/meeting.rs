@@ -1,5 +1,6 @@
 use std::env;
 
+use futures::StreamExt;
 use sqlx::mysql::MySqlPoolOptions;
 use sqlx::MySqlPool;
 use sqlx::types::Decimal;
@@ -7,6 +8,7 @@ use sqlx::types::Decimal;
 use yukino::prelude::*;
 
 #[derive(Entity, Clone, Debug)]
+#[has_many(Meeting, via = "host_id", name = "hosted_meetings")]
 pub struct Person {
     #[id]
     pub id: u32,
@@ -46,12 +48,8 @@ pub async fn meeting_count_by_level(pool: &MySqlPool) -> Vec<(u16, Option<Decima
     Person::all()
         .group_by(|p| p.level)
         .fold_group(|p| {
-            p.map(|p| {
-                Meeting::belonging_to_view::<meeting::host_id>(&p)
-                    .fold(|m| m.id.count())
-                    .into_expr()
-            })
-            .sum()
+            p.map(|p| p.hosted_meetings().fold(|m| m.id.count()).into_expr())
+                .sum()
         })
         .exec(pool)
         .await
@@ -67,14 +65,8 @@ pub async fn person_and_hosted_meeting(executor: &MySqlPool) -> Vec<(Person, Vec
         .unwrap()
         .try_collect()
         .unwrap();
-    let meetings = Meeting::belonging_to::<meeting::host_id>(&persons)
-        .exec(executor)
-        .await
-        .unwrap()
-        .try_collect()
-        .unwrap();
 
-    persons.join::<meeting::host_id>(meetings)
+    persons.load_hosted_meetings(executor).await.unwrap()
 }
 
 pub async fn hosted_meeting_titles(pool: &MySqlPool) -> Vec<(u32, Option<String>)> {
@@ -82,7 +74,7 @@ pub async fn hosted_meeting_titles(pool: &MySqlPool) -> Vec<(u32, Option<String>
         .map(|p| {
             (
                 p.id.clone(),
-                Meeting::belonging_to_view::<meeting::host_id>(&p)
+                p.hosted_meetings()
                     .fold(|m| m.sort(|m| m.id.asc()).map(|m| m.title).join(Some(", ")))
                     .into_expr(),
             )
@@ -168,8 +160,19 @@ pub async fn prepare_data(pool: &MySqlPool) {
         },
     ];
 
-    person_list.insert_all().exec(pool).await.unwrap();
-    meeting_list.insert_all().exec(pool).await.unwrap();
+    transaction(pool, |tx| {
+        Box::pin(async move {
+            person_list.insert_all().exec(&mut *tx).await?;
+            transaction(&mut *tx, |savepoint| {
+                Box::pin(async move { meeting_list.insert_all().exec(savepoint).await })
+            })
+            .await?;
+
+            Ok::<_, sqlx::Error>(())
+        })
+    })
+    .await
+    .unwrap();
 }
 
 pub async fn big_data_person(pool: &MySqlPool, size: usize) {
@@ -186,6 +189,28 @@ pub async fn big_data_person(pool: &MySqlPool, size: usize) {
         .unwrap();
 }
 
+pub async fn insert_returning(pool: &MySqlPool) -> (Person, Vec<Person>) {
+    let new_person = |name: &str| NewPerson {
+        name: name.to_string(),
+        age: 18,
+        level: 1,
+        comment: "".to_string(),
+    };
+
+    let person = new_person("Eve")
+        .insert()
+        .exec_returning(pool)
+        .await
+        .unwrap();
+    let persons = vec![new_person("Frank"), new_person("Grace")]
+        .insert_all()
+        .exec_returning(pool)
+        .await
+        .unwrap();
+
+    (person, persons)
+}
+
 pub async fn simple_query(pool: &MySqlPool) {
     Person::all()
         .exec(pool)
@@ -195,6 +220,18 @@ pub async fn simple_query(pool: &MySqlPool) {
         .unwrap();
 }
 
+pub async fn stream_query(pool: &MySqlPool) -> usize {
+    let mut persons = Person::all().exec_stream(pool);
+    let mut count = 0;
+
+    while let Some(person) = persons.next().await {
+        person.unwrap();
+        count += 1;
+    }
+
+    count
+}
+
 #[tokio::main]
 pub async fn main() -> Result<(), sqlx::Error> {
     let url = env::var("DB").unwrap();
@@ -205,6 +242,14 @@ pub async fn main() -> Result<(), sqlx::Error> {
 
     //big_data_person(&pool, 10000).await;
     simple_query(&pool).await;
+    stream_query(&pool).await;
+
+    let (person, persons) = insert_returning(&pool).await;
+    assert_eq!(person.name, "Eve");
+    assert_eq!(
+        persons.into_iter().map(|p| p.name).collect::<Vec<_>>(),
+        vec!["Frank", "Grace"]
+    );
     /*
         Person::all().delete().exec(&pool).await.unwrap();
         Meeting::all().delete().exec(&pool).await.unwrap();
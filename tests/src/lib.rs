@@ -1,9 +1,14 @@
 use std::time::SystemTime;
 
+use serde::{Deserialize, Serialize};
 use sqlx::types::Decimal;
 use sqlx::types::time::PrimitiveDateTime;
 
-use yukino::Entity;
+use yukino::{Embedded, Entity, YukinoValue};
+use yukino::query_builder::{
+    Dialect, MySqlDialect, PostgresDialect, QueryBuildState, SqliteDialect, ToSql,
+};
+use yukino::view::{ExprViewBoxWithTag, TagList, Value};
 
 #[derive(Entity, Debug, Clone)]
 pub struct Bar {
@@ -13,6 +18,114 @@ pub struct Bar {
 }
 
 #[derive(Entity, Clone, Debug)]
+pub struct Tag {
+    #[id]
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(Entity, Clone, Debug)]
+#[has_many(FooTagNote, via = "foo_tag")]
+pub struct FooTag {
+    #[id]
+    #[belongs_to(Foo)]
+    pub foo_id: u32,
+    #[id]
+    #[belongs_to(Tag)]
+    pub tag_id: u32,
+}
+
+#[derive(Entity, Clone, Debug)]
+#[belongs_to(FooTag, via = ("foo_id", "tag_id"))]
+pub struct FooTagNote {
+    #[id]
+    pub id: u32,
+    pub foo_id: u32,
+    pub tag_id: u32,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Address {
+    pub city: String,
+    pub zip_code: String,
+}
+
+#[derive(Entity, Clone, Debug)]
+pub struct Profile {
+    #[id]
+    pub id: u32,
+    pub settings: serde_json::Value,
+    pub address: sqlx::types::Json<Address>,
+    pub extra: Option<serde_json::Value>,
+}
+
+#[derive(YukinoValue, Clone, Debug, PartialEq)]
+pub enum Status {
+    Active,
+    #[name = "blocked"]
+    Banned,
+}
+
+#[derive(YukinoValue, Clone, Copy, Debug, PartialEq)]
+#[store = "integer"]
+pub enum Priority {
+    Low = 1,
+    High = 10,
+}
+
+#[derive(YukinoValue, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UserId(pub u32);
+
+#[derive(Entity, Clone, Debug)]
+#[has_many(Post, via = "author_id")]
+pub struct Account {
+    #[id]
+    #[value]
+    pub id: UserId,
+    #[value]
+    pub status: Status,
+    #[value]
+    pub priority: Priority,
+    #[value]
+    pub previous_status: Option<Status>,
+}
+
+#[derive(Entity, Clone, Debug)]
+pub struct Post {
+    #[id]
+    pub id: u32,
+    #[belongs_to(Account)]
+    #[value]
+    pub author_id: UserId,
+    pub title: String,
+}
+
+#[derive(Embedded, Clone, Debug)]
+pub struct PostalAddress {
+    pub street: String,
+    pub city: String,
+    #[name = "zip_code"]
+    pub zip: u32,
+    pub note: Option<String>,
+}
+
+#[derive(Entity, Clone, Debug)]
+pub struct Shop {
+    #[id]
+    pub id: u32,
+    pub name: String,
+    #[embedded]
+    pub address: PostalAddress,
+    #[embedded]
+    #[name = "billing"]
+    pub billing_address: PostalAddress,
+}
+
+#[derive(Entity, Clone, Debug)]
+#[has_many(Bar, via = "foo_id")]
+#[has_one(Bar, via = "foo_id", name = "first_bar")]
+#[many_to_many(Tag, through = FooTag)]
 pub struct Foo {
     #[id]
     #[auto_increment]
@@ -33,6 +146,8 @@ pub struct Foo {
     pub date: sqlx::types::time::Date,
     pub time: sqlx::types::time::Time,
     pub datetime: sqlx::types::time::PrimitiveDateTime,
+    pub binary: Vec<u8>,
+    pub optional_binary: Option<Vec<u8>>,
 }
 
 pub fn create_foo() -> Foo {
@@ -55,6 +170,8 @@ pub fn create_foo() -> Foo {
         date: now.date(),
         time: now.time(),
         datetime: now,
+        binary: vec![],
+        optional_binary: None,
     }
 }
 
@@ -77,5 +194,25 @@ pub fn create_new_foo() -> NewFoo {
         date: now.date(),
         time: now.time(),
         datetime: now,
+        binary: vec![],
+        optional_binary: None,
     }
 }
+
+pub fn cmp_view<T: Value, Tags: TagList>(view: ExprViewBoxWithTag<T, Tags>, query: &str) {
+    assert_eq!(
+        view.collect_expr().into_iter().next().unwrap().to_string(),
+        query.to_string(),
+    );
+}
+
+// Renders `query` in MySQL, Postgres and SQLite
+pub fn render_all<Q: ToSql>(query: &Q) -> [String; 3] {
+    let dialects: [&'static dyn Dialect; 3] = [&MySqlDialect, &PostgresDialect, &SqliteDialect];
+
+    dialects.map(|dialect| {
+        let mut state = QueryBuildState::create(dialect);
+        query.to_sql(&mut state).unwrap();
+        state.to_string()
+    })
+}
@@ -1,3 +1,5 @@
+use sqlx::MySql;
+
 use yukino::Association;
 use yukino::prelude::*;
 use yukino_tests::*;
@@ -8,8 +10,8 @@ fn test_association_impl() {
         foo_id: 1,
         name: "test".to_string(),
     };
-    assert_eq!(Bar::foreign_key_name(), "foo_id");
-    assert_eq!(*bar.foreign_key(), 1);
+    assert_eq!(Bar::foreign_key_columns(), ["foo_id"]);
+    assert_eq!(bar.foreign_key(), 1);
 }
 
 #[test]
@@ -20,7 +22,7 @@ fn test_association_query() {
 
     let query = bar
         .filter(|b| eq!(b.name, "test".to_string()))
-        .generate_query();
+        .generate_query_for::<MySql>();
 
     println!("{}", query)
 }
@@ -35,7 +37,7 @@ fn test_subquery_from_view() {
                 .fold(|b| b.name.join(Some(", ")))
                 .into_expr()
         })
-        .generate_query();
+        .generate_query_for::<MySql>();
 
     println!("{}", query)
 }
@@ -55,7 +57,88 @@ fn test_subquery_fn() {
                 .fold(|b| b.name.join(Some(", ")))
                 .into_expr()
         })
-        .generate_query();
+        .generate_query_for::<MySql>();
+
+    println!("{}", query)
+}
+
+#[test]
+fn test_join() {
+    let query = Bar::all()
+        .join::<bar::foo_id>()
+        .filter(|b, f| eq!(b.name, f.string))
+        .filter(|_, f| lt!(f.int, 114514))
+        .map(|b, f| make_tuple!(b.name, f.int))
+        .generate_query_for::<MySql>();
+
+    println!("{}", query)
+}
+
+#[test]
+fn test_left_join() {
+    let query = Bar::all()
+        .left_join::<bar::foo_id>()
+        .map(|b, f| make_tuple!(b.name, f.field(foo::optional), f.field(foo::string)))
+        .generate_query_for::<MySql>();
+
+    println!("{}", query)
+}
+
+#[test]
+fn test_has_many() {
+    let query = Foo::all()
+        .filter(|f| f.bars().map(|b| b.name).exists())
+        .map(|f| f.first_bar().map(|b| b.name).first().as_expr())
+        .generate_query_for::<MySql>();
+
+    println!("{}", query)
+}
+
+#[test]
+fn test_many_to_many() {
+    let query = Foo::tags_of(Foo::all().filter(|f| lt!(f.int, 114514)))
+        .map(|t| t.name)
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let query = Foo::all()
+        .map(|f| f.tags().fold(|t| t.name.join(Some(", "))).into_expr())
+        .generate_query_for::<MySql>();
+
+    println!("{}", query)
+}
+
+#[test]
+fn test_composite_association() {
+    let note = FooTagNote {
+        id: 1,
+        foo_id: 2,
+        tag_id: 3,
+        content: "test".to_string(),
+    };
+    assert_eq!(FooTagNote::foreign_key_columns(), ["foo_id", "tag_id"]);
+    assert_eq!(note.foreign_key(), (2, 3));
+
+    let query = FooTag::foo_tag_notes_of(FooTag::all().filter(|t| eq!(t.tag_id, 1)))
+        .map(|n| n.content)
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let query = FooTagNote::all()
+        .join::<foo_tag_note::foo_tag>()
+        .filter(|_, t| eq!(t.tag_id, 1))
+        .map(|n, _| n.content)
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let query = FooTagNote::belonging_to::<foo_tag_note::foo_tag>(&[FooTag {
+        foo_id: 2,
+        tag_id: 3,
+    }])
+    .generate_query_for::<MySql>();
 
     println!("{}", query)
 }
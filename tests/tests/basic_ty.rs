@@ -107,3 +107,25 @@ pub fn test_boolean() {
     cmp_view(lt, "b.double < b.double");
     cmp_view(lte, "b.float <= b.float");
 }
+
+#[test]
+pub fn test_binary() {
+    let alias = Alias {
+        name: "b".to_string(),
+    };
+
+    let view = FooView::pure(&alias);
+
+    let eq_const = eq!(view.binary.clone(), vec![1, 1, 4, 5, 1, 4]);
+    let neq = neq!(view.binary.clone(), view.binary.clone());
+    let length = view.binary.length();
+    let optional_length = view.optional_binary.length();
+
+    cmp_view(eq_const, "b.binary == BinaryData");
+    cmp_view(neq, "b.binary != b.binary");
+    cmp_view(length, "CAST(Length(b.binary) AS BigInteger)");
+    cmp_view(
+        optional_length,
+        "CAST(Length(b.optional_binary) AS BigInteger)",
+    );
+}
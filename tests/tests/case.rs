@@ -0,0 +1,107 @@
+use sqlx::MySql;
+
+use yukino::prelude::*;
+use yukino::query_builder::Alias;
+use yukino::view::EntityView;
+use yukino_tests::*;
+
+#[test]
+pub fn test_case() {
+    let alias = Alias {
+        name: "b".to_string(),
+    };
+
+    let view = FooView::pure(&alias);
+
+    cmp_view(
+        case()
+            .when(lt!(view.int.clone(), 18), "minor".to_string())
+            .otherwise("adult".to_string()),
+        "CASE WHEN b.int < 18 THEN \"minor\" ELSE \"adult\" END",
+    );
+    cmp_view(
+        case()
+            .when(lt!(view.int.clone(), 0), view.long.clone())
+            .when(eq!(view.int.clone(), 0), 0i64)
+            .otherwise(view.long.clone() * 2),
+        "CASE WHEN b.int < 0 THEN b.long WHEN b.int == 0 THEN 0 ELSE b.long * 2 END",
+    );
+}
+
+#[test]
+pub fn test_case_query() {
+    let query = Foo::all()
+        .map(|f| {
+            case()
+                .when(lt!(f.int.clone(), 18), "minor".to_string())
+                .when(lt!(f.int, 65), "adult".to_string())
+                .otherwise("senior".to_string())
+        })
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let query = Foo::all()
+        .sort(|f| {
+            case()
+                .when(f.boolean, f.int.clone())
+                .otherwise(f.int * 2)
+                .asc()
+        })
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
+
+#[test]
+pub fn test_case_group() {
+    let query = Foo::all()
+        .group_by(|f| case().when(bte!(f.int, 100), 100).otherwise(0))
+        .map(|bucket| bucket)
+        .generate_query_for::<MySql>();
+
+    let rendered = render_all(&query);
+
+    assert_eq!(
+        rendered[0],
+        "SELECT CASE WHEN ( `foo_1` . `int` >= ? ) THEN ? ELSE ? END AS `U0` FROM foo `foo_1` GROUP BY 1"
+    );
+    assert_eq!(
+        rendered[1],
+        "SELECT CASE WHEN ( \"foo_1\" . \"int\" >= $1 ) THEN $2 ELSE $3 END AS \"U0\" FROM foo \"foo_1\" GROUP BY 1"
+    );
+    assert_eq!(
+        rendered[2],
+        "SELECT CASE WHEN ( \"foo_1\" . \"int\" >= ?1 ) THEN ?2 ELSE ?3 END AS \"U0\" FROM foo \"foo_1\" GROUP BY 1"
+    );
+}
+
+#[test]
+pub fn test_case_group_having_order() {
+    let query = Foo::all()
+        .group_by(|f| case().when(bte!(f.int, 100), 100).otherwise(0))
+        .filter(|bucket| bt!(bucket, 10))
+        .sort(|bucket| bucket.desc())
+        .map(|bucket| bucket)
+        .generate_query_for::<MySql>();
+
+    let rendered = render_all(&query);
+
+    assert_eq!(
+        rendered[0],
+        "SELECT CASE WHEN ( `foo_1` . `int` >= ? ) THEN ? ELSE ? END AS `U0` FROM foo `foo_1` GROUP BY 1 \
+         HAVING ( `U0` > ? ) ORDER BY `U0` DESC"
+    );
+    assert_eq!(
+        rendered[1],
+        "SELECT CASE WHEN ( \"foo_1\" . \"int\" >= $1 ) THEN $2 ELSE $3 END AS \"U0\" FROM foo \"foo_1\" \
+         GROUP BY 1 HAVING ( CASE WHEN ( \"foo_1\" . \"int\" >= $1 ) THEN $2 ELSE $3 END > $4 ) \
+         ORDER BY CASE WHEN ( \"foo_1\" . \"int\" >= $1 ) THEN $2 ELSE $3 END DESC"
+    );
+    assert_eq!(
+        rendered[2],
+        "SELECT CASE WHEN ( \"foo_1\" . \"int\" >= ?1 ) THEN ?2 ELSE ?3 END AS \"U0\" FROM foo \"foo_1\" \
+         GROUP BY 1 HAVING ( CASE WHEN ( \"foo_1\" . \"int\" >= ?1 ) THEN ?2 ELSE ?3 END > ?4 ) \
+         ORDER BY CASE WHEN ( \"foo_1\" . \"int\" >= ?1 ) THEN ?2 ELSE ?3 END DESC"
+    );
+}
@@ -0,0 +1,67 @@
+use sqlx::MySql;
+
+use yukino::prelude::*;
+use yukino_tests::*;
+
+#[test]
+pub fn test_compound() {
+    let hosts = Foo::all().filter(|f| f.boolean).map(|f| f.id);
+    let co_hosts = Bar::all().map(|b| b.foo_id);
+
+    let query = hosts
+        .clone()
+        .union(co_hosts.clone())
+        .sort(|id| id.desc())
+        .limit(10)
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let query = hosts
+        .clone()
+        .intersect(co_hosts.clone())
+        .except(Foo::all().filter(|f| lt!(f.int, 0)).map(|f| f.id))
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let query = Foo::all()
+        .filter(|f| f.id.in_subquery(hosts.clone().union_all(co_hosts.clone())))
+        .map(|f| (f.id, f.string))
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let query = Foo::all()
+        .map(|f| (f.id, f.string))
+        .union(Foo::all().map(|f| (f.id, f.string)))
+        .sort(|(id, _)| id.asc())
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
+
+#[test]
+pub fn test_compound_dialect() {
+    let query = Foo::all()
+        .filter(|f| eq!(f.int, 1))
+        .map(|f| f.id)
+        .union(Bar::all().sort(|b| b.name.asc()).map(|b| b.foo_id).limit(5))
+        .sort(|id| id.desc())
+        .limit(10)
+        .generate_query_for::<MySql>();
+
+    let rendered = render_all(&query);
+
+    assert!(rendered[0].contains(
+        "UNION SELECT * FROM ( SELECT `bar_1` . `foo_id` AS `U0` FROM bar `bar_1` \
+         ORDER BY `bar_1` . `name` ASC LIMIT  5 ) `compound_1_1` ) `compound_1`"
+    ));
+    assert!(rendered[1].starts_with(
+        "SELECT \"compound_1\" . \"U0\" AS \"U0\" FROM ( SELECT \"foo_1\" . \"id\" AS \"U0\" \
+         FROM foo \"foo_1\" WHERE ( \"foo_1\" . \"int\" = $1 ) UNION SELECT"
+    ));
+    assert!(
+        rendered[2].ends_with(") \"compound_1\" ORDER BY \"compound_1\" . \"U0\" DESC LIMIT  10")
+    );
+}
@@ -0,0 +1,98 @@
+use sqlx::MySql;
+
+use yukino::prelude::*;
+use yukino_tests::*;
+
+#[test]
+pub fn test_cte() {
+    let query = with(
+        "big_foo",
+        Foo::all()
+            .filter(|f| bt!(f.int.clone(), 100))
+            .map(|f| (f.id, f.long)),
+    )
+    .filter(|(_, long)| lt!(long, 1000))
+    .map(|(id, _)| id)
+    .generate_query_for::<MySql>();
+
+    let rendered = query.to_string();
+
+    assert!(rendered.starts_with("WITH big_foo(U0, U1) AS (SELECT foo_1.id"));
+    assert!(rendered.contains("FROM big_foo big_foo_1  WHERE big_foo_1.U1 < 1000"));
+
+    let query = with("ids", Foo::all().map(|f| f.id))
+        .sort(|id| id.desc())
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
+
+#[test]
+pub fn test_recursive_cte() {
+    let query = with_recursive(
+        "chain",
+        Foo::all().filter(|f| eq!(f.id, 1)).map(|f| f.id),
+        |chain| {
+            chain
+                .join::<Foo, _, _, _>(|id, f| eq!(f.id, id + 1))
+                .filter(|id, _| lt!(id, 10))
+                .map(|_, f| f.id)
+        },
+    )
+    .join::<Foo, _, _, _>(|id, f| eq!(f.id, id))
+    .map(|_, f| (f.id, f.string))
+    .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let rendered = render_all(&query);
+
+    assert!(rendered[0].starts_with(
+        "WITH RECURSIVE chain ( `U0` ) AS ( SELECT `foo_1` . `id` AS `U0` FROM foo `foo_1` \
+         WHERE ( `foo_1` . `id` = ? ) UNION ALL SELECT"
+    ));
+    assert!(rendered[1].contains(
+        "FROM chain \"chain_1\" INNER JOIN foo \"foo_1\" \
+         ON ( \"foo_1\" . \"id\" = ( \"chain_1\" . \"U0\" + $2 ) ) \
+         WHERE ( \"chain_1\" . \"U0\" < $3 ) )"
+    ));
+    assert!(rendered[2].contains(
+        ") SELECT \"foo_1\" . \"id\" AS \"U0\" , \"foo_1\" . \"string\" AS \"U1\" \
+         FROM chain \"chain_1\""
+    ));
+}
+
+#[test]
+pub fn test_cte_fold_and_limit() {
+    let query = with("ids", Foo::all().map(|f| f.id))
+        .fold(|ids| ids.count())
+        .generate_query_for::<MySql>();
+
+    assert!(render_all(&query)[1]
+        .ends_with(") SELECT COUNT ( \"ids_1\" . \"U0\" ) AS \"U0\" FROM ids \"ids_1\""));
+
+    let query = with("ids", Foo::all().map(|f| f.id))
+        .sort(|id| id.desc())
+        .limit(10)
+        .offset(5)
+        .generate_query_for::<MySql>();
+
+    assert!(render_all(&query)[1]
+        .ends_with("FROM ids \"ids_1\" ORDER BY \"ids_1\" . \"U0\" DESC LIMIT  10 OFFSET  5"));
+
+    // A CTE defined on another one nests its `WITH`
+    let query = with(
+        "small_ids",
+        with("ids", Foo::all().map(|f| f.id))
+            .filter(|id| lt!(id, 100))
+            .map(|id| id),
+    )
+    .map(|id| id + 1)
+    .generate_query_for::<MySql>();
+
+    assert!(render_all(&query)[1].starts_with(
+        "WITH small_ids ( \"U0\" ) AS ( WITH ids ( \"U0\" ) AS ( SELECT \"foo_1\" . \"id\" AS \"U0\" \
+         FROM foo \"foo_1\" ) SELECT \"ids_1\" . \"U0\" AS \"U0\" FROM ids \"ids_1\" \
+         WHERE ( \"ids_1\" . \"U0\" < $1 ) ) SELECT ( \"small_ids_1\" . \"U0\" + $2 )"
+    ));
+}
@@ -0,0 +1,86 @@
+use sqlx::MySql;
+
+use yukino::{DatabaseType, YukinoEntity};
+use yukino::prelude::*;
+use yukino::operator::In;
+use yukino::query_builder::Alias;
+use yukino::view::{CustomValue, EntityView};
+use yukino_tests::*;
+
+#[test]
+pub fn test_custom_value_mapping() {
+    assert_eq!(Status::Banned.into_inner(), "blocked".to_string());
+    assert_eq!(
+        Status::from_inner("active".to_string()),
+        Some(Status::Active)
+    );
+    assert_eq!(Status::from_inner("unknown".to_string()), None);
+    assert_eq!(Priority::High.into_inner(), 10);
+    assert_eq!(Priority::from_inner(1), Some(Priority::Low));
+    assert_eq!(Priority::from_inner(2), None);
+    assert_eq!(UserId::from_inner(114514), Some(UserId(114514)));
+}
+
+#[test]
+pub fn test_custom_value_view() {
+    let alias = Alias {
+        name: "a".to_string(),
+    };
+
+    let view = AccountView::pure(&alias);
+
+    let eq_status = eq!(view.status.clone(), Status::Banned);
+    let neq_id = neq!(view.id.clone(), UserId(114));
+    let bt_id = bt!(view.id.clone(), UserId(514));
+    let lte_priority = lte!(view.priority.clone(), Priority::High);
+    let in_status = view
+        .status
+        .clone()
+        .in_arr(vec![Status::Active, Status::Banned]);
+
+    cmp_view(eq_status, "a.status == \"blocked\"");
+    cmp_view(neq_id, "a.id != 114");
+    cmp_view(bt_id, "a.id > 514");
+    cmp_view(lte_priority, "a.priority <= 10");
+    cmp_view(in_status, "a.status IN (\"active\", \"blocked\")");
+}
+
+#[test]
+pub fn test_custom_value_definition() {
+    let definition = Account::definition();
+    let column_types: Vec<_> = definition.fields.iter().map(|f| f.columns[0].ty).collect();
+
+    assert_eq!(
+        column_types,
+        vec![
+            DatabaseType::UnsignedInteger,
+            DatabaseType::String,
+            DatabaseType::Integer,
+            DatabaseType::String
+        ]
+    );
+    assert!(definition.fields[3].columns[0].optional);
+}
+
+#[test]
+pub fn test_custom_value_query() {
+    let query = Account::all()
+        .filter(|a| eq!(a.status, Status::Active))
+        .sort(|a| a.priority.desc())
+        .map(|a| (a.id, a.previous_status))
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let query = Post::all()
+        .join::<post::author_id>()
+        .filter(|_, a| neq!(a.status, Status::Banned))
+        .map(|p, _| p.title)
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let query = Account::get(UserId(114514)).generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
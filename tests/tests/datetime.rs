@@ -0,0 +1,107 @@
+use sqlx::MySql;
+
+use yukino::prelude::*;
+use yukino::query_builder::{Alias, DateTimeUnit};
+use yukino::view::EntityView;
+use yukino_tests::*;
+
+#[test]
+pub fn test_datetime_functions() {
+    let alias = Alias {
+        name: "b".to_string(),
+    };
+
+    let view = FooView::pure(&alias);
+
+    cmp_view(now(), "NOW()");
+    cmp_view(
+        view.date.clone().year(),
+        "CAST(EXTRACT(Year FROM b.date) AS BigInteger)",
+    );
+    cmp_view(
+        view.datetime.clone().hour(),
+        "CAST(EXTRACT(Hour FROM b.datetime) AS BigInteger)",
+    );
+    cmp_view(
+        view.time.clone().second(),
+        "CAST(EXTRACT(Second FROM b.time) AS BigInteger)",
+    );
+    cmp_view(
+        view.datetime.clone().date_trunc(DateTimeUnit::Month),
+        "CAST(DATE_TRUNC(Month, b.datetime) AS DateTime)",
+    );
+    cmp_view(
+        view.date.clone().add_days(7),
+        "CAST(DATE_ADD(b.date, INTERVAL 7 Day) AS Date)",
+    );
+    cmp_view(
+        view.time
+            .clone()
+            .add_interval(view.int.clone(), DateTimeUnit::Minute),
+        "CAST(DATE_ADD(b.time, INTERVAL b.int Minute) AS Time)",
+    );
+    cmp_view(
+        view.datetime.clone().diff_in_seconds(now()),
+        "CAST(DIFF_IN_SECONDS(b.datetime, NOW()) AS BigInteger)",
+    );
+}
+
+#[test]
+pub fn test_datetime_query() {
+    let query = Foo::all()
+        .filter(|f| lt!(f.datetime.clone(), now()))
+        .group_by(|f| f.datetime.date_trunc(DateTimeUnit::Month))
+        .map(|month| month.year())
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let query = Foo::all()
+        .sort(|f| f.date.clone().month().asc())
+        .map(|f| f.datetime.clone().diff_in_seconds(f.datetime.add_days(1)))
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
+
+#[test]
+pub fn test_datetime_dialect() {
+    let query = Foo::all()
+        .filter(|f| {
+            bt!(
+                f.date.clone().add_interval(1, DateTimeUnit::Month),
+                f.date.clone()
+            )
+        })
+        .map(|f| {
+            (
+                f.datetime.clone().date_trunc(DateTimeUnit::Day),
+                f.date.clone().diff_in_seconds(f.date),
+            )
+        })
+        .generate_query_for::<MySql>();
+
+    let rendered = render_all(&query);
+
+    assert!(rendered[0]
+        .contains("CAST( DATE_FORMAT( `foo_1` . `datetime` , '%Y-%m-%d 00:00:00' ) AS DATETIME )"));
+    assert!(rendered[0].contains("DATE_ADD( `foo_1` . `date` , INTERVAL ? MONTH )"));
+    assert!(
+        rendered[0].contains("( - TIMESTAMPDIFF(SECOND, `foo_1` . `date` , `foo_1` . `date` ) )")
+    );
+    assert!(rendered[1].contains("DATE_TRUNC( 'DAY' , \"foo_1\" . \"datetime\" )"));
+    assert!(rendered[1]
+        .contains("CAST( ( \"foo_1\" . \"date\" + ( $1 * INTERVAL '1 MONTH' ) ) AS DATE )"));
+    assert!(rendered[1].contains("( ( \"foo_1\" . \"date\" - \"foo_1\" . \"date\" ) * 86400 )"));
+    assert!(rendered[2].contains("STRFTIME( '%Y-%m-%d 00:00:00' , \"foo_1\" . \"datetime\" )"));
+    assert!(rendered[2].contains("DATE ( \"foo_1\" . \"date\" , ( ?1 || ' months' ) )"));
+    assert!(rendered[2].contains(
+        "( STRFTIME( '%s' , \"foo_1\" . \"date\" ) - STRFTIME( '%s' , \"foo_1\" . \"date\" ) )"
+    ));
+
+    // fractional seconds of Postgres are floored as in other databases
+    let query = Foo::all().map(|f| f.time.second()).generate_query_for::<MySql>();
+    let rendered = render_all(&query);
+
+    assert!(rendered[1].contains("FLOOR( EXTRACT( SECOND FROM \"foo_1\" . \"time\" ) )"));
+}
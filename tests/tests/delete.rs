@@ -1,3 +1,5 @@
+use sqlx::MySql;
+
 use yukino::prelude::*;
 use yukino_tests::*;
 
@@ -9,7 +11,7 @@ fn test_delete() {
         .sort(|f| f.id.asc())
         .delete()
         .limit(10)
-        .generate_query();
+        .generate_query_for::<MySql>();
 
     println!("{}", query);
 }
@@ -0,0 +1,19 @@
+use sqlx::MySql;
+
+use yukino::prelude::*;
+use yukino_tests::*;
+
+#[test]
+fn test_runtime_dialect() {
+    let query = Foo::all()
+        .filter(|f| lt!(f.int, 114514))
+        .filter(|f| bt!(f.short, 1919))
+        .generate_query_for::<MySql>();
+
+    let rendered = render_all(&query);
+
+    assert!(rendered[0].contains("`int` < ?"));
+    assert!(rendered[1].contains("\"int\" < $1"));
+    assert!(rendered[1].contains("\"short\" > $2"));
+    assert!(rendered[2].contains("\"int\" < ?1"));
+}
@@ -0,0 +1,70 @@
+use sqlx::MySql;
+
+use yukino::prelude::*;
+use yukino::query_builder::Alias;
+use yukino::view::EntityView;
+use yukino::YukinoEntity;
+use yukino_tests::*;
+
+#[test]
+pub fn test_embedded_definition() {
+    let definition = Shop::definition();
+    let columns: Vec<_> = definition
+        .fields
+        .iter()
+        .flat_map(|f| f.columns.iter().map(|c| (c.name.clone(), c.optional)))
+        .collect();
+
+    assert_eq!(
+        columns,
+        vec![
+            ("id".to_string(), false),
+            ("name".to_string(), false),
+            ("address_street".to_string(), false),
+            ("address_city".to_string(), false),
+            ("address_zip_code".to_string(), false),
+            ("address_note".to_string(), true),
+            ("billing_street".to_string(), false),
+            ("billing_city".to_string(), false),
+            ("billing_zip_code".to_string(), false),
+            ("billing_note".to_string(), true),
+        ]
+    );
+    assert_eq!(
+        <Shop as Insertable<sqlx::MySql>>::columns(),
+        columns.into_iter().map(|(c, _)| c).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+pub fn test_embedded_view() {
+    let alias = Alias {
+        name: "s".to_string(),
+    };
+
+    let view = ShopView::pure(&alias);
+
+    let eq_city = eq!(view.address.city.clone(), "Tokyo".to_string());
+    let bt_zip = bt!(view.billing_address.zip.clone(), 1000);
+
+    cmp_view(eq_city, "s.address_city == \"Tokyo\"");
+    cmp_view(bt_zip, "s.billing_zip_code > 1000");
+}
+
+#[test]
+pub fn test_embedded_query() {
+    let query = Shop::all()
+        .filter(|s| eq!(s.address.city, "Tokyo".to_string()))
+        .sort(|s| s.billing_address.zip.desc())
+        .map(|s| (s.name, s.address.street))
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let query = Shop::all()
+        .filter(|s| neq!(s.address.city, s.billing_address.city))
+        .map(|s| s.billing_address)
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
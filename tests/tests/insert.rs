@@ -1,11 +1,17 @@
+use std::time::Duration;
+
+use sqlx::MySql;
+use sqlx::mysql::MySqlPoolOptions;
+
 use yukino::prelude::*;
-use yukino_tests::{create_foo, create_new_foo};
+use yukino::query_builder::{Dialect, MySqlDialect, OnConflict, QueryBuildState};
+use yukino_tests::*;
 
 #[test]
 fn test_insert() {
     let test = create_foo();
 
-    let query = test.insert().generate_query();
+    let query = test.insert().generate_query_for::<MySql>();
 
     println!("{}", query)
 }
@@ -13,7 +19,82 @@ fn test_insert() {
 #[test]
 fn test_new() {
     let test = create_new_foo();
-    let query = test.insert().generate_query();
+    let query = test.insert().generate_query_for::<MySql>();
 
     println!("{}", query)
 }
+
+#[test]
+fn test_upsert() {
+    let query = vec![create_foo()]
+        .insert_all()
+        .on_conflict_update(|excluded| {
+            UpsertAssignments::create()
+                .set(foo::string, excluded.string)
+                .set(foo::int, 114514)
+        })
+        .generate_query_for::<MySql>();
+    let rendered = render_all(&query);
+
+    assert!(rendered[0].ends_with(
+        "? ) AS `excluded` ON DUPLICATE KEY UPDATE `string` = `excluded` . `string` , `int` = ? ;"
+    ));
+    assert!(rendered[1].ends_with(
+        "$19 ) ON CONFLICT ( \"id\" ) DO UPDATE SET \"string\" = \"excluded\" . \"string\" , \
+         \"int\" = $20 ;"
+    ));
+    assert!(rendered[2].ends_with(
+        "?19 ) ON CONFLICT ( \"id\" ) DO UPDATE SET \"string\" = \"excluded\" . \"string\" , \
+         \"int\" = ?20 ;"
+    ));
+
+    let query = vec![create_foo()]
+        .insert_all()
+        .on_conflict_do_nothing()
+        .generate_query_for::<MySql>();
+    let rendered = render_all(&query);
+
+    assert!(rendered[0].ends_with("? ) ON DUPLICATE KEY UPDATE  `id` = `id` ;"));
+    assert!(rendered[1].ends_with("$19 ) ON CONFLICT ( \"id\" ) DO NOTHING ;"));
+    assert!(rendered[2].ends_with("?19 ) ON CONFLICT ( \"id\" ) DO NOTHING ;"));
+}
+
+#[test]
+fn test_do_nothing_on_mysql() {
+    let render = |columns: &[String], target: Vec<String>| {
+        let mut state = QueryBuildState::create(&MySqlDialect);
+        MySqlDialect
+            .write_on_conflict(columns, &OnConflict::DoNothing { target }, &mut state)
+            .map(|_| state.to_string())
+    };
+
+    assert!(render(&["name".to_string()], vec!["id".to_string()])
+        .unwrap()
+        .ends_with("`id` = `id`"));
+    assert!(render(&["name".to_string()], vec![])
+        .unwrap()
+        .ends_with("`name` = `name`"));
+    assert!(render(&[], vec![]).is_err());
+}
+
+// Upserts are rejected before connecting, batch inserts are fetched by their id range
+#[tokio::test]
+async fn test_insert_returning_without_returning() {
+    let pool = MySqlPoolOptions::new()
+        .connect_timeout(Duration::from_millis(100))
+        .connect_lazy("mysql://localhost/yukino")
+        .unwrap();
+
+    let upsert = vec![create_new_foo()]
+        .insert_all()
+        .on_conflict_do_nothing()
+        .exec_returning(&pool)
+        .await;
+    assert!(matches!(upsert, Err(sqlx::Error::Configuration(_))));
+
+    let batch = vec![create_new_foo(), create_new_foo()]
+        .insert_all()
+        .exec_returning(&pool)
+        .await;
+    assert!(!matches!(batch, Err(sqlx::Error::Configuration(_))));
+}
@@ -0,0 +1,114 @@
+use sqlx::MySql;
+
+use yukino::{DatabaseType, Json, YukinoEntity};
+use yukino::prelude::*;
+use yukino::view::{ExprViewBoxWithTag, OrdViewTag, TagList2, WindowViewTag};
+use yukino_tests::*;
+
+#[derive(Entity, Clone, Debug)]
+pub struct Delivery {
+    #[id]
+    pub id: u32,
+    pub address: Json<Address>,
+    pub fallback: Option<Json<Address>>,
+}
+
+#[test]
+fn test_json_filter() {
+    let query = Profile::all()
+        .filter(|p| {
+            eq!(
+                p.settings.json_extract::<String>("$.theme"),
+                "dark".to_string()
+            )
+        })
+        .filter(|p| bt!(p.address.json_extract::<u32>("$.zip_code"), 1000))
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
+
+#[test]
+fn test_json_map() {
+    let query = Profile::all()
+        .map(|p| {
+            (
+                p.address.json_extract::<String>("$.city"),
+                p.extra.json_extract::<Option<i64>>("$.visits[0]"),
+            )
+        })
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
+
+#[test]
+fn test_json_extract_tags() {
+    let query = Profile::all()
+        .map(|p| {
+            let previous: ExprViewBoxWithTag<Option<String>, TagList2<OrdViewTag, WindowViewTag>> =
+                window()
+                    .order_by(p.id.clone().asc())
+                    .lag(p.settings, 1)
+                    .json_extract::<Option<String>>("$.theme");
+            (p.id, previous)
+        })
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
+
+#[test]
+fn test_json_sort() {
+    let query = Profile::all()
+        .sort(|p| p.settings.json_extract::<Option<String>>("$.theme").asc())
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
+
+#[test]
+fn test_json_dialect() {
+    let query = Profile::all()
+        .filter(|p| {
+            eq!(
+                p.settings.json_extract::<String>("$.theme"),
+                "dark".to_string()
+            )
+        })
+        .generate_query_for::<MySql>();
+
+    let rendered = render_all(&query);
+
+    assert!(rendered[0]
+        .contains("CAST( JSON_UNQUOTE( JSON_EXTRACT ( `profile_1` . `settings` , ? ) ) AS CHAR )"));
+    assert!(rendered[1].contains(
+        "CAST( ( JSONB_PATH_QUERY_FIRST( CAST( \"profile_1\" . \"settings\" AS JSONB) , \
+        CAST( $1 AS JSONPATH) ) #>> '{}' ) AS TEXT )"
+    ));
+    assert!(
+        rendered[2].contains("CAST( JSON_EXTRACT ( \"profile_1\" . \"settings\" , ?1 ) AS TEXT )")
+    );
+}
+
+#[test]
+fn test_json_wrapper() {
+    let definition = Delivery::definition();
+    let types: Vec<_> = definition.columns().map(|c| (c.ty, c.optional)).collect();
+    assert_eq!(
+        types[1..],
+        [(DatabaseType::Json, false), (DatabaseType::Json, true)]
+    );
+
+    let query = Delivery::all()
+        .filter(|d| {
+            eq!(
+                d.address.json_extract::<String>("$.city"),
+                "Tokyo".to_string()
+            )
+        })
+        .map(|d| d.fallback.json_extract::<Option<String>>("$.zip_code"))
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
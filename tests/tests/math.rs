@@ -0,0 +1,93 @@
+use sqlx::MySql;
+use sqlx::types::Decimal;
+
+use yukino::prelude::*;
+use yukino::query_builder::{Alias, QueryBuildState, SqliteDialect, ToSql};
+use yukino::view::{EntityView, ExprViewBoxWithTag};
+use yukino_tests::*;
+
+#[test]
+pub fn test_math_functions() {
+    let alias = Alias {
+        name: "b".to_string(),
+    };
+
+    let view = FooView::pure(&alias);
+
+    cmp_view(view.int.clone().abs(), "CAST(Abs(b.int) AS Integer)");
+    cmp_view(
+        view.double.clone().round(2),
+        "CAST(Round(b.double, 2) AS Double)",
+    );
+    cmp_view(
+        view.decimal.clone().floor(),
+        "CAST(Floor(b.decimal) AS Decimal)",
+    );
+    cmp_view(view.long.clone().ceil(), "CAST(Ceil(b.long) AS BigInteger)");
+    cmp_view(
+        view.int.clone().pow(view.int.clone()),
+        "CAST(Power(b.int, b.int) AS Double)",
+    );
+    cmp_view(view.u_long.clone().sqrt(), "CAST(Sqrt(b.u_long) AS Double)");
+    cmp_view(
+        view.int.clone().greatest(view.short.clone().cast::<i32>()),
+        "Greatest(b.int, CAST(b.short AS Integer))",
+    );
+    cmp_view(view.int.clone().least(0), "Least(b.int, 0)");
+    cmp_view(
+        view.u_long.clone().cast::<Decimal>(),
+        "CAST(b.u_long AS Decimal)",
+    );
+    cmp_view(view.int.clone().cast::<String>(), "CAST(b.int AS String)");
+
+    let optional: ExprViewBoxWithTag<Option<String>, _> = view.optional.clone().cast::<String>();
+    cmp_view(optional, "CAST(b.optional AS String)");
+}
+
+#[test]
+pub fn test_math_query() {
+    let query = Foo::all()
+        .filter(|f| bt!(f.double.clone().sqrt(), 2.0))
+        .map(|f| (f.int.abs(), f.decimal.round(2)))
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let query = Foo::all()
+        .fold(|f| f.int.average().unwrap_or(Decimal::ZERO).ceil())
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
+
+#[test]
+pub fn test_math_dialect() {
+    let query = Foo::all()
+        .filter(|f| bt!(f.decimal.clone().round(1), f.decimal))
+        .map(|f| {
+            (
+                (f.double.clone() * 2.0).floor(),
+                f.int.greatest(0).cast::<String>(),
+            )
+        })
+        .generate_query_for::<MySql>();
+
+    let rendered = render_all(&query);
+
+    assert!(rendered[0].contains("CAST( GREATEST ( `foo_1` . `int` , ? ) AS CHAR )"));
+    assert!(rendered[0].contains("ROUND ( `foo_1` . `decimal` , ? )"));
+    assert!(rendered[1].contains("ROUND( CAST( \"foo_1\" . \"decimal\" AS NUMERIC) , $3 )"));
+    assert!(rendered[2].contains(
+        "( CAST( ( \"foo_1\" . \"double\" * ?1 ) AS INTEGER) - ( ( \"foo_1\" . \"double\" * ?1 ) \
+         < CAST( ( \"foo_1\" . \"double\" * ?1 ) AS INTEGER) ) )"
+    ));
+    assert!(rendered[2].contains("MAX ( \"foo_1\" . \"int\" , ?2 )"));
+
+    let query = Foo::all().map(|f| f.double.sqrt()).generate_query_for::<MySql>();
+    let mut state = QueryBuildState::create(&SqliteDialect);
+    assert!(query.to_sql(&mut state).is_err());
+
+    let query = Foo::all().map(|f| f.int.pow(2)).generate_query_for::<MySql>();
+    let mut state = QueryBuildState::create(&SqliteDialect);
+    assert!(query.to_sql(&mut state).is_err());
+}
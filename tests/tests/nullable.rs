@@ -0,0 +1,55 @@
+use sqlx::MySql;
+use sqlx::types::Decimal;
+
+use yukino::prelude::*;
+use yukino::query_builder::{Alias, MySqlDialect, QueryBuildState, ToSql};
+use yukino::view::EntityView;
+use yukino_tests::*;
+
+#[test]
+pub fn test_nullable() {
+    let alias = Alias {
+        name: "b".to_string(),
+    };
+
+    let view = FooView::pure(&alias);
+
+    cmp_view(view.optional.clone().is_null(), "b.optional IS NULL");
+    cmp_view(
+        view.optional.clone().is_not_null(),
+        "b.optional IS NOT NULL",
+    );
+    cmp_view(
+        view.optional.clone().unwrap_or(0),
+        "Coalesce(b.optional, 0)",
+    );
+    cmp_view(
+        coalesce(view.optional.clone(), view.u_int.clone()),
+        "Coalesce(b.optional, b.u_int)",
+    );
+    cmp_view(
+        view.optional.clone().if_null(view.u_int.clone()),
+        "Coalesce(b.optional, b.u_int)",
+    );
+}
+
+#[test]
+pub fn test_nullable_query() {
+    let query = Foo::all()
+        .filter(|f| f.optional.clone().is_null())
+        .map(|f| f.optional_decimal.unwrap_or(Decimal::ZERO))
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let query = Foo::all()
+        .fold(|f| f.int.average().unwrap_or(Decimal::ZERO))
+        .generate_query_for::<MySql>();
+
+    let mut state = QueryBuildState::create(&MySqlDialect);
+    query.to_sql(&mut state).unwrap();
+
+    assert!(state
+        .to_string()
+        .contains("COALESCE ( AVG ( `foo_1` . `int` ) , ? )"));
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "pgsql")]
+
+use std::env;
+
+use sqlx::{Executor, PgPool};
+
+use yukino::prelude::*;
+use yukino::query_builder::{CreateTable, DropTable, PostgresDialect, QueryBuildState, ToSql};
+use yukino::YukinoEntity;
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+pub struct UnsignedValue {
+    #[id]
+    pub id: u32,
+    pub small: u16,
+    pub big: u64,
+    pub optional: Option<u64>,
+}
+
+fn render<Q: ToSql>(query: &Q) -> String {
+    let mut state = QueryBuildState::create(&PostgresDialect);
+    query.to_sql(&mut state).unwrap();
+    state.to_string()
+}
+
+// Tests against a live database are skipped unless `YUKINO_PGSQL_URL` is set, run them with
+// `cargo test --features pgsql --test pgsql`
+async fn connect() -> Option<PgPool> {
+    let url = env::var("YUKINO_PGSQL_URL").ok()?;
+
+    Some(PgPool::connect(&url).await.unwrap())
+}
+
+#[tokio::test]
+async fn test_unsigned_round_trip() {
+    let pool = match connect().await {
+        Some(pool) => pool,
+        None => return,
+    };
+
+    let definition = UnsignedValue::definition();
+    let mut drop = DropTable::from_definition(&definition);
+    pool.execute(render(drop.if_exists()).as_str())
+        .await
+        .unwrap();
+    pool.execute(render(&CreateTable::from_definition(&definition)).as_str())
+        .await
+        .unwrap();
+
+    let values = vec![
+        UnsignedValue {
+            id: 7,
+            small: 1,
+            big: 42,
+            optional: None,
+        },
+        UnsignedValue {
+            id: u32::MAX,
+            small: u16::MAX,
+            big: u64::MAX,
+            optional: Some(u64::MAX),
+        },
+    ];
+    values.clone().insert_all().exec(&pool).await.unwrap();
+
+    let result: Vec<UnsignedValue> = UnsignedValue::all()
+        .exec(&pool)
+        .await
+        .unwrap()
+        .try_collect()
+        .unwrap();
+    assert_eq!(result, values);
+
+    let big: Vec<u64> = UnsignedValue::all()
+        .filter(|v| eq!(v.id, u32::MAX))
+        .map(|v| v.big)
+        .exec(&pool)
+        .await
+        .unwrap()
+        .try_collect()
+        .unwrap();
+    assert_eq!(big, vec![u64::MAX]);
+
+    // grouped expressions carrying arguments must not be bound again in GROUP BY
+    let mut buckets: Vec<u32> = UnsignedValue::all()
+        .group_by(|v| case().when(bte!(v.small, 100u16), 100u32).otherwise(0u32))
+        .map(|bucket| bucket)
+        .exec(&pool)
+        .await
+        .unwrap()
+        .try_collect()
+        .unwrap();
+    buckets.sort_unstable();
+    assert_eq!(buckets, vec![0, 100]);
+
+    // nor in HAVING and ORDER BY
+    let buckets: Vec<u32> = UnsignedValue::all()
+        .group_by(|v| case().when(bte!(v.small, 100u16), 100u32).otherwise(0u32))
+        .filter(|bucket| bte!(bucket, 0u32))
+        .sort(|bucket| bucket.desc())
+        .map(|bucket| bucket)
+        .exec(&pool)
+        .await
+        .unwrap()
+        .try_collect()
+        .unwrap();
+    assert_eq!(buckets, vec![100, 0]);
+}
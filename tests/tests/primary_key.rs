@@ -1,3 +1,5 @@
+use sqlx::MySql;
+
 use yukino::prelude::*;
 use yukino::WithPrimaryKey;
 use yukino_tests::*;
@@ -6,13 +8,24 @@ use yukino_tests::*;
 fn test_parse_primary_key() {
     let foo = create_foo();
 
-    assert_eq!(Foo::primary_key_name(), "id");
-    assert_eq!(*foo.primary_key(), 114514);
+    assert_eq!(Foo::primary_key_columns(), ["id"]);
+    assert_eq!(foo.primary_key(), 114514);
+}
+
+#[test]
+fn test_composite_primary_key() {
+    let foo_tag = FooTag {
+        foo_id: 1,
+        tag_id: 2,
+    };
+
+    assert_eq!(FooTag::primary_key_columns(), ["foo_id", "tag_id"]);
+    assert_eq!(foo_tag.primary_key(), (1, 2));
 }
 
 #[test]
 fn test_get() {
-    let query = Foo::get(114514).generate_query();
+    let query = Foo::get(114514).generate_query_for::<MySql>();
 
     println!("{}", query)
 }
@@ -21,7 +34,26 @@ fn test_get() {
 fn test_delete() {
     let foo = create_foo();
 
-    let query = foo.delete().generate_query();
+    let query = foo.delete().generate_query_for::<MySql>();
+
+    println!("{}", query)
+}
+
+#[test]
+fn test_get_composite() {
+    let query = FooTag::get((1, 2)).generate_query_for::<MySql>();
+
+    println!("{}", query)
+}
+
+#[test]
+fn test_delete_composite() {
+    let foo_tag = FooTag {
+        foo_id: 1,
+        tag_id: 2,
+    };
+
+    let query = foo_tag.delete().generate_query_for::<MySql>();
 
     println!("{}", query)
 }
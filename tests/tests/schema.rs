@@ -0,0 +1,147 @@
+use yukino::{EntityDefinition, IndexDefinition, YukinoEntity};
+use yukino::migration::{ColumnSchema, DatabaseSchema, Migration, TableSchema};
+use yukino::query_builder::{CreateTable, Dialect, DropTable, MySqlDialect};
+use yukino_tests::*;
+
+#[test]
+fn test_create_table() {
+    let foo = CreateTable::from_definition(&Foo::definition());
+    println!("{}", foo);
+
+    let bar = CreateTable::from_definition(&Bar::definition()).to_string();
+    println!("{}", bar);
+    assert!(bar.contains("FOREIGN KEY ( `foo_id` ) REFERENCES `foo` ( `id` )"));
+}
+
+#[test]
+fn test_drop_table() {
+    println!(
+        "{}",
+        DropTable::from_definition(&Foo::definition()).if_exists()
+    );
+}
+
+// Schema of the table of `definition` as loaded from a MySQL database
+fn schema_of(definition: &EntityDefinition) -> DatabaseSchema {
+    let table = TableSchema {
+        columns: definition
+            .columns()
+            .map(|column| ColumnSchema {
+                name: column.name.clone(),
+                ty: MySqlDialect.column_type(&column.ty).to_string(),
+                optional: column.optional,
+            })
+            .collect(),
+        indexes: definition.indexes.clone(),
+    };
+
+    let mut schema = DatabaseSchema::default();
+    schema.tables.insert(definition.table_name.clone(), table);
+
+    schema
+}
+
+#[test]
+fn test_migration_diff() {
+    let migration = Migration::diff(
+        &[Bar::definition(), Foo::definition()],
+        &DatabaseSchema::default(),
+        &MySqlDialect,
+    )
+    .unwrap();
+
+    let up = migration.up();
+    println!("{:#?}", up);
+    assert!(up[0].starts_with("CREATE TABLE `foo`"));
+    assert!(up[1].starts_with("CREATE TABLE `bar`"));
+    assert_eq!(
+        migration.down().unwrap(),
+        vec!["DROP TABLE `bar` ;", "DROP TABLE `foo` ;"]
+    );
+}
+
+#[test]
+fn test_migration_diff_unchanged() {
+    let schema = schema_of(&Foo::definition());
+    let migration = Migration::diff(&[Foo::definition()], &schema, &MySqlDialect).unwrap();
+
+    assert!(migration.is_empty());
+}
+
+#[test]
+fn test_migration_diff_columns() {
+    let definition = Foo::definition();
+    let mut schema = schema_of(&definition);
+    let table = schema.tables.get_mut("foo").unwrap();
+    table.columns.retain(|column| column.name != "string");
+    table
+        .columns
+        .iter_mut()
+        .find(|column| column.name == "int")
+        .unwrap()
+        .ty = "bigint".to_string();
+    table.columns.push(ColumnSchema {
+        name: "legacy".to_string(),
+        ty: "int".to_string(),
+        optional: true,
+    });
+
+    let migration = Migration::diff(&[definition], &schema, &MySqlDialect).unwrap();
+
+    println!("{:#?}", migration);
+    assert_eq!(
+        migration.up(),
+        vec![
+            "ALTER TABLE `foo` MODIFY COLUMN `int` INT NOT NULL ;",
+            "ALTER TABLE `foo` ADD COLUMN `string` VARCHAR(255) NOT NULL ;",
+            "ALTER TABLE `foo` DROP COLUMN `legacy` ;",
+        ]
+    );
+    assert_eq!(
+        migration.down().unwrap(),
+        vec![
+            "ALTER TABLE `foo` ADD COLUMN `legacy` INT ;",
+            "ALTER TABLE `foo` DROP COLUMN `string` ;",
+            "ALTER TABLE `foo` MODIFY COLUMN `int` BIGINT NOT NULL ;",
+        ]
+    );
+}
+
+#[test]
+fn test_migration_diff_indexes() {
+    let mut definition = Foo::definition();
+    let mut schema = schema_of(&definition);
+    schema
+        .tables
+        .get_mut("foo")
+        .unwrap()
+        .indexes
+        .push(IndexDefinition {
+            name: "foo_string_index".to_string(),
+            columns: vec!["string".to_string()],
+            unique: false,
+        });
+    definition.indexes.push(IndexDefinition {
+        name: "foo_int_unique".to_string(),
+        columns: vec!["int".to_string()],
+        unique: true,
+    });
+
+    let migration = Migration::diff(&[definition], &schema, &MySqlDialect).unwrap();
+
+    println!("{:#?}", migration);
+    assert_eq!(
+        migration.up(),
+        vec![
+            "DROP INDEX `foo_string_index` ON `foo` ;",
+            "CREATE UNIQUE INDEX `foo_int_unique` ON `foo` ( `int` );",
+        ]
+    );
+    assert_eq!(
+        migration.down().unwrap(),
+        vec![
+            "DROP INDEX `foo_int_unique` ON `foo` ;",
+            "CREATE INDEX `foo_string_index` ON `foo` ( `string` );",
+        ]
+    );
+}
@@ -1,10 +1,12 @@
+use sqlx::MySql;
+
 use yukino::prelude::*;
 use yukino::view::TupleExprView;
 use yukino_tests::*;
 
 #[test]
 fn test_filter_map() {
-    let query = Foo::all().filter(|b| lt!(b.int, 114514)).generate_query();
+    let query = Foo::all().filter(|b| lt!(b.int, 114514)).generate_query_for::<MySql>();
 
     println!("{}", query);
 }
@@ -15,7 +17,7 @@ fn test_fold() {
         .filter(|b| lt!(b.int, 114514))
         .filter(|b| bt!(b.int, 1919))
         .fold(|b| b.int.average())
-        .generate_query();
+        .generate_query_for::<MySql>();
 
     println!("{}", query);
 }
@@ -28,7 +30,7 @@ fn test_group() {
         .group_by(|b| (b.int, b.short))
         .filter(|(a, _)| eq!(a, 910))
         .fold(|(a, b)| (a.average(), b.average()))
-        .generate_query();
+        .generate_query_for::<MySql>();
 
     println!("{}", query);
 }
@@ -38,7 +40,7 @@ fn test_order_by() {
     let query = Foo::all()
         .filter(|b| lt!(b.int, 114514))
         .sort(|b| b.int.asc())
-        .generate_query();
+        .generate_query_for::<MySql>();
 
     println!("{}", query);
 }
@@ -51,7 +53,7 @@ fn test_group_order_by() {
         .group_by(|b| (b.int, b.short))
         .filter(|(a, _)| eq!(a, 910))
         .sort(|(a, b)| (a.asc(), b.desc()))
-        .generate_query();
+        .generate_query_for::<MySql>();
 
     println!("{}", query);
 }
@@ -65,7 +67,7 @@ fn test_map() {
         .filter(|(a, _)| eq!(a, 910))
         .sort(|(a, b)| (a.asc(), b.desc()))
         .map(|(a, b)| (a, b))
-        .generate_query();
+        .generate_query_for::<MySql>();
 
     println!("{}", query);
 }
@@ -83,7 +85,7 @@ fn test_group_fold_map() {
             )
         })
         .map(|(a, b), (c, _)| make_tuple!(a, b, c))
-        .generate_query();
+        .generate_query_for::<MySql>();
 
     println!("{}", query);
 }
@@ -0,0 +1,235 @@
+#![cfg(feature = "sqlite")]
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use sqlx::types::time::{Date, PrimitiveDateTime, Time};
+use sqlx::types::Decimal;
+use sqlx::{Executor, SqlitePool};
+
+use yukino::prelude::*;
+use yukino::query_builder::{CreateTable, QueryBuildState, SqliteDialect, ToSql};
+use yukino::{Json, YukinoEntity};
+use yukino_tests::Address;
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+pub struct TextValue {
+    #[id]
+    pub id: u32,
+    pub small: u16,
+    pub big: u64,
+    pub decimal: sqlx::types::Decimal,
+    pub date: sqlx::types::time::Date,
+    pub time: sqlx::types::time::Time,
+    pub datetime: sqlx::types::time::PrimitiveDateTime,
+    pub optional: Option<u64>,
+    pub address: Json<Address>,
+    pub fallback: Option<Json<Address>>,
+}
+
+#[derive(Entity, Clone, Debug)]
+pub struct Shelf {
+    #[id]
+    pub id: u32,
+    pub slots: Json<HashMap<(u32, u32), String>>,
+}
+
+fn render<Q: ToSql>(query: &Q) -> String {
+    let mut state = QueryBuildState::create(&SqliteDialect);
+    query.to_sql(&mut state).unwrap();
+    state.to_string()
+}
+
+// Run with `cargo test --features sqlite --test sqlite`
+async fn prepare() -> (SqlitePool, Vec<TextValue>) {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    pool.execute(render(&CreateTable::from_definition(&TextValue::definition())).as_str())
+        .await
+        .unwrap();
+
+    let address = Json(Address {
+        city: "Tokyo".to_string(),
+        zip_code: "114-0514".to_string(),
+    });
+    let date = Date::try_from_ymd(2022, 2, 22).unwrap();
+    let time = Time::try_from_hms(22, 2, 22).unwrap();
+    let values = vec![
+        TextValue {
+            id: 1,
+            small: 7,
+            big: 10,
+            decimal: Decimal::from_str("114.514").unwrap(),
+            date,
+            time,
+            datetime: PrimitiveDateTime::new(date, time),
+            optional: None,
+            address: address.clone(),
+            fallback: None,
+        },
+        TextValue {
+            id: 2,
+            small: 6,
+            big: 20,
+            decimal: Decimal::from_str("-1919.810").unwrap(),
+            date,
+            time,
+            datetime: PrimitiveDateTime::new(date, time),
+            optional: Some(i64::MAX as u64),
+            address: address.clone(),
+            fallback: Some(address.clone()),
+        },
+        TextValue {
+            id: 3,
+            small: 14,
+            big: 30,
+            decimal: Decimal::ZERO,
+            date,
+            time,
+            datetime: PrimitiveDateTime::new(date, time),
+            optional: Some(0),
+            address,
+            fallback: None,
+        },
+    ];
+    values.clone().insert_all().exec(&pool).await.unwrap();
+
+    (pool, values)
+}
+
+#[tokio::test]
+async fn test_text_value_round_trip() {
+    let (pool, values) = prepare().await;
+
+    let result: Vec<TextValue> = TextValue::all()
+        .sort(|v| v.id.asc())
+        .exec(&pool)
+        .await
+        .unwrap()
+        .try_collect()
+        .unwrap();
+
+    assert_eq!(result, values);
+}
+
+#[tokio::test]
+async fn test_subquery_fn_rewrite() {
+    let (pool, _) = prepare().await;
+
+    let any: Vec<u32> = TextValue::all()
+        .filter(|v| bt!(v.big, TextValue::all().map(|o| o.big).any()))
+        .sort(|v| v.id.asc())
+        .map(|v| v.id)
+        .exec(&pool)
+        .await
+        .unwrap()
+        .try_collect()
+        .unwrap();
+    assert_eq!(any, vec![2, 3]);
+
+    let all: Vec<u32> = TextValue::all()
+        .filter(|v| bte!(v.big, TextValue::all().map(|o| o.big).all()))
+        .map(|v| v.id)
+        .exec(&pool)
+        .await
+        .unwrap()
+        .try_collect()
+        .unwrap();
+    assert_eq!(all, vec![3]);
+
+    // ALL of no rows is true
+    let empty: Vec<u32> = TextValue::all()
+        .filter(|v| {
+            bt!(
+                v.big,
+                TextValue::all()
+                    .filter(|o| lt!(o.big, 0))
+                    .map(|o| o.big)
+                    .all()
+            )
+        })
+        .map(|v| v.id)
+        .exec(&pool)
+        .await
+        .unwrap()
+        .try_collect()
+        .unwrap();
+    assert_eq!(empty.len(), 3);
+}
+
+#[tokio::test]
+async fn test_bit_aggregate_emulation() {
+    let (pool, _) = prepare().await;
+
+    let (and, or) = TextValue::all()
+        .fold(|v| (v.small.clone().bit_and(), v.small.bit_or()))
+        .exec(&pool)
+        .await
+        .unwrap();
+    assert_eq!((and, or), (Some(6), Some(15)));
+
+    let xor = TextValue::all()
+        .fold(|v| v.small.bit_xor())
+        .exec(&pool)
+        .await
+        .unwrap();
+    assert_eq!(xor, Some(15));
+}
+
+#[tokio::test]
+async fn test_unsupported_function_error() {
+    let (pool, _) = prepare().await;
+
+    let result = TextValue::all().map(|v| v.small.sqrt()).exec(&pool).await;
+    assert!(matches!(result, Err(sqlx::Error::Configuration(_))));
+}
+
+#[tokio::test]
+async fn test_u64_out_of_range_error() {
+    let (pool, values) = prepare().await;
+
+    let mut value = values[0].clone();
+    value.id = 4;
+    value.big = u64::MAX;
+    let result = value.insert().exec(&pool).await;
+    assert!(matches!(result, Err(sqlx::Error::Configuration(_))));
+
+    let result = TextValue::all()
+        .filter(|v| eq!(v.big, u64::MAX))
+        .map(|v| v.id)
+        .exec(&pool)
+        .await;
+    assert!(matches!(result, Err(sqlx::Error::Configuration(_))));
+}
+
+#[tokio::test]
+async fn test_json_serialize_error() {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    pool.execute(render(&CreateTable::from_definition(&Shelf::definition())).as_str())
+        .await
+        .unwrap();
+
+    // JSON objects cannot have tuple keys
+    let shelf = Shelf {
+        id: 1,
+        slots: Json(HashMap::from([((0, 1), "book".to_string())])),
+    };
+    let result = shelf.insert().exec(&pool).await;
+    assert!(matches!(result, Err(sqlx::Error::Configuration(_))));
+}
+
+#[tokio::test]
+async fn test_grouped_expression_reference() {
+    let (pool, _) = prepare().await;
+
+    let buckets: Vec<u16> = TextValue::all()
+        .group_by(|v| case().when(bte!(v.small, 10u16), 10u16).otherwise(0u16))
+        .filter(|bucket| bte!(bucket, 0u16))
+        .sort(|bucket| bucket.desc())
+        .map(|bucket| bucket)
+        .exec(&pool)
+        .await
+        .unwrap()
+        .try_collect()
+        .unwrap();
+    assert_eq!(buckets, vec![10, 0]);
+}
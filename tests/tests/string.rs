@@ -0,0 +1,101 @@
+use sqlx::MySql;
+
+use yukino::prelude::*;
+use yukino::query_builder::Alias;
+use yukino::view::EntityView;
+use yukino_tests::*;
+
+#[test]
+pub fn test_like() {
+    let alias = Alias {
+        name: "b".to_string(),
+    };
+
+    let view = FooView::pure(&alias);
+
+    cmp_view(view.string.clone().like("a_b%"), "b.string LIKE \"a_b%\"");
+    cmp_view(
+        view.string.clone().contains("100%"),
+        "b.string LIKE \"%100\\%%\"",
+    );
+    cmp_view(
+        view.string.clone().starts_with("snake_case"),
+        "b.string LIKE \"snake\\_case%\"",
+    );
+    cmp_view(
+        view.string.clone().ends_with("C:\\"),
+        "b.string LIKE \"%C:\\\\\"",
+    );
+}
+
+#[test]
+pub fn test_like_nullable() {
+    let query = Shop::all()
+        .filter(|s| s.address.note.starts_with("gate_"))
+        .filter(|s| s.billing_address.note.contains("back"))
+        .map(|s| s.name)
+        .generate_query_for::<MySql>();
+
+    let rendered = render_all(&query);
+
+    assert!(rendered[1].contains("\"shop_1\" . \"address_note\" LIKE $1 ESCAPE '\\'"));
+    assert!(rendered[1].contains("\"shop_1\" . \"billing_note\" LIKE $2 ESCAPE '\\'"));
+}
+
+#[test]
+pub fn test_string_functions() {
+    let alias = Alias {
+        name: "b".to_string(),
+    };
+
+    let view = FooView::pure(&alias);
+
+    cmp_view(view.string.clone().lower(), "Lower(b.string)");
+    cmp_view(view.string.clone().upper(), "Upper(b.string)");
+    cmp_view(view.string.clone().trim(), "Trim(b.string)");
+    cmp_view(
+        view.string.clone().char_length(),
+        "CAST(CharLength(b.string) AS BigInteger)",
+    );
+    cmp_view(
+        view.string.clone().concat(view.string.clone()),
+        "Concat(b.string, b.string)",
+    );
+    cmp_view(
+        view.string.clone().concat("!".to_string()),
+        "Concat(b.string, \"!\")",
+    );
+    cmp_view(
+        view.string.clone().substring(1, 3),
+        "Substring(b.string, 1, 3)",
+    );
+}
+
+#[test]
+pub fn test_string_query() {
+    let query = Foo::all()
+        .filter(|f| f.string.clone().lower().contains("yukino"))
+        .map(|f| (f.string.clone().upper(), f.string.char_length()))
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
+
+#[test]
+pub fn test_string_dialect() {
+    let query = Foo::all()
+        .filter(|f| f.string.clone().starts_with("a"))
+        .map(|f| f.string.clone().concat(f.string).substring(2, 4))
+        .generate_query_for::<MySql>();
+
+    let rendered = render_all(&query);
+
+    assert!(rendered[0].contains("( `foo_1` . `string` LIKE ? ESCAPE '\\\\' )"));
+    assert!(rendered[0]
+        .contains("SUBSTRING ( CONCAT ( `foo_1` . `string` , `foo_1` . `string` ) , ? , ? )"));
+    assert!(rendered[1].contains("( \"foo_1\" . \"string\" LIKE $3 ESCAPE '\\' )"));
+    assert!(rendered[1]
+        .contains("SUBSTRING ( ( \"foo_1\" . \"string\" || \"foo_1\" . \"string\" ) , $1 , $2 )"));
+    assert!(rendered[2]
+        .contains("SUBSTR ( ( \"foo_1\" . \"string\" || \"foo_1\" . \"string\" ) , ?1 , ?2 )"));
+}
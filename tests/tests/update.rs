@@ -1,3 +1,5 @@
+use sqlx::MySql;
+
 use yukino::prelude::*;
 use yukino_tests::*;
 
@@ -12,7 +14,28 @@ fn test_update() {
         .set_by(foo::long, |l| l + 1)
         .sort(|f| f.id.asc())
         .limit(20)
-        .generate_query();
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
+
+#[test]
+fn test_update_binary() {
+    let query = Foo::all()
+        .filter(|b| eq!(b.id, 114514))
+        .update()
+        .set(foo::binary, vec![1, 9, 1, 9])
+        .set(foo::optional_binary, None)
+        .generate_query_for::<MySql>();
+
+    let rendered = render_all(&query);
 
     println!("{}", query);
+    // assignments are not ordered, so the indexes of placeholders are not checked
+    assert!(rendered[0].contains("`binary` = ?"));
+    assert!(rendered[0].contains("`optional_binary` = ?"));
+    assert!(rendered[1].contains("\"binary\" = $"));
+    assert!(rendered[1].contains("\"optional_binary\" = $"));
+    assert!(rendered[2].contains("\"binary\" = ?"));
+    assert!(rendered[2].contains("\"optional_binary\" = ?"));
 }
@@ -0,0 +1,103 @@
+use sqlx::MySql;
+
+use yukino::prelude::*;
+use yukino::query_builder::Alias;
+use yukino::view::EntityView;
+use yukino_tests::*;
+
+#[test]
+pub fn test_window_functions() {
+    let alias = Alias {
+        name: "b".to_string(),
+    };
+
+    let view = FooView::pure(&alias);
+
+    cmp_view(
+        window()
+            .partition_by(view.int.clone())
+            .order_by(view.long.clone().desc())
+            .row_number(),
+        "CAST(RowNumber() OVER (PARTITION BY b.int ORDER BY b.long DESC) AS BigInteger)",
+    );
+    cmp_view(
+        window().order_by(view.long.clone().asc()).dense_rank(),
+        "CAST(DenseRank() OVER (ORDER BY b.long ASC) AS BigInteger)",
+    );
+    cmp_view(
+        window()
+            .order_by(view.long.clone().asc())
+            .lag(view.int.clone(), 1),
+        "Lag(b.int, 1) OVER (ORDER BY b.long ASC)",
+    );
+    cmp_view(
+        window()
+            .partition_by((view.int.clone(), view.boolean.clone()))
+            .first_value(view.string.clone()),
+        "FirstValue(b.string) OVER (PARTITION BY b.int, b.boolean)",
+    );
+    cmp_view(window().sum(view.long.clone()), "Sum(b.long) OVER ()");
+}
+
+#[test]
+pub fn test_window_query() {
+    let query = Foo::all()
+        .filter(|f| f.boolean.clone())
+        .map(|f| {
+            (
+                f.id.clone(),
+                window()
+                    .partition_by(f.int.clone())
+                    .order_by(f.long.clone().asc())
+                    .rank(),
+            )
+        })
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+
+    let query = Foo::all()
+        .sort(|f| f.long.clone().asc())
+        .map(|f| {
+            (
+                f.long.clone(),
+                window().order_by(f.long.clone().asc()).sum(f.u_int),
+            )
+        })
+        .generate_query_for::<MySql>();
+
+    println!("{}", query);
+}
+
+#[test]
+pub fn test_window_dialect() {
+    let query = Foo::all()
+        .map(|f| {
+            (
+                window()
+                    .partition_by(f.int.clone())
+                    .order_by((f.long.clone().asc(), f.id.clone().desc()))
+                    .row_number(),
+                window()
+                    .partition_by(f.int.clone())
+                    .order_by(f.long.clone().asc())
+                    .average(f.long.clone()),
+            )
+        })
+        .generate_query_for::<MySql>();
+
+    let rendered = render_all(&query);
+
+    assert!(rendered[0].contains(
+        "CAST( ROW_NUMBER ( ) OVER ( PARTITION BY `foo_1` . `int` \
+         ORDER BY `foo_1` . `long` ASC , `foo_1` . `id` DESC ) AS SIGNED )"
+    ));
+    assert!(rendered[1].contains(
+        "CAST( AVG ( \"foo_1\" . \"long\" ) OVER ( PARTITION BY \"foo_1\" . \"int\" \
+         ORDER BY \"foo_1\" . \"long\" ASC ) AS NUMERIC)"
+    ));
+    assert!(rendered[2].contains(
+        "AVG ( \"foo_1\" . \"long\" ) OVER ( PARTITION BY \"foo_1\" . \"int\" \
+         ORDER BY \"foo_1\" . \"long\" ASC )"
+    ));
+}